@@ -0,0 +1,144 @@
+//! Agent 结构化日志协议
+//!
+//! Agent 可以在 stdout 上输出单行 JSON 对象来上报结构化日志，例如：
+//!
+//! ```text
+//! {"level":"info","message":"3/10 stages cleared","node":"StageLoop","fields":{"cleared":3,"total":10}}
+//! ```
+//!
+//! 字段说明：
+//! - `level`: 日志级别，`trace` / `debug` / `info` / `warn`（或 `warning`）/ `error`，不区分大小写
+//! - `message`: 日志内容（`msg` 为别名）
+//! - `node`: 当前所在的 pipeline 节点（可选）
+//! - `fields`: 任意附加键值对（可选）
+//! - `image`: 附带的图片路径，相对路径基于 agent 工作目录（可选）
+//!
+//! 缺少 `level` 或 `message` 的行、以及无法解析为 JSON 对象的行都按普通文本处理，
+//! 保持原有的 `maa-agent-output` 行为。
+//!
+//! 反方向上，MXU 会在实例暂停 / 恢复时向 agent 的 stdin 写入单行 JSON 通知：
+//! `{"event":"pause"}` / `{"event":"resume"}`。暂停期间 MaaFramework 不会开始新的节点动作，
+//! 正在执行的自定义动作可以据此自行等待；不需要的 agent 可以忽略 stdin。
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::utils::normalize_path;
+
+/// Agent 日志级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AgentLogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl AgentLogLevel {
+    /// 从字符串解析日志级别（不区分大小写）
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "trace" => Some(Self::Trace),
+            "debug" => Some(Self::Debug),
+            "info" => Some(Self::Info),
+            "warn" | "warning" => Some(Self::Warn),
+            "error" | "err" => Some(Self::Error),
+            _ => None,
+        }
+    }
+
+    /// 小写名称，与序列化结果一致
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Trace => "trace",
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Error => "error",
+        }
+    }
+
+    /// 转换为 `log` crate 的级别，用于写入 MXU 日志
+    pub fn as_log_level(self) -> log::Level {
+        match self {
+            Self::Trace => log::Level::Trace,
+            Self::Debug => log::Level::Debug,
+            Self::Info => log::Level::Info,
+            Self::Warn => log::Level::Warn,
+            Self::Error => log::Level::Error,
+        }
+    }
+}
+
+/// 协议行的原始结构（仅用于反序列化）
+#[derive(Deserialize)]
+struct RawAgentLogLine {
+    level: String,
+    #[serde(alias = "msg")]
+    message: String,
+    #[serde(default)]
+    node: Option<String>,
+    #[serde(default)]
+    fields: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    image: Option<String>,
+}
+
+/// 解析后的结构化日志记录
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentLogRecord {
+    pub level: AgentLogLevel,
+    pub message: String,
+    pub node: Option<String>,
+    pub fields: serde_json::Map<String, serde_json::Value>,
+    pub image: Option<String>,
+}
+
+impl AgentLogRecord {
+    /// 生成写入 MXU 日志的单行文本，如 `[StageLoop] 3/10 stages cleared {"cleared":3}`
+    pub fn to_log_text(&self) -> String {
+        let mut text = match &self.node {
+            Some(node) => format!("[{}] {}", node, self.message),
+            None => self.message.clone(),
+        };
+        if !self.fields.is_empty() {
+            text.push(' ');
+            text.push_str(&serde_json::Value::Object(self.fields.clone()).to_string());
+        }
+        if let Some(image) = &self.image {
+            text.push_str(&format!(" (image: {})", image));
+        }
+        text
+    }
+}
+
+/// 尝试将一行 agent 输出解析为结构化日志
+///
+/// `cwd` 为 agent 的工作目录，用于将 `image` 中的相对路径解析为绝对路径。
+/// 返回 `None` 表示该行不属于协议格式，应按普通文本处理。
+pub fn parse_agent_log_line(line: &str, cwd: &Path) -> Option<AgentLogRecord> {
+    let trimmed = line.trim();
+    if !trimmed.starts_with('{') || !trimmed.ends_with('}') {
+        return None;
+    }
+
+    let raw: RawAgentLogLine = serde_json::from_str(trimmed).ok()?;
+    let level = AgentLogLevel::parse(&raw.level)?;
+
+    let image = raw
+        .image
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| normalize_path(&cwd.join(p).to_string_lossy()))
+        .map(|p| p.to_string_lossy().to_string());
+
+    Some(AgentLogRecord {
+        level,
+        message: raw.message,
+        node: raw.node.filter(|n| !n.is_empty()),
+        fields: raw.fields,
+        image,
+    })
+}
@@ -0,0 +1,52 @@
+//! MXU 内置动作查询命令
+//!
+//! 导出所有 MXU 内置 custom action / recognition 及插件动作的参数 JSON Schema，
+//! 供前端校验 `specialTasks.ts` 中的 `custom_action_param` 与后端定义是否一致；
+//! 以及插件列表与允许加载的插件设置。
+
+use super::types::{MxuActionSchema, PluginInfo};
+use crate::mxu_actions::plugin::{self, plugin_actions};
+use crate::mxu_actions::{builtin_actions, builtin_recognitions};
+
+/// 获取所有 MXU 内置动作、识别及插件动作的参数 Schema
+#[tauri::command]
+pub fn get_mxu_action_schemas() -> Vec<MxuActionSchema> {
+    let actions = builtin_actions().into_iter().map(|entry| MxuActionSchema {
+        name: entry.name.to_string(),
+        kind: "action".to_string(),
+        schema: (entry.param_schema)().to_value(),
+        plugin: None,
+        description: None,
+    });
+    let recognitions = builtin_recognitions()
+        .into_iter()
+        .map(|entry| MxuActionSchema {
+            name: entry.name.to_string(),
+            kind: "recognition".to_string(),
+            schema: (entry.param_schema)().to_value(),
+            plugin: None,
+            description: None,
+        });
+    let plugins = plugin_actions().iter().map(|action| MxuActionSchema {
+        name: action.name.clone(),
+        kind: "action".to_string(),
+        schema: action.schema.clone(),
+        plugin: Some(action.plugin_name().to_string()),
+        description: action.description.clone(),
+    });
+    actions.chain(recognitions).chain(plugins).collect()
+}
+
+/// 获取插件目录中的所有插件及其加载状态
+#[tauri::command]
+pub fn get_plugins() -> Vec<PluginInfo> {
+    plugin::list_plugins()
+}
+
+/// 设置允许加载的插件目录（由前端在加载配置和修改设置时同步）
+/// 首次同步时在后台加载允许的插件，之后的修改需重启生效
+#[tauri::command]
+pub fn set_allowed_plugins(plugins: Vec<String>) {
+    log::info!("Allowed plugins: {:?}", plugins);
+    plugin::set_allowed_plugins(plugins);
+}
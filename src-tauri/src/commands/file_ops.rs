@@ -1,9 +1,11 @@
 //! 文件操作命令
 //!
-//! 提供本地文件读取和路径检查功能
+//! 提供本地文件读取和路径检查功能，以及 MXU_FILE 动作允许访问的目录设置
 
-use log::debug;
+use log::{debug, warn};
+use once_cell::sync::Lazy;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
 use super::utils::{get_app_data_dir, get_exe_directory, normalize_path};
 
@@ -64,6 +66,50 @@ pub fn get_data_dir() -> Result<String, String> {
     Ok(data_dir.to_string_lossy().to_string())
 }
 
+/// 用户设置的 MXU_FILE 额外允许目录（程序目录与数据目录始终允许）
+static FILE_ACTION_ROOTS: Lazy<Mutex<Vec<String>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// 设置 MXU_FILE 动作额外允许访问的目录（由前端在加载配置和修改设置时同步）
+#[tauri::command]
+pub fn set_file_action_roots(roots: Vec<String>) {
+    let roots: Vec<String> = roots
+        .into_iter()
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty())
+        .collect();
+    log::info!("File action roots: {:?}", roots);
+    *FILE_ACTION_ROOTS.lock().unwrap_or_else(|e| e.into_inner()) = roots;
+}
+
+/// MXU_FILE 动作允许访问的全部根目录（已解析为真实路径，不存在或非绝对路径的目录会被忽略）
+pub fn file_action_roots() -> Result<Vec<PathBuf>, String> {
+    let mut candidates = vec![get_exe_directory()?, get_app_data_dir()?];
+    candidates.extend(
+        FILE_ACTION_ROOTS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(PathBuf::from),
+    );
+
+    let mut roots = Vec::new();
+    for root in candidates {
+        if !root.is_absolute() {
+            warn!("Ignoring relative file action root: {}", root.display());
+            continue;
+        }
+        match root.canonicalize() {
+            Ok(real) => {
+                if !roots.contains(&real) {
+                    roots.push(real);
+                }
+            }
+            Err(e) => warn!("Ignoring file action root {}: {}", root.display(), e),
+        }
+    }
+    Ok(roots)
+}
+
 /// 获取当前工作目录
 #[tauri::command]
 pub fn get_cwd() -> Result<String, String> {
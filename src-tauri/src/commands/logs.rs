@@ -0,0 +1,281 @@
+//! 日志管理命令
+//!
+//! 负责 agent 日志（mxu-agent-*.log）与后端日志（mxu-tauri*.log）的轮转和保留策略：
+//! - 单文件超过大小上限时轮转为带时间戳的新文件
+//! - 按保留天数、文件数量、总大小清理旧日志（启动时及每小时执行一次）
+
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+
+use super::types::{LogRetentionResult, LogRetentionSettings};
+use super::utils::{get_app_data_dir, get_logs_dir};
+
+/// 定期清理的间隔
+pub const LOG_RETENTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// 保留策略配置文件名（位于数据目录的 config 子目录）
+const SETTINGS_FILE_NAME: &str = "mxu-log-retention.json";
+
+/// 当前生效的保留策略（首次访问时从配置文件加载）
+static SETTINGS: Lazy<Mutex<LogRetentionSettings>> =
+    Lazy::new(|| Mutex::new(load_settings_from_disk()));
+
+/// 正在写入的日志文件，清理时跳过
+static ACTIVE_LOG_FILES: Lazy<Mutex<HashSet<PathBuf>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+fn settings_path() -> Result<PathBuf, String> {
+    Ok(get_app_data_dir()?.join("config").join(SETTINGS_FILE_NAME))
+}
+
+fn load_settings_from_disk() -> LogRetentionSettings {
+    let Ok(path) = settings_path() else {
+        return LogRetentionSettings::default();
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!(
+                "Invalid log retention settings {:?}: {}, using defaults",
+                path, e
+            );
+            LogRetentionSettings::default()
+        }),
+        Err(_) => LogRetentionSettings::default(),
+    }
+}
+
+/// 获取当前生效的保留策略
+pub fn current_settings() -> LogRetentionSettings {
+    SETTINGS
+        .lock()
+        .map(|s| s.clone())
+        .unwrap_or_else(|_| LogRetentionSettings::default())
+}
+
+/// 是否为受保留策略管理的日志文件
+fn is_managed_log_file(name: &str) -> bool {
+    name.ends_with(".log") && (name.starts_with("mxu-agent-") || name.starts_with("mxu-tauri"))
+}
+
+/// 按保留策略清理日志目录
+pub fn apply_log_retention(logs_dir: &Path, settings: &LogRetentionSettings) -> LogRetentionResult {
+    let mut result = LogRetentionResult::default();
+
+    let Ok(entries) = std::fs::read_dir(logs_dir) else {
+        return result;
+    };
+
+    let active = ACTIVE_LOG_FILES
+        .lock()
+        .map(|s| s.clone())
+        .unwrap_or_default();
+    // mxu-tauri.log 由日志插件持有，始终视为活动文件
+    let tauri_log = logs_dir.join("mxu-tauri.log");
+
+    // (路径, 修改时间, 大小, 是否活动)
+    let mut files: Vec<(PathBuf, SystemTime, u64, bool)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = path.file_name()?.to_string_lossy().to_string();
+            if !is_managed_log_file(&name) {
+                return None;
+            }
+            let meta = entry.metadata().ok()?;
+            if !meta.is_file() {
+                return None;
+            }
+            let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            let is_active = path == tauri_log || active.contains(&path);
+            Some((path, modified, meta.len(), is_active))
+        })
+        .collect();
+
+    // 最新的在前
+    files.sort_by_key(|f| std::cmp::Reverse(f.1));
+
+    let now = SystemTime::now();
+    let max_age = Duration::from_secs(u64::from(settings.max_age_days) * 24 * 60 * 60);
+    let max_total = settings.max_total_size_mb * 1024 * 1024;
+    let mut total: u64 = files.iter().map(|f| f.2).sum();
+    let mut kept = 0usize;
+    let mut to_delete = Vec::new();
+
+    for (path, modified, size, is_active) in &files {
+        if *is_active {
+            kept += 1;
+            continue;
+        }
+        let expired = settings.max_age_days > 0
+            && now.duration_since(*modified).unwrap_or_default() > max_age;
+        let over_count = settings.max_files > 0 && kept >= settings.max_files as usize;
+        if expired || over_count {
+            to_delete.push((path.clone(), *size));
+        } else {
+            kept += 1;
+        }
+    }
+
+    // 总大小超限时从最旧的开始删除
+    if max_total > 0 {
+        total -= to_delete.iter().map(|(_, size)| size).sum::<u64>();
+        for (path, _, size, is_active) in files.iter().rev() {
+            if total <= max_total {
+                break;
+            }
+            if *is_active || to_delete.iter().any(|(p, _)| p == path) {
+                continue;
+            }
+            to_delete.push((path.clone(), *size));
+            total -= size;
+        }
+    }
+
+    for (path, size) in to_delete {
+        match std::fs::remove_file(&path) {
+            Ok(()) => {
+                debug!("Removed old log file: {:?}", path);
+                result.deleted_files += 1;
+                result.freed_bytes += size;
+            }
+            Err(e) => {
+                warn!("Failed to remove old log file {:?}: {}", path, e);
+                result.failed_files += 1;
+            }
+        }
+    }
+
+    result
+}
+
+/// 使用当前策略清理默认日志目录，并输出汇总日志
+pub fn run_log_retention() -> LogRetentionResult {
+    let result = apply_log_retention(&get_logs_dir(), &current_settings());
+    if result.deleted_files > 0 || result.failed_files > 0 {
+        info!(
+            "Log retention: {} file(s) deleted ({} bytes freed), {} failed",
+            result.deleted_files, result.freed_bytes, result.failed_files
+        );
+    }
+    result
+}
+
+/// 超过大小上限时自动轮转的日志文件
+///
+/// 轮转时将当前文件重命名为 `<name>.<时间戳>.log`，再创建新的空文件
+pub struct RotatingLogFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_size: u64,
+}
+
+impl RotatingLogFile {
+    /// 打开（或创建）日志文件，`max_size` 为 0 表示不轮转
+    pub fn open(path: PathBuf, max_size: u64) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+        if let Ok(mut active) = ACTIVE_LOG_FILES.lock() {
+            active.insert(path.clone());
+        }
+        Ok(Self {
+            path,
+            file,
+            size,
+            max_size,
+        })
+    }
+
+    /// 写入一行，必要时先轮转
+    pub fn write_line(&mut self, line: &str) {
+        let len = line.len() as u64 + 1;
+        if self.max_size > 0 && self.size > 0 && self.size + len > self.max_size {
+            if let Err(e) = self.rotate() {
+                warn!("Failed to rotate log file {:?}: {}", self.path, e);
+            }
+        }
+        if writeln!(self.file, "{}", line).is_ok() {
+            self.size += len;
+        }
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        let _ = self.file.flush();
+        let stem = self
+            .path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        let mut rotated = self
+            .path
+            .with_file_name(format!("{}.{}.log", stem, timestamp));
+        let mut i = 1;
+        while rotated.exists() {
+            rotated = self
+                .path
+                .with_file_name(format!("{}.{}-{}.log", stem, timestamp, i));
+            i += 1;
+        }
+        std::fs::rename(&self.path, &rotated)?;
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = 0;
+        debug!("Rotated log file {:?} -> {:?}", self.path, rotated);
+        Ok(())
+    }
+}
+
+impl Drop for RotatingLogFile {
+    fn drop(&mut self) {
+        if let Ok(mut active) = ACTIVE_LOG_FILES.lock() {
+            active.remove(&self.path);
+        }
+    }
+}
+
+// ============================================================================
+// Tauri 命令
+// ============================================================================
+
+/// 获取日志保留策略
+#[tauri::command]
+pub fn get_log_retention_settings() -> LogRetentionSettings {
+    current_settings()
+}
+
+/// 保存日志保留策略并立即执行一次清理
+/// 注意：mxu-tauri 日志的单文件大小上限在下次启动后生效
+#[tauri::command]
+pub fn set_log_retention_settings(
+    settings: LogRetentionSettings,
+) -> Result<LogRetentionResult, String> {
+    info!("set_log_retention_settings: {:?}", settings);
+
+    let path = settings_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("无法创建目录 [{}]: {}", parent.display(), e))?;
+    }
+    let content = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+    std::fs::write(&path, content)
+        .map_err(|e| format!("保存日志保留策略失败 [{}]: {}", path.display(), e))?;
+
+    *SETTINGS.lock().map_err(|e| e.to_string())? = settings;
+
+    Ok(run_log_retention())
+}
+
+/// 立即按当前策略清理日志
+#[tauri::command]
+pub fn apply_log_retention_now() -> LogRetentionResult {
+    run_log_retention()
+}
@@ -3,144 +3,98 @@
 //! 提供 MaaFramework Agent 启动和管理功能
 
 use log::{debug, error, info, warn};
-use std::fs::OpenOptions;
-use std::io::{BufRead, BufReader, Write};
-use std::process::{Command, Stdio};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 
+use futures_util::future::try_join_all;
+use once_cell::sync::Lazy;
 use tauri::State;
 
+use crate::agent_log::parse_agent_log_line;
 use crate::maa_ffi::{
-    emit_agent_output, from_cstr, get_event_callback, to_cstring, MaaAgentClient, SendPtr,
-    MAA_INVALID_ID, MAA_LIBRARY,
+    emit_agent_log, emit_agent_output, emit_agent_progress, from_cstr, get_event_callback,
+    to_cstring, MaaAgentClient, MaaLibrary, SendPtr, MAA_INVALID_ID, MAA_LIBRARY,
 };
 
+use super::logs::{self, RotatingLogFile};
 use super::types::{AgentConfig, MaaState, TaskConfig};
 use super::utils::{get_logs_dir, normalize_path};
 
-/// 启动单个 Agent 子进程并完成连接
+/// 创建附加模式的 AgentClient
 ///
-/// 返回 `(agent_client_ptr, child_process)` 供调用方保存。
-async fn start_single_agent(
-    state: &Arc<MaaState>,
-    instance_id: &str,
+/// 指定 `tcp_port` 时使用固定 TCP 端口，否则使用 `identifier` 作为固定的 IPC 标识符，
+/// 外部启动的 agent（如在调试器中运行）以相同的地址连接即可。
+///
+/// MaaFramework 只在本机回环地址上监听，不支持直接指定远程主机；
+/// 远程机器上的 agent 需要先通过端口转发（如 `ssh -R <port>:127.0.0.1:<port>`）映射到本机端口。
+fn create_attach_client(
+    lib: &MaaLibrary,
     agent: &AgentConfig,
     agent_index: usize,
-    resource: &SendPtr<crate::maa_ffi::MaaResource>,
-    tasker: &SendPtr<crate::maa_ffi::MaaTasker>,
-    cwd: &str,
-    tcp_compat_mode: bool,
-) -> Result<(SendPtr<MaaAgentClient>, std::process::Child), String> {
-    info!("[agent#{}] Starting agent: {:?}", agent_index, agent);
+) -> Result<*mut MaaAgentClient, String> {
+    if let Some(port) = agent.tcp_port {
+        let create_tcp_fn = lib.maa_agent_client_create_tcp.ok_or_else(|| {
+            format!(
+                "Agent #{} attach over TCP requires MaaAgentClientCreateTcp, please update MaaFramework",
+                agent_index
+            )
+        })?;
+        debug!(
+            "[agent#{}] Attach mode, creating TCP agent client on port {}",
+            agent_index, port
+        );
+        return Ok(unsafe { create_tcp_fn(port) });
+    }
 
-    // 创建 AgentClient 并获取 socket_id
+    let identifier = agent
+        .identifier
+        .as_deref()
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .ok_or_else(|| {
+            format!(
+                "Agent #{} attach mode requires either identifier or tcp_port",
+                agent_index
+            )
+        })?;
     debug!(
-        "[agent#{}] Acquiring MAA_LIBRARY lock for agent creation...",
-        agent_index
+        "[agent#{}] Attach mode, creating agent client with identifier: {}",
+        agent_index, identifier
     );
-    let (agent_client, socket_id) = {
-        let guard = MAA_LIBRARY
-            .lock()
-            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-        debug!("[agent#{}] MAA_LIBRARY lock acquired", agent_index);
-        let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
-
-        // 根据 tcp_compat_mode 选择创建方式
-        let agent_client = if tcp_compat_mode {
-            if let Some(create_tcp_fn) = lib.maa_agent_client_create_tcp {
-                debug!(
-                    "[agent#{}] Using TCP compat mode, calling maa_agent_client_create_tcp...",
-                    agent_index
-                );
-                let client = unsafe { create_tcp_fn(0) };
-                debug!(
-                    "[agent#{}] maa_agent_client_create_tcp returned: {:?}",
-                    agent_index, client
-                );
-                client
-            } else {
-                warn!(
-                    "[agent#{}] TCP compat mode requested but MaaAgentClientCreateTcp not available, falling back to V2",
-                    agent_index
-                );
-                let client = unsafe { (lib.maa_agent_client_create_v2)(std::ptr::null()) };
-                debug!(
-                    "[agent#{}] maa_agent_client_create_v2 (fallback) returned: {:?}",
-                    agent_index, client
-                );
-                client
-            }
-        } else {
-            debug!(
-                "[agent#{}] Calling maa_agent_client_create_v2...",
-                agent_index
-            );
-            let client = unsafe { (lib.maa_agent_client_create_v2)(std::ptr::null()) };
-            debug!(
-                "[agent#{}] maa_agent_client_create_v2 returned: {:?}",
-                agent_index, client
-            );
-            client
-        };
 
-        if agent_client.is_null() {
-            error!(
-                "[agent#{}] Failed to create agent client (null pointer)",
+    let c_identifier = to_cstring(identifier);
+    unsafe {
+        let id_buffer = (lib.maa_string_buffer_create)();
+        if id_buffer.is_null() {
+            return Err(format!(
+                "Failed to create string buffer for agent #{}",
                 agent_index
-            );
-            return Err(format!("Failed to create agent client #{}", agent_index));
+            ));
         }
+        (lib.maa_string_buffer_set)(id_buffer, c_identifier.as_ptr());
+        let client = (lib.maa_agent_client_create_v2)(id_buffer);
+        (lib.maa_string_buffer_destroy)(id_buffer);
+        Ok(client)
+    }
+}
 
-        // 绑定资源
-        debug!(
-            "[agent#{}] Binding resource to agent client, resource ptr: {:?}",
-            agent_index,
-            resource.as_ptr()
-        );
-        unsafe {
-            (lib.maa_agent_client_bind_resource)(agent_client, resource.as_ptr());
-        }
-        debug!("[agent#{}] Resource bound to agent client", agent_index);
-
-        // 获取 socket identifier
-        debug!("[agent#{}] Getting socket identifier...", agent_index);
-        let socket_id = unsafe {
-            let id_buffer = (lib.maa_string_buffer_create)();
-            if id_buffer.is_null() {
-                error!(
-                    "[agent#{}] Failed to create string buffer (null pointer)",
-                    agent_index
-                );
-                (lib.maa_agent_client_destroy)(agent_client);
-                return Err(format!(
-                    "Failed to create string buffer for agent #{}",
-                    agent_index
-                ));
-            }
-
-            let success = (lib.maa_agent_client_identifier)(agent_client, id_buffer);
-            if success == 0 {
-                error!("[agent#{}] Failed to get agent identifier", agent_index);
-                (lib.maa_string_buffer_destroy)(id_buffer);
-                (lib.maa_agent_client_destroy)(agent_client);
-                return Err(format!(
-                    "Failed to get agent identifier for agent #{}",
-                    agent_index
-                ));
-            }
-
-            let id = from_cstr((lib.maa_string_buffer_get)(id_buffer));
-            debug!("[agent#{}] Got socket_id: {}", agent_index, id);
-            (lib.maa_string_buffer_destroy)(id_buffer);
-            id
-        };
-
-        (SendPtr::new(agent_client), socket_id)
-    };
-
-    info!("[agent#{}] Agent socket_id: {}", agent_index, socket_id);
-
+/// 启动 Agent 子进程并转发其 stdout/stderr，stdin 用于下发暂停 / 恢复通知
+///
+/// 启动失败时会销毁传入的 `agent_client`。
+fn spawn_agent_process(
+    state: &Arc<MaaState>,
+    instance_id: &str,
+    agent: &AgentConfig,
+    agent_index: usize,
+    agent_client: &SendPtr<MaaAgentClient>,
+    socket_id: String,
+    cwd: &str,
+) -> Result<std::process::Child, String> {
     // 构建子进程参数
     let mut args = agent.child_args.clone().unwrap_or_default();
     args.push(socket_id);
@@ -170,6 +124,7 @@ async fn start_single_agent(
             .current_dir(cwd)
             .env("PYTHONIOENCODING", "utf-8")
             .env("PYTHONUTF8", "1")
+            .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .creation_flags(CREATE_NO_WINDOW)
@@ -182,6 +137,7 @@ async fn start_single_agent(
         .current_dir(cwd)
         .env("PYTHONIOENCODING", "utf-8")
         .env("PYTHONUTF8", "1")
+        .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn();
@@ -225,11 +181,11 @@ async fn start_single_agent(
     };
     let agent_log_file = get_logs_dir().join(&log_filename);
     let log_file = Arc::new(Mutex::new(
-        OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&agent_log_file)
-            .ok(),
+        RotatingLogFile::open(
+            agent_log_file.clone(),
+            logs::current_settings().max_file_size_bytes(),
+        )
+        .ok(),
     ));
     info!(
         "[agent#{}] Agent log file: {:?}",
@@ -237,10 +193,13 @@ async fn start_single_agent(
     );
 
     // 在单独线程中读取 stdout
+    // stdout 支持结构化日志协议（单行 JSON），无法识别的行按普通文本转发
     if let Some(stdout) = child.stdout.take() {
         let log_file_clone = Arc::clone(&log_file);
         let instance_id_clone = instance_id.to_string();
         let idx = agent_index;
+        let agent_cwd = std::path::PathBuf::from(cwd);
+        let state_clone = Arc::clone(state);
         thread::spawn(move || {
             let mut reader = BufReader::new(stdout);
             let mut buffer = Vec::new();
@@ -259,11 +218,36 @@ async fn start_single_agent(
                         if let Ok(mut guard) = log_file_clone.lock() {
                             if let Some(ref mut file) = *guard {
                                 let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
-                                let _ = writeln!(file, "{} [stdout] {}", timestamp, line);
+                                file.write_line(&format!("{} [stdout] {}", timestamp, line));
                             }
                         }
-                        log::info!(target: "agent", "[agent#{}][stdout] {}", idx, line);
-                        emit_agent_output(&instance_id_clone, "stdout", &line);
+                        if let Some(record) = parse_agent_log_line(&line, &agent_cwd) {
+                            log::log!(
+                                target: "agent",
+                                record.level.as_log_level(),
+                                "[agent#{}][stdout] {}",
+                                idx,
+                                record.to_log_text()
+                            );
+                            emit_agent_log(&instance_id_clone, &record);
+                            state_clone.push_agent_output(
+                                &instance_id_clone,
+                                idx,
+                                "stdout",
+                                &line,
+                                Some(record),
+                            );
+                        } else {
+                            log::info!(target: "agent", "[agent#{}][stdout] {}", idx, line);
+                            emit_agent_output(&instance_id_clone, "stdout", &line);
+                            state_clone.push_agent_output(
+                                &instance_id_clone,
+                                idx,
+                                "stdout",
+                                &line,
+                                None,
+                            );
+                        }
                     }
                     Err(e) => {
                         log::error!(target: "agent", "[agent#{}][stdout error] {}", idx, e);
@@ -279,6 +263,7 @@ async fn start_single_agent(
         let log_file_clone = Arc::clone(&log_file);
         let instance_id_clone = instance_id.to_string();
         let idx = agent_index;
+        let state_clone = Arc::clone(state);
         thread::spawn(move || {
             let mut reader = BufReader::new(stderr);
             let mut buffer = Vec::new();
@@ -297,11 +282,18 @@ async fn start_single_agent(
                         if let Ok(mut guard) = log_file_clone.lock() {
                             if let Some(ref mut file) = *guard {
                                 let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
-                                let _ = writeln!(file, "{} [stderr] {}", timestamp, line);
+                                file.write_line(&format!("{} [stderr] {}", timestamp, line));
                             }
                         }
                         log::warn!(target: "agent", "[agent#{}][stderr] {}", idx, line);
                         emit_agent_output(&instance_id_clone, "stderr", &line);
+                        state_clone.push_agent_output(
+                            &instance_id_clone,
+                            idx,
+                            "stderr",
+                            &line,
+                            None,
+                        );
                     }
                     Err(e) => {
                         log::error!(target: "agent", "[agent#{}][stderr error] {}", idx, e);
@@ -312,38 +304,448 @@ async fn start_single_agent(
         });
     }
 
-    // 设置连接超时并获取 connect 函数指针
-    let timeout_ms = agent.timeout.unwrap_or(-1);
-    let connect_fn = {
+    Ok(child)
+}
+
+/// 附加模式下检测断线的间隔
+const ATTACH_WATCH_INTERVAL: Duration = Duration::from_secs(2);
+/// 单次重连尝试的等待时长（毫秒），停止 agent 时最多等待这么久
+const ATTACH_RECONNECT_ATTEMPT_MS: i64 = 3000;
+
+/// 附加模式 agent 的断线重连监视状态
+#[derive(Default)]
+struct AttachWatch {
+    stopped: AtomicBool,
+    /// 检测与重连期间持有，销毁 client 前需等待其释放
+    busy: Mutex<()>,
+}
+
+/// 正在监视的附加模式 agent（按 client 指针地址区分）
+static ATTACH_WATCHES: Lazy<Mutex<HashMap<usize, Arc<AttachWatch>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 监视附加模式的 agent，断线后按配置的超时时间等待外部 agent 重新连接
+///
+/// 外部 agent（如调试器中重启的进程）断开后不会有子进程退出可供感知，
+/// 因此定期检查连接状态并重新等待连接；超过 `timeout_ms`（-1 表示无限）仍未连上则放弃。
+fn watch_attached_agent(
+    instance_id: String,
+    agent_index: usize,
+    client: SendPtr<MaaAgentClient>,
+    timeout_ms: i64,
+) {
+    let fns = MAA_LIBRARY.lock().ok().and_then(|guard| {
+        let lib = guard.as_ref()?;
+        Some((
+            lib.maa_agent_client_alive?,
+            lib.maa_agent_client_connect,
+            lib.maa_agent_client_set_timeout,
+        ))
+    });
+    let Some((alive_fn, connect_fn, set_timeout_fn)) = fns else {
+        debug!(
+            "[agent#{}] MaaAgentClientAlive not available, reconnect disabled",
+            agent_index
+        );
+        return;
+    };
+
+    let watch = Arc::new(AttachWatch::default());
+    if let Ok(mut watches) = ATTACH_WATCHES.lock() {
+        watches.insert(client.as_ptr() as usize, Arc::clone(&watch));
+    }
+
+    thread::spawn(move || {
+        let mut lost_since: Option<Instant> = None;
+        loop {
+            thread::sleep(ATTACH_WATCH_INTERVAL);
+            let _busy = watch.busy.lock().unwrap_or_else(|e| e.into_inner());
+            if watch.stopped.load(Ordering::SeqCst) {
+                break;
+            }
+            if unsafe { alive_fn(client.as_ptr()) } != 0 {
+                continue;
+            }
+
+            let since = *lost_since.get_or_insert_with(|| {
+                warn!(
+                    "[agent#{}] Attached agent disconnected, waiting for it to reconnect",
+                    agent_index
+                );
+                emit_agent_progress(&instance_id, agent_index, "disconnected", None);
+                Instant::now()
+            });
+            if timeout_ms >= 0 && since.elapsed() >= Duration::from_millis(timeout_ms as u64) {
+                let message = format!("Agent #{} did not reconnect in time", agent_index);
+                error!("[agent#{}] {}", agent_index, message);
+                emit_agent_progress(&instance_id, agent_index, "failed", Some(&message));
+                break;
+            }
+
+            let connected = unsafe {
+                set_timeout_fn(client.as_ptr(), ATTACH_RECONNECT_ATTEMPT_MS);
+                connect_fn(client.as_ptr())
+            };
+            if connected != 0 {
+                info!("[agent#{}] Attached agent reconnected", agent_index);
+                emit_agent_progress(&instance_id, agent_index, "connected", None);
+                lost_since = None;
+            }
+        }
+        if let Ok(mut watches) = ATTACH_WATCHES.lock() {
+            watches.remove(&(client.as_ptr() as usize));
+        }
+    });
+}
+
+/// 停止对附加模式 agent 的监视，并等待进行中的重连尝试结束（销毁 client 前调用）
+pub fn unwatch_attached_agent(client: *mut MaaAgentClient) {
+    let watch = ATTACH_WATCHES
+        .lock()
+        .ok()
+        .and_then(|mut watches| watches.remove(&(client as usize)));
+    if let Some(watch) = watch {
+        watch.stopped.store(true, Ordering::SeqCst);
+        drop(watch.busy.lock());
+    }
+}
+
+/// 断开并销毁 agent client，终止其子进程
+fn release_agent(agent_index: usize, client: *mut MaaAgentClient, child: Option<Child>) {
+    if let Ok(guard) = MAA_LIBRARY.lock() {
+        if let Some(lib) = guard.as_ref() {
+            unsafe {
+                (lib.maa_agent_client_disconnect)(client);
+                (lib.maa_agent_client_destroy)(client);
+            }
+        }
+    }
+    if let Some(mut child) = child {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+    debug!("[agent#{}] Agent released", agent_index);
+}
+
+/// 启动时单次连接尝试的等待时长（毫秒），放弃启动后连接线程最多再阻塞这么久
+const CONNECT_ATTEMPT_MS: i64 = 3000;
+
+/// 分段等待连接：每次尝试不超过 [`CONNECT_ATTEMPT_MS`]，总时长不超过 `timeout_ms`（-1 表示无限）
+///
+/// 两次尝试之间检查等待方是否已放弃，使被放弃的连接（包括超时为 -1 的附加模式）
+/// 能及时返回并销毁 client。`attempt` 以本次等待的毫秒数连接，返回是否已连接
+fn connect_in_attempts(
+    timeout_ms: i64,
+    state: &Mutex<ConnectState>,
+    mut attempt: impl FnMut(i64) -> bool,
+) -> bool {
+    let deadline =
+        (timeout_ms >= 0).then(|| Instant::now() + Duration::from_millis(timeout_ms as u64));
+    loop {
+        let attempt_ms = match deadline {
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                (remaining.as_millis() as i64).min(CONNECT_ATTEMPT_MS)
+            }
+            None => CONNECT_ATTEMPT_MS,
+        };
+        if attempt(attempt_ms) {
+            return true;
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return false;
+        }
+        if *state.lock().unwrap_or_else(|e| e.into_inner()) == ConnectState::Abandoned {
+            return false;
+        }
+    }
+}
+
+/// 连接线程的状态，用于决定由哪一方销毁 client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConnectState {
+    Pending,
+    Finished,
+    Abandoned,
+}
+
+/// 正在等待连接的 agent
+///
+/// 并发启动时任一 agent 失败会丢弃其余的启动 future：此时立即终止子进程，
+/// client 则在连接线程返回后销毁（连接仍在阻塞时不能销毁，见 [`connect_in_attempts`]）。
+struct PendingConnect {
+    agent_index: usize,
+    client: SendPtr<MaaAgentClient>,
+    child: Option<Child>,
+    state: Arc<Mutex<ConnectState>>,
+    done: bool,
+}
+
+impl PendingConnect {
+    fn new(agent_index: usize, client: SendPtr<MaaAgentClient>, child: Option<Child>) -> Self {
+        Self {
+            agent_index,
+            client,
+            child,
+            state: Arc::new(Mutex::new(ConnectState::Pending)),
+            done: false,
+        }
+    }
+
+    /// 连接线程返回时调用：等待方已放弃时由连接线程负责销毁 client
+    fn finish(state: &Mutex<ConnectState>, agent_index: usize, client: SendPtr<MaaAgentClient>) {
+        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+        if *state == ConnectState::Abandoned {
+            info!(
+                "[agent#{}] Connect returned after start was aborted, releasing client",
+                agent_index
+            );
+            release_agent(agent_index, client.as_ptr(), None);
+        } else {
+            *state = ConnectState::Finished;
+        }
+    }
+
+    /// 连接结果已被等待方接收，取回子进程
+    fn complete(mut self) -> Option<Child> {
+        self.done = true;
+        self.child.take()
+    }
+}
+
+impl Drop for PendingConnect {
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+        warn!(
+            "[agent#{}] Agent start aborted while connecting",
+            self.agent_index
+        );
+        if let Some(mut child) = self.child.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if *state == ConnectState::Finished {
+            release_agent(self.agent_index, self.client.as_ptr(), None);
+        } else {
+            *state = ConnectState::Abandoned;
+        }
+    }
+}
+
+/// 已连接的 agent
+///
+/// 在保存到实例之前被丢弃（其他 agent 启动失败或后续步骤出错）时自动断开并终止子进程。
+struct StartedAgent {
+    agent_index: usize,
+    client: SendPtr<MaaAgentClient>,
+    child: Option<Child>,
+    kept: bool,
+}
+
+impl StartedAgent {
+    /// 交给实例保存，不再自动清理
+    fn keep(mut self) -> (*mut MaaAgentClient, Option<Child>) {
+        self.kept = true;
+        (self.client.as_ptr(), self.child.take())
+    }
+}
+
+impl Drop for StartedAgent {
+    fn drop(&mut self) {
+        if !self.kept {
+            info!("[agent#{}] Rolling back started agent", self.agent_index);
+            release_agent(self.agent_index, self.client.as_ptr(), self.child.take());
+        }
+    }
+}
+
+/// 启动单个 Agent 子进程（或附加到已运行的 agent）并完成连接
+///
+/// 附加模式下没有子进程。返回的 [`StartedAgent`] 在调用方保存前被丢弃时会自动回滚。
+async fn start_single_agent(
+    state: &Arc<MaaState>,
+    instance_id: &str,
+    agent: &AgentConfig,
+    agent_index: usize,
+    resource: &SendPtr<crate::maa_ffi::MaaResource>,
+    tasker: &SendPtr<crate::maa_ffi::MaaTasker>,
+    cwd: &str,
+    tcp_compat_mode: bool,
+) -> Result<StartedAgent, String> {
+    info!("[agent#{}] Starting agent: {:?}", agent_index, agent);
+
+    // 创建 AgentClient 并获取 socket_id
+    debug!(
+        "[agent#{}] Acquiring MAA_LIBRARY lock for agent creation...",
+        agent_index
+    );
+    let (agent_client, socket_id) = {
         let guard = MAA_LIBRARY
             .lock()
             .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
+        debug!("[agent#{}] MAA_LIBRARY lock acquired", agent_index);
         let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
 
-        info!(
-            "[agent#{}] Setting agent connect timeout: {} ms",
-            agent_index, timeout_ms
+        // 附加模式使用固定的端口或标识符；否则根据 tcp_compat_mode 选择创建方式
+        let agent_client = if agent.attach {
+            create_attach_client(lib, agent, agent_index)?
+        } else if tcp_compat_mode {
+            if let Some(create_tcp_fn) = lib.maa_agent_client_create_tcp {
+                debug!(
+                    "[agent#{}] Using TCP compat mode, calling maa_agent_client_create_tcp...",
+                    agent_index
+                );
+                let client = unsafe { create_tcp_fn(0) };
+                debug!(
+                    "[agent#{}] maa_agent_client_create_tcp returned: {:?}",
+                    agent_index, client
+                );
+                client
+            } else {
+                warn!(
+                    "[agent#{}] TCP compat mode requested but MaaAgentClientCreateTcp not available, falling back to V2",
+                    agent_index
+                );
+                let client = unsafe { (lib.maa_agent_client_create_v2)(std::ptr::null()) };
+                debug!(
+                    "[agent#{}] maa_agent_client_create_v2 (fallback) returned: {:?}",
+                    agent_index, client
+                );
+                client
+            }
+        } else {
+            debug!(
+                "[agent#{}] Calling maa_agent_client_create_v2...",
+                agent_index
+            );
+            let client = unsafe { (lib.maa_agent_client_create_v2)(std::ptr::null()) };
+            debug!(
+                "[agent#{}] maa_agent_client_create_v2 returned: {:?}",
+                agent_index, client
+            );
+            client
+        };
+
+        if agent_client.is_null() {
+            error!(
+                "[agent#{}] Failed to create agent client (null pointer)",
+                agent_index
+            );
+            return Err(format!("Failed to create agent client #{}", agent_index));
+        }
+
+        // 绑定资源
+        debug!(
+            "[agent#{}] Binding resource to agent client, resource ptr: {:?}",
+            agent_index,
+            resource.as_ptr()
         );
         unsafe {
-            (lib.maa_agent_client_set_timeout)(agent_client.as_ptr(), timeout_ms);
+            (lib.maa_agent_client_bind_resource)(agent_client, resource.as_ptr());
         }
-        lib.maa_agent_client_connect
+        debug!("[agent#{}] Resource bound to agent client", agent_index);
+
+        // 获取 socket identifier
+        debug!("[agent#{}] Getting socket identifier...", agent_index);
+        let socket_id = unsafe {
+            let id_buffer = (lib.maa_string_buffer_create)();
+            if id_buffer.is_null() {
+                error!(
+                    "[agent#{}] Failed to create string buffer (null pointer)",
+                    agent_index
+                );
+                (lib.maa_agent_client_destroy)(agent_client);
+                return Err(format!(
+                    "Failed to create string buffer for agent #{}",
+                    agent_index
+                ));
+            }
+
+            let success = (lib.maa_agent_client_identifier)(agent_client, id_buffer);
+            if success == 0 {
+                error!("[agent#{}] Failed to get agent identifier", agent_index);
+                (lib.maa_string_buffer_destroy)(id_buffer);
+                (lib.maa_agent_client_destroy)(agent_client);
+                return Err(format!(
+                    "Failed to get agent identifier for agent #{}",
+                    agent_index
+                ));
+            }
+
+            let id = from_cstr((lib.maa_string_buffer_get)(id_buffer));
+            debug!("[agent#{}] Got socket_id: {}", agent_index, id);
+            (lib.maa_string_buffer_destroy)(id_buffer);
+            id
+        };
+
+        (SendPtr::new(agent_client), socket_id)
     };
 
+    info!("[agent#{}] Agent socket_id: {}", agent_index, socket_id);
+
+    // 附加模式下不启动子进程，等待外部已运行的 agent 使用该标识连接
+    let child = if agent.attach {
+        info!(
+            "[agent#{}] Attach mode, waiting for external agent on: {}",
+            agent_index, socket_id
+        );
+        emit_agent_progress(instance_id, agent_index, "waiting", Some(&socket_id));
+        None
+    } else {
+        Some(spawn_agent_process(
+            state,
+            instance_id,
+            agent,
+            agent_index,
+            &agent_client,
+            socket_id,
+            cwd,
+        )?)
+    };
+
+    // 获取 connect 函数指针，连接超时在连接线程中按每次尝试设置
+    let timeout_ms = agent.timeout.unwrap_or(-1);
+    let (connect_fn, set_timeout_fn) = {
+        let guard = MAA_LIBRARY
+            .lock()
+            .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
+        let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
+        (
+            lib.maa_agent_client_connect,
+            lib.maa_agent_client_set_timeout,
+        )
+    };
+    info!(
+        "[agent#{}] Agent connect timeout: {} ms",
+        agent_index, timeout_ms
+    );
+
     // 等待连接（在独立线程池中执行，避免阻塞 UI 线程）
-    let agent_ptr = agent_client.as_ptr() as usize;
+    // 其他 agent 启动失败时本 future 会被丢弃，由 PendingConnect 负责清理
+    let pending = PendingConnect::new(agent_index, SendPtr::new(agent_client.as_ptr()), child);
+    let connect_state = Arc::clone(&pending.state);
+    let connect_client = SendPtr::new(agent_client.as_ptr());
     info!(
         "[agent#{}] Waiting for agent connection (non-blocking)...",
         agent_index
     );
+    emit_agent_progress(instance_id, agent_index, "connecting", None);
     let connected = tokio::task::spawn_blocking(move || {
-        let result = unsafe { connect_fn(agent_ptr as *mut MaaAgentClient) };
+        let result = connect_in_attempts(timeout_ms, &connect_state, |attempt_ms| unsafe {
+            set_timeout_fn(connect_client.as_ptr(), attempt_ms);
+            connect_fn(connect_client.as_ptr()) != 0
+        });
+        PendingConnect::finish(&connect_state, agent_index, connect_client);
         result
     })
     .await
     .map_err(|e| format!("Agent #{} connect task panicked: {}", agent_index, e))?;
+    let mut child = pending.complete();
 
-    if connected == 0 {
+    if !connected {
         error!(
             "[agent#{}] Agent connection failed, cleaning up...",
             agent_index
@@ -354,16 +756,18 @@ async fn start_single_agent(
         let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
 
         // 直接终止未成功连接的子进程，避免无用的后台进程残留
-        if let Err(e) = child.kill() {
-            warn!(
-                "[agent#{}] Failed to kill agent child process after connection failure: {}",
-                agent_index, e
-            );
-        } else if let Err(e) = child.wait() {
-            warn!(
-                "[agent#{}] Failed to wait on agent child process after connection failure: {}",
-                agent_index, e
-            );
+        if let Some(child) = child.as_mut() {
+            if let Err(e) = child.kill() {
+                warn!(
+                    "[agent#{}] Failed to kill agent child process after connection failure: {}",
+                    agent_index, e
+                );
+            } else if let Err(e) = child.wait() {
+                warn!(
+                    "[agent#{}] Failed to wait on agent child process after connection failure: {}",
+                    agent_index, e
+                );
+            }
         }
 
         unsafe {
@@ -373,6 +777,12 @@ async fn start_single_agent(
     }
 
     info!("[agent#{}] Agent connected successfully!", agent_index);
+    let started = StartedAgent {
+        agent_index,
+        client: SendPtr::new(agent_client.as_ptr()),
+        child,
+        kept: false,
+    };
 
     // 注册 Agent sink
     {
@@ -407,7 +817,7 @@ async fn start_single_agent(
         );
     }
 
-    Ok((agent_client, child))
+    Ok(started)
 }
 
 /// 启动任务（支持多个 Agent）
@@ -512,55 +922,44 @@ pub async fn maa_start_tasks(
             debug!("[start_tasks] Agent configs list is empty, skipping agent setup");
             false
         } else {
-            info!("[start_tasks] Starting {} agent(s)...", agents.len());
-
-            // 用于收集所有成功启动的 agent，失败时需要回滚清理
-            let mut started_clients: Vec<SendPtr<MaaAgentClient>> = Vec::new();
-            let mut started_children: Vec<std::process::Child> = Vec::new();
-
-            for (idx, agent) in agents.iter().enumerate() {
-                match start_single_agent(
-                    state.inner(),
-                    &instance_id,
-                    agent,
-                    idx,
-                    &resource,
-                    &tasker,
-                    &cwd,
-                    tcp_compat_mode,
-                )
-                .await
-                {
-                    Ok((client, child)) => {
-                        started_clients.push(client);
-                        started_children.push(child);
-                    }
-                    Err(e) => {
-                        error!(
-                            "[start_tasks] Agent #{} failed to start: {}, cleaning up previously started agents...",
-                            idx, e
-                        );
+            info!(
+                "[start_tasks] Starting {} agent(s) concurrently...",
+                agents.len()
+            );
 
-                        // 回滚：清理已启动的 agent
-                        if let Ok(guard) = MAA_LIBRARY.lock() {
-                            if let Some(lib) = guard.as_ref() {
-                                for client in &started_clients {
-                                    unsafe {
-                                        (lib.maa_agent_client_disconnect)(client.as_ptr());
-                                        (lib.maa_agent_client_destroy)(client.as_ptr());
-                                    }
-                                }
-                            }
-                        }
-                        for mut child in started_children {
-                            let _ = child.kill();
-                            let _ = child.wait();
+            // 并发启动所有 agent：连接等待在各自的阻塞线程中进行，
+            // 总耗时取决于最慢的 agent，而不是所有 agent 耗时之和。
+            // 任一 agent 失败时立即返回，其余仍在连接的 future 被丢弃并自行回滚
+            let started = try_join_all(agents.iter().enumerate().map(|(idx, agent)| {
+                let state = state.inner();
+                let instance_id = instance_id.as_str();
+                let resource = &resource;
+                let tasker = &tasker;
+                let cwd = cwd.as_str();
+                async move {
+                    emit_agent_progress(instance_id, idx, "starting", None);
+                    let result = start_single_agent(
+                        state,
+                        instance_id,
+                        agent,
+                        idx,
+                        resource,
+                        tasker,
+                        cwd,
+                        tcp_compat_mode,
+                    )
+                    .await;
+                    match &result {
+                        Ok(_) => emit_agent_progress(instance_id, idx, "connected", None),
+                        Err(e) => {
+                            error!("[start_tasks] Agent #{} failed to start: {}", idx, e);
+                            emit_agent_progress(instance_id, idx, "failed", Some(e));
                         }
-
-                        return Err(e);
                     }
+                    result
                 }
-            }
+            }))
+            .await?;
 
             // 保存所有 agent 状态到 instance
             {
@@ -568,17 +967,28 @@ pub async fn maa_start_tasks(
                     .instances
                     .lock()
                     .map_err(|e: std::sync::PoisonError<_>| e.to_string())?;
-                if let Some(instance) = instances.get_mut(&instance_id) {
-                    for client in &started_clients {
-                        instance.agent_clients.push(client.as_ptr());
+                let instance = instances
+                    .get_mut(&instance_id)
+                    .ok_or("Instance not found")?;
+                for (agent, config) in started.into_iter().zip(agents) {
+                    let agent_index = agent.agent_index;
+                    let (client, child) = agent.keep();
+                    if config.attach {
+                        watch_attached_agent(
+                            instance_id.clone(),
+                            agent_index,
+                            SendPtr::new(client),
+                            config.timeout.unwrap_or(-1),
+                        );
                     }
-                    instance.agent_children.extend(started_children);
+                    instance.agent_clients.push(client);
+                    instance.agent_children.extend(child);
                 }
             }
 
             info!(
                 "[start_tasks] All {} agent(s) started successfully",
-                started_clients.len()
+                agents.len()
             );
             true
         }
@@ -609,6 +1019,10 @@ pub async fn maa_start_tasks(
         return Err("Tasker not properly initialized".to_string());
     }
 
+    // 清除上次停止留下的取消标记
+    crate::mxu_actions::clear_cancel(tasker.as_ptr());
+    crate::run_context::begin_run(&instance_id);
+
     // 提交所有任务
     debug!("[start_tasks] Submitting {} tasks...", tasks.len());
     let mut task_ids = Vec::new();
@@ -636,6 +1050,7 @@ pub async fn maa_start_tasks(
             continue;
         }
 
+        crate::run_context::record_task(task_id, &instance_id, &task.entry);
         task_ids.push(task_id);
         debug!(
             "[start_tasks] Task {} submitted successfully, task_id: {}",
@@ -708,6 +1123,10 @@ pub fn maa_stop_agent(state: State<Arc<MaaState>>, instance_id: String) -> Resul
 
     // 在后台线程执行阻塞的清理操作（disconnect 和 wait 可能阻塞）
     thread::spawn(move || {
+        for agent in &send_clients {
+            unwatch_attached_agent(agent.as_ptr());
+        }
+
         // 断开并销毁所有 agent
         let guard = MAA_LIBRARY.lock();
         if let Ok(guard) = guard {
@@ -736,3 +1155,47 @@ pub fn maa_stop_agent(state: State<Arc<MaaState>>, instance_id: String) -> Resul
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connect_retries_until_connected() {
+        let state = Mutex::new(ConnectState::Pending);
+        let mut attempts = Vec::new();
+        let connected = connect_in_attempts(-1, &state, |ms| {
+            attempts.push(ms);
+            attempts.len() == 3
+        });
+        assert!(connected);
+        assert_eq!(attempts, [CONNECT_ATTEMPT_MS; 3]);
+    }
+
+    #[test]
+    fn abandoned_connect_without_timeout_returns() {
+        let state = Mutex::new(ConnectState::Pending);
+        let mut attempts = 0;
+        let connected = connect_in_attempts(-1, &state, |_| {
+            attempts += 1;
+            *state.lock().unwrap() = ConnectState::Abandoned;
+            false
+        });
+        assert!(!connected);
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn connect_attempts_stay_within_timeout() {
+        let state = Mutex::new(ConnectState::Pending);
+        let mut attempts = Vec::new();
+        let connected = connect_in_attempts(50, &state, |ms| {
+            attempts.push(ms);
+            thread::sleep(Duration::from_millis(ms as u64));
+            false
+        });
+        assert!(!connected);
+        assert!(attempts.iter().all(|ms| *ms <= 50));
+        assert!(attempts.iter().sum::<i64>() <= 60);
+    }
+}
@@ -10,10 +10,11 @@ use tauri::State;
 
 use crate::maa_ffi::{
     from_cstr, get_event_callback, get_maa_version, get_maa_version_standalone, init_maa_library,
-    to_cstring, MaaImageBuffer, MaaLibrary, MaaToolkitAdbDeviceList, MaaToolkitDesktopWindowList,
-    MAA_CTRL_OPTION_SCREENSHOT_TARGET_SHORT_SIDE, MAA_GAMEPAD_TYPE_DUALSHOCK4,
-    MAA_GAMEPAD_TYPE_XBOX360, MAA_INVALID_ID, MAA_LIBRARY, MAA_STATUS_PENDING, MAA_STATUS_RUNNING,
-    MAA_STATUS_SUCCEEDED, MAA_WIN32_SCREENCAP_DXGI_DESKTOPDUP,
+    to_cstring, MaaImageBuffer, MaaLibrary, MaaTasker, MaaToolkitAdbDeviceList,
+    MaaToolkitDesktopWindowList, MAA_CTRL_OPTION_SCREENSHOT_TARGET_SHORT_SIDE,
+    MAA_GAMEPAD_TYPE_DUALSHOCK4, MAA_GAMEPAD_TYPE_XBOX360, MAA_INVALID_ID, MAA_LIBRARY,
+    MAA_STATUS_PENDING, MAA_STATUS_RUNNING, MAA_STATUS_SUCCEEDED,
+    MAA_WIN32_SCREENCAP_DXGI_DESKTOPDUP,
 };
 
 use super::types::{
@@ -377,6 +378,38 @@ pub fn maa_create_instance(state: State<Arc<MaaState>>, instance_id: String) ->
     Ok(())
 }
 
+/// 销毁前等待 tasker 停止的最长时间
+const DESTROY_STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 销毁实例前停止正在运行的任务，并等待工作线程退出（最多 DESTROY_STOP_TIMEOUT）
+///
+/// 等待期间不持有 MAA_LIBRARY，以便仍在执行的自定义动作可以正常返回
+fn stop_tasker_for_destroy(tasker: *mut MaaTasker) {
+    let (post_stop, running) = {
+        let Ok(guard) = MAA_LIBRARY.lock() else {
+            return;
+        };
+        let Some(lib) = guard.as_ref() else {
+            return;
+        };
+        (lib.maa_tasker_post_stop, lib.maa_tasker_running)
+    };
+    if unsafe { running(tasker) } == 0 {
+        return;
+    }
+
+    info!("Stopping running tasks before destroying instance");
+    unsafe { post_stop(tasker) };
+    let started = Instant::now();
+    while unsafe { running(tasker) } != 0 {
+        if started.elapsed() >= DESTROY_STOP_TIMEOUT {
+            warn!("Tasker still running after {:?}", DESTROY_STOP_TIMEOUT);
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
 /// 销毁实例
 #[tauri::command]
 pub fn maa_destroy_instance(
@@ -385,8 +418,30 @@ pub fn maa_destroy_instance(
 ) -> Result<(), String> {
     info!("maa_destroy_instance called, instance_id: {}", instance_id);
 
-    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
-    let removed = instances.remove(&instance_id).is_some();
+    let removed_instance = state
+        .instances
+        .lock()
+        .map_err(|e| e.to_string())?
+        .remove(&instance_id);
+    let tasker = removed_instance
+        .as_ref()
+        .and_then(|instance| instance.tasker);
+    if let Some(tasker) = tasker {
+        // 先唤醒暂停 / 等待中的 MXU 动作并停止任务，否则销毁 tasker 时会一直等待工作线程
+        crate::mxu_actions::request_cancel(tasker);
+        stop_tasker_for_destroy(tasker);
+    }
+    let removed = removed_instance.is_some();
+    // 在 instances 锁外释放，销毁过程中不阻塞其他实例的命令
+    drop(removed_instance);
+    if let Some(tasker) = tasker {
+        crate::mxu_actions::clear_cancel(tasker);
+    }
+    crate::run_context::forget_instance(&instance_id);
+
+    if let Ok(mut buffers) = state.agent_output.lock() {
+        buffers.remove(&instance_id);
+    }
 
     if removed {
         info!("maa_destroy_instance success, instance_id: {}", instance_id);
@@ -587,6 +642,7 @@ pub fn maa_connect_controller(
         }
 
         instance.controller = Some(controller);
+        instance.controller_config = Some(config);
     }
 
     Ok(conn_id)
@@ -677,6 +733,7 @@ pub fn maa_load_resource(
 
     // 加载资源（不等待，通过回调通知完成）
     let mut res_ids = Vec::new();
+    let mut loaded_paths = Vec::new();
     for path in &paths {
         let normalized = normalize_path(path);
         let normalized_str = normalized.to_string_lossy();
@@ -693,6 +750,13 @@ pub fn maa_load_resource(
         }
 
         res_ids.push(res_id);
+        loaded_paths.push(normalized_str.into_owned());
+    }
+
+    if let Ok(mut instances) = state.instances.lock() {
+        if let Some(instance) = instances.get_mut(&instance_id) {
+            instance.resource_paths.extend(loaded_paths);
+        }
     }
 
     Ok(res_ids)
@@ -740,6 +804,7 @@ pub fn maa_destroy_resource(
         .ok_or("Instance not found")?;
 
     // 销毁旧的资源
+    instance.resource_paths.clear();
     if let Some(resource) = instance.resource.take() {
         debug!("Destroying old resource...");
         unsafe {
@@ -832,6 +897,9 @@ pub fn maa_run_task(
         return Err("Tasker not properly initialized".to_string());
     }
 
+    // 清除上次停止留下的取消标记
+    crate::mxu_actions::clear_cancel(tasker);
+
     // 提交任务（不等待，通过回调通知完成）
     let entry_c = to_cstring(&entry);
     let override_c = to_cstring(&pipeline_override);
@@ -848,6 +916,7 @@ pub fn maa_run_task(
     if task_id == MAA_INVALID_ID {
         return Err("Failed to post task".to_string());
     }
+    crate::run_context::record_task(task_id, &instance_id, &entry);
 
     // 缓存 task_id，用于刷新后恢复状态
     {
@@ -944,9 +1013,48 @@ pub fn maa_stop_task(state: State<Arc<MaaState>>, instance_id: String) -> Result
     let stop_id = unsafe { (lib.maa_tasker_post_stop)(tasker) };
     info!("MaaTaskerPostStop returned: {}", stop_id);
 
+    // 唤醒正在等待的 MXU_SLEEP / MXU_WAITUNTIL，使其尽快返回
+    crate::mxu_actions::request_cancel(tasker);
+    crate::run_context::mark_stopped(&instance_id);
+
     Ok(())
 }
 
+/// 暂停或恢复实例的任务，并通知其 agent 子进程
+/// 未在运行的任务不接受暂停，否则暂停标记会残留到下一次运行
+fn set_task_paused(state: &MaaState, instance_id: &str, paused: bool) -> Result<(), String> {
+    let guard = MAA_LIBRARY.lock().map_err(|e| e.to_string())?;
+    let lib = guard.as_ref().ok_or("MaaFramework not initialized")?;
+
+    let mut instances = state.instances.lock().map_err(|e| e.to_string())?;
+    let instance = instances.get_mut(instance_id).ok_or("Instance not found")?;
+    let tasker = instance.tasker.ok_or("Tasker not created")?;
+    if paused && instance.stop_in_progress {
+        return Err("Task is stopping".to_string());
+    }
+    if paused && unsafe { (lib.maa_tasker_running)(tasker) } == 0 {
+        return Err("Task is not running".to_string());
+    }
+
+    crate::mxu_actions::set_paused(tasker, paused);
+    instance.notify_agents(if paused { "pause" } else { "resume" });
+    Ok(())
+}
+
+/// 暂停任务：在下一个节点执行动作前阻塞，正在等待的 MXU 动作停止计时
+#[tauri::command]
+pub fn maa_pause_task(state: State<Arc<MaaState>>, instance_id: String) -> Result<(), String> {
+    info!("maa_pause_task called, instance_id: {}", instance_id);
+    set_task_paused(&state, &instance_id, true)
+}
+
+/// 恢复已暂停的任务，从暂停的节点继续执行
+#[tauri::command]
+pub fn maa_resume_task(state: State<Arc<MaaState>>, instance_id: String) -> Result<(), String> {
+    info!("maa_resume_task called, instance_id: {}", instance_id);
+    set_task_paused(&state, &instance_id, false)
+}
+
 /// 覆盖已提交任务的 Pipeline 配置（用于运行中修改尚未执行的任务选项）
 #[tauri::command]
 pub fn maa_override_pipeline(
@@ -7,8 +7,13 @@
 //! - `utils`: 辅助函数
 //! - `maa_core`: Maa 核心命令（初始化、设备搜索、控制器、资源、任务）
 //! - `maa_agent`: Agent 相关命令
+//! - `actions`: MXU 内置动作参数 Schema 查询
 //! - `state`: 状态查询命令
 //! - `file_ops`: 文件操作命令
+//! - `logs`: 日志轮转与保留策略
+//! - `run_history`: 运行历史记录
+//! - `notification`: 通知渠道命令
+//! - `variables`: 持久化变量命令
 //! - `update`: 更新安装相关命令
 //! - `download`: 下载相关命令
 //! - `system`: 系统相关命令
@@ -17,14 +22,19 @@
 pub mod types;
 pub mod utils;
 
+pub mod actions;
 pub mod download;
 pub mod file_ops;
+pub mod logs;
 pub mod maa_agent;
 pub mod maa_core;
+pub mod notification;
+pub mod run_history;
 pub mod state;
 pub mod system;
 pub mod tray;
 pub mod update;
+pub mod variables;
 
 // 重新导出类型（供 lib.rs 使用）
 pub use types::MaaState;
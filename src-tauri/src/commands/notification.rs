@@ -0,0 +1,896 @@
+//! 通知渠道命令
+//!
+//! 管理外部通知渠道（SMTP 邮件、Telegram、Discord/Slack、Bark、Server 酱、ntfy、通用 HTTP），
+//! 供 MXU_NOTIFY 的 `channel` 参数及运行结束时的自动通知使用。
+//! 渠道配置保存在数据目录的 `config/mxu-notification-channels.json`。
+//!
+//! 注意：SMTP 密码、Bot Token、SendKey 等凭据以明文保存在该文件中（Unix 上仅当前用户可读写），
+//! 不要将数据目录分享给他人。
+
+use std::path::PathBuf;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use reqwest::blocking::Client;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+
+use super::types::{NotificationChannel, NotificationChannelKind, NotificationMessages};
+use super::utils::{build_user_agent, get_app_data_dir};
+use crate::mxu_actions::template::Escape;
+
+/// 渠道配置文件名（位于数据目录的 config 子目录）
+const CHANNELS_FILE_NAME: &str = "mxu-notification-channels.json";
+
+/// HTTP 类渠道的请求超时
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// 运行事件：所有任务成功完成
+pub const EVENT_RUN_SUCCEEDED: &str = "run_succeeded";
+/// 运行事件：存在失败的任务
+pub const EVENT_RUN_FAILED: &str = "run_failed";
+
+/// Server 酱³ 的 SendKey 格式（`sctp{uid}t...`）
+static SERVER_CHAN3_KEY: Lazy<regex::Regex> =
+    Lazy::new(|| regex::Regex::new(r"^sctp(\d+)t").unwrap());
+
+/// 后端通知的文案，未下发时使用英文默认值
+static RUN_MESSAGES: Lazy<RwLock<NotificationMessages>> =
+    Lazy::new(|| RwLock::new(NotificationMessages::default()));
+
+fn channels_path() -> Result<PathBuf, String> {
+    Ok(get_app_data_dir()?.join("config").join(CHANNELS_FILE_NAME))
+}
+
+/// 读取已保存的通知渠道，文件不存在时返回空列表
+pub fn load_channels() -> Result<Vec<NotificationChannel>, String> {
+    let path = channels_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("读取通知渠道配置失败 [{}]: {}", path.display(), e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("解析通知渠道配置失败 [{}]: {}", path.display(), e))
+}
+
+fn http_client() -> Result<Client, String> {
+    Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .user_agent(build_user_agent())
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
+
+/// 检查响应状态，非 2xx 时返回包含响应内容的错误
+fn check_response(resp: reqwest::blocking::Response) -> Result<String, String> {
+    let status = resp.status();
+    let text = resp.text().unwrap_or_default();
+    if status.is_success() {
+        Ok(text)
+    } else {
+        Err(format!(
+            "HTTP {}: {}",
+            status,
+            text.chars().take(300).collect::<String>()
+        ))
+    }
+}
+
+fn post_json(client: &Client, url: &str, body: &serde_json::Value) -> Result<String, String> {
+    let resp = client
+        .post(url)
+        .header(CONTENT_TYPE, "application/json")
+        .body(body.to_string())
+        .send()
+        .map_err(|e| format!("Request failed: {}", e))?;
+    check_response(resp)
+}
+
+/// 按请求体的 Content-Type 选择 `{title}` / `{body}` 的转义方式
+fn body_escape(content_type: &str) -> Escape {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    if mime == "application/json" || mime.ends_with("+json") {
+        Escape::Json
+    } else if mime == "application/x-www-form-urlencoded" {
+        Escape::Url
+    } else {
+        Escape::Raw
+    }
+}
+
+fn send_smtp(kind: &NotificationChannelKind, title: &str, body: &str) -> Result<(), String> {
+    use lettre::message::{header::ContentType, Mailbox};
+    use lettre::transport::smtp::authentication::Credentials;
+    use lettre::{Message, SmtpTransport, Transport};
+
+    let NotificationChannelKind::Smtp {
+        host,
+        port,
+        security,
+        username,
+        password,
+        from,
+        to,
+    } = kind
+    else {
+        return Err("Not an SMTP channel".to_string());
+    };
+
+    if to.is_empty() {
+        return Err("SMTP channel has no recipients".to_string());
+    }
+
+    let mut builder = Message::builder()
+        .from(
+            from.parse::<Mailbox>()
+                .map_err(|e| format!("Invalid sender '{}': {}", from, e))?,
+        )
+        .subject(title)
+        .header(ContentType::TEXT_PLAIN);
+    for recipient in to {
+        builder = builder.to(recipient
+            .parse::<Mailbox>()
+            .map_err(|e| format!("Invalid recipient '{}': {}", recipient, e))?);
+    }
+    let email = builder
+        .body(body.to_string())
+        .map_err(|e| format!("Failed to build email: {}", e))?;
+
+    let mut transport = match security.as_deref().unwrap_or("tls") {
+        "none" => SmtpTransport::builder_dangerous(host),
+        "starttls" => SmtpTransport::starttls_relay(host)
+            .map_err(|e| format!("Invalid SMTP host '{}': {}", host, e))?,
+        "tls" => SmtpTransport::relay(host)
+            .map_err(|e| format!("Invalid SMTP host '{}': {}", host, e))?,
+        other => return Err(format!("Unknown SMTP security '{}'", other)),
+    };
+    if let Some(port) = port {
+        transport = transport.port(*port);
+    }
+    if let (Some(user), Some(pass)) = (username, password) {
+        transport = transport.credentials(Credentials::new(user.clone(), pass.clone()));
+    }
+
+    transport
+        .timeout(Some(REQUEST_TIMEOUT))
+        .build()
+        .send(&email)
+        .map(|_| ())
+        .map_err(|e| format!("SMTP send failed: {}", e))
+}
+
+/// Server 酱 SendKey 对应的推送地址（`sctp` 开头的 Key 使用 Server 酱³）
+fn server_chan_url(send_key: &str, server: Option<&str>) -> String {
+    if let Some(server) = server.filter(|s| !s.trim().is_empty()) {
+        return format!("{}/{}.send", server.trim_end_matches('/'), send_key);
+    }
+    match SERVER_CHAN3_KEY.captures(send_key) {
+        Some(caps) => format!("https://{}.push.ft07.com/send/{}.send", &caps[1], send_key),
+        None => format!("https://sctapi.ftqq.com/{}.send", send_key),
+    }
+}
+
+/// 通过指定渠道发送一条通知
+pub fn send_notification(
+    channel: &NotificationChannel,
+    title: &str,
+    body: &str,
+) -> Result<(), String> {
+    info!(
+        "Sending notification via channel '{}' ({})",
+        channel.id, channel.name
+    );
+
+    let client = http_client()?;
+    match &channel.kind {
+        NotificationChannelKind::Smtp { .. } => send_smtp(&channel.kind, title, body)?,
+        NotificationChannelKind::Telegram {
+            bot_token,
+            chat_id,
+            api_base,
+        } => {
+            let base = api_base
+                .as_deref()
+                .filter(|s| !s.trim().is_empty())
+                .unwrap_or("https://api.telegram.org")
+                .trim_end_matches('/');
+            let url = format!("{}/bot{}/sendMessage", base, bot_token);
+            let text = post_json(
+                &client,
+                &url,
+                &serde_json::json!({
+                    "chat_id": chat_id,
+                    "text": format!("{}\n\n{}", title, body),
+                }),
+            )?;
+            // Telegram 在 HTTP 200 时也可能返回 ok=false
+            let ok = serde_json::from_str::<serde_json::Value>(&text)
+                .ok()
+                .and_then(|v| v.get("ok").and_then(|ok| ok.as_bool()))
+                .unwrap_or(true);
+            if !ok {
+                return Err(format!("Telegram API error: {}", text));
+            }
+        }
+        NotificationChannelKind::Discord { webhook_url } => {
+            post_json(
+                &client,
+                webhook_url,
+                &serde_json::json!({ "content": format!("**{}**\n{}", title, body) }),
+            )?;
+        }
+        NotificationChannelKind::Slack { webhook_url } => {
+            post_json(
+                &client,
+                webhook_url,
+                &serde_json::json!({ "text": format!("*{}*\n{}", title, body) }),
+            )?;
+        }
+        NotificationChannelKind::Bark {
+            device_key,
+            server,
+            group,
+            sound,
+        } => {
+            let server = server
+                .as_deref()
+                .filter(|s| !s.trim().is_empty())
+                .unwrap_or("https://api.day.app")
+                .trim_end_matches('/');
+            let mut payload = serde_json::json!({
+                "device_key": device_key,
+                "title": title,
+                "body": body,
+            });
+            if let Some(group) = group {
+                payload["group"] = serde_json::json!(group);
+            }
+            if let Some(sound) = sound {
+                payload["sound"] = serde_json::json!(sound);
+            }
+            post_json(&client, &format!("{}/push", server), &payload)?;
+        }
+        NotificationChannelKind::ServerChan { send_key, server } => {
+            let url = server_chan_url(send_key, server.as_deref());
+            let resp = client
+                .post(&url)
+                .form(&[("title", title), ("desp", body)])
+                .send()
+                .map_err(|e| format!("Request failed: {}", e))?;
+            check_response(resp)?;
+        }
+        NotificationChannelKind::Ntfy {
+            topic,
+            server,
+            token,
+            priority,
+        } => {
+            let server = server
+                .as_deref()
+                .filter(|s| !s.trim().is_empty())
+                .unwrap_or("https://ntfy.sh")
+                .trim_end_matches('/');
+            let mut payload = serde_json::json!({
+                "topic": topic,
+                "title": title,
+                "message": body,
+            });
+            if let Some(priority) = priority {
+                payload["priority"] = serde_json::json!(priority);
+            }
+            let mut request = client
+                .post(server)
+                .header(CONTENT_TYPE, "application/json")
+                .body(payload.to_string());
+            if let Some(token) = token.as_deref().filter(|t| !t.is_empty()) {
+                request = request.bearer_auth(token);
+            }
+            let resp = request
+                .send()
+                .map_err(|e| format!("Request failed: {}", e))?;
+            check_response(resp)?;
+        }
+        NotificationChannelKind::Http {
+            url,
+            method,
+            headers,
+            body: body_template,
+        } => {
+            let method = method.as_deref().unwrap_or("POST").to_ascii_uppercase();
+            let method = reqwest::Method::from_bytes(method.as_bytes())
+                .map_err(|_| format!("Invalid HTTP method '{}'", method))?;
+
+            let mut header_map = HeaderMap::new();
+            for (name, value) in headers.iter().flatten() {
+                let name = HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|e| format!("Invalid header name '{}': {}", name, e))?;
+                let value = HeaderValue::from_str(value)
+                    .map_err(|e| format!("Invalid header value for '{}': {}", name, e))?;
+                header_map.insert(name, value);
+            }
+            if !header_map.contains_key(CONTENT_TYPE) {
+                header_map.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+            }
+
+            let escape = header_map
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .map(body_escape)
+                .unwrap_or(Escape::Json);
+            let payload = body_template
+                .as_deref()
+                .unwrap_or(r#"{"title":"{title}","body":"{body}"}"#)
+                .replace("{title}", &escape.apply(title))
+                .replace("{body}", &escape.apply(body));
+
+            let resp = client
+                .request(method, url)
+                .headers(header_map)
+                .body(payload)
+                .send()
+                .map_err(|e| format!("Request failed: {}", e))?;
+            check_response(resp)?;
+        }
+    }
+
+    info!("Notification sent via channel '{}'", channel.id);
+    Ok(())
+}
+
+/// 通过指定的渠道发送通知（按 ID 或名称匹配，`all` 表示所有已启用渠道）
+/// 返回成功发送的渠道数，任一渠道失败时返回错误
+pub fn send_to_channels(selectors: &[String], title: &str, body: &str) -> Result<usize, String> {
+    let channels = load_channels()?;
+    let send_all = selectors.iter().any(|s| s.eq_ignore_ascii_case("all"));
+
+    let selected: Vec<&NotificationChannel> = channels
+        .iter()
+        .filter(|c| c.enabled && (send_all || selectors.iter().any(|s| *s == c.id || *s == c.name)))
+        .collect();
+
+    if selected.is_empty() {
+        return Err(format!(
+            "No enabled notification channel matches: {}",
+            selectors.join(", ")
+        ));
+    }
+
+    let mut errors = Vec::new();
+    for channel in &selected {
+        if let Err(e) = send_notification(channel, title, body) {
+            warn!("Notification channel '{}' failed: {}", channel.id, e);
+            errors.push(format!("{}: {}", channel.id, e));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(selected.len())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// 运行结束时通知订阅了对应事件的渠道（在后台线程中发送）
+pub fn notify_run_finished(instance_id: &str, succeeded: bool, last_error: &str) {
+    let event = if succeeded {
+        EVENT_RUN_SUCCEEDED
+    } else {
+        EVENT_RUN_FAILED
+    };
+
+    let channels: Vec<NotificationChannel> = match load_channels() {
+        Ok(channels) => channels
+            .into_iter()
+            .filter(|c| c.enabled && c.events.iter().any(|e| e == event))
+            .collect(),
+        Err(e) => {
+            warn!("Failed to load notification channels: {}", e);
+            return;
+        }
+    };
+    if channels.is_empty() {
+        return;
+    }
+
+    let (title, body) = {
+        let messages = RUN_MESSAGES.read().unwrap_or_else(|e| e.into_inner());
+        let template = if succeeded {
+            &messages.run_succeeded
+        } else if last_error.is_empty() {
+            &messages.run_failed
+        } else {
+            &messages.run_failed_with_error
+        };
+        (
+            messages.title.clone(),
+            template
+                .replace("{instance}", instance_id)
+                .replace("{error}", last_error),
+        )
+    };
+
+    std::thread::spawn(move || {
+        for channel in &channels {
+            if let Err(e) = send_notification(channel, &title, &body) {
+                warn!("Notification channel '{}' failed: {}", channel.id, e);
+            }
+        }
+    });
+}
+
+// ============================================================================
+// Tauri 命令
+// ============================================================================
+
+/// 获取通知渠道配置
+#[tauri::command]
+pub fn get_notification_channels() -> Result<Vec<NotificationChannel>, String> {
+    load_channels()
+}
+
+/// 保存通知渠道配置
+#[tauri::command]
+pub fn save_notification_channels(channels: Vec<NotificationChannel>) -> Result<(), String> {
+    info!("save_notification_channels: {} channel(s)", channels.len());
+
+    let path = channels_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("无法创建目录 [{}]: {}", parent.display(), e))?;
+    }
+    let content = serde_json::to_string_pretty(&channels).map_err(|e| e.to_string())?;
+    std::fs::write(&path, content)
+        .map_err(|e| format!("保存通知渠道配置失败 [{}]: {}", path.display(), e))?;
+
+    // 文件中包含明文凭据，仅允许当前用户读写
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Err(e) = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)) {
+            warn!(
+                "Failed to restrict permissions of {}: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+/// 获取当前语言的通知文案
+pub fn notification_messages() -> NotificationMessages {
+    RUN_MESSAGES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// 设置后端通知的文案（前端在切换语言时调用）
+#[tauri::command]
+pub fn set_notification_messages(messages: NotificationMessages) {
+    *RUN_MESSAGES.write().unwrap_or_else(|e| e.into_inner()) = messages;
+}
+
+/// 通过指定渠道发送一条测试通知，message 为空时使用英文默认文案
+#[tauri::command]
+pub async fn test_notification_channel(
+    channel: NotificationChannel,
+    message: Option<String>,
+) -> Result<(), String> {
+    let message = message
+        .filter(|m| !m.trim().is_empty())
+        .unwrap_or_else(|| "This is a test notification from MXU".to_string());
+    tokio::task::spawn_blocking(move || send_notification(&channel, "MXU", &message))
+        .await
+        .map_err(|e| format!("Notification task panicked: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    /// 模拟服务器收到的请求
+    struct CapturedRequest {
+        method: String,
+        path: String,
+        headers: HashMap<String, String>,
+        body: String,
+    }
+
+    impl CapturedRequest {
+        fn json(&self) -> serde_json::Value {
+            serde_json::from_str(&self.body).expect("request body is not JSON")
+        }
+    }
+
+    /// 启动只处理一个请求的模拟服务器，返回其地址与捕获结果
+    fn mock_server() -> (String, JoinHandle<CapturedRequest>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut parts = request_line.split_whitespace();
+            let method = parts.next().unwrap_or_default().to_string();
+            let path = parts.next().unwrap_or_default().to_string();
+
+            let mut headers = HashMap::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+                }
+            }
+
+            let length: usize = headers
+                .get("content-length")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0);
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+
+            let reply = b"{\"ok\":true}";
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                reply.len()
+            )
+            .unwrap();
+            stream.write_all(reply).unwrap();
+
+            CapturedRequest {
+                method,
+                path,
+                headers,
+                body: String::from_utf8(body).unwrap(),
+            }
+        });
+        (addr, handle)
+    }
+
+    fn channel(kind: NotificationChannelKind) -> NotificationChannel {
+        NotificationChannel {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            enabled: true,
+            events: Vec::new(),
+            kind,
+        }
+    }
+
+    fn send(kind: NotificationChannelKind, handle: JoinHandle<CapturedRequest>) -> CapturedRequest {
+        send_notification(&channel(kind), "Title", "Line \"1\"\nLine 2").unwrap();
+        handle.join().unwrap()
+    }
+
+    #[test]
+    fn telegram_posts_send_message() {
+        let (addr, handle) = mock_server();
+        let req = send(
+            NotificationChannelKind::Telegram {
+                bot_token: "123:abc".to_string(),
+                chat_id: "42".to_string(),
+                api_base: Some(format!("{}/", addr)),
+            },
+            handle,
+        );
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.path, "/bot123:abc/sendMessage");
+        assert_eq!(req.headers["content-type"], "application/json");
+        assert_eq!(
+            req.json(),
+            serde_json::json!({ "chat_id": "42", "text": "Title\n\nLine \"1\"\nLine 2" })
+        );
+    }
+
+    #[test]
+    fn discord_posts_content() {
+        let (addr, handle) = mock_server();
+        let req = send(
+            NotificationChannelKind::Discord {
+                webhook_url: format!("{}/api/webhooks/1/token", addr),
+            },
+            handle,
+        );
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.path, "/api/webhooks/1/token");
+        assert_eq!(
+            req.json(),
+            serde_json::json!({ "content": "**Title**\nLine \"1\"\nLine 2" })
+        );
+    }
+
+    #[test]
+    fn slack_posts_text() {
+        let (addr, handle) = mock_server();
+        let req = send(
+            NotificationChannelKind::Slack {
+                webhook_url: format!("{}/services/T/B/X", addr),
+            },
+            handle,
+        );
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.path, "/services/T/B/X");
+        assert_eq!(
+            req.json(),
+            serde_json::json!({ "text": "*Title*\nLine \"1\"\nLine 2" })
+        );
+    }
+
+    #[test]
+    fn bark_posts_push_with_optional_fields() {
+        let (addr, handle) = mock_server();
+        let req = send(
+            NotificationChannelKind::Bark {
+                device_key: "key".to_string(),
+                server: Some(addr),
+                group: Some("mxu".to_string()),
+                sound: None,
+            },
+            handle,
+        );
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.path, "/push");
+        assert_eq!(
+            req.json(),
+            serde_json::json!({
+                "device_key": "key",
+                "title": "Title",
+                "body": "Line \"1\"\nLine 2",
+                "group": "mxu",
+            })
+        );
+    }
+
+    #[test]
+    fn ntfy_posts_json_with_bearer_token() {
+        let (addr, handle) = mock_server();
+        let req = send(
+            NotificationChannelKind::Ntfy {
+                topic: "mxu".to_string(),
+                server: Some(format!("{}/", addr)),
+                token: Some("tk_secret".to_string()),
+                priority: Some(4),
+            },
+            handle,
+        );
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.path, "/");
+        assert_eq!(req.headers["authorization"], "Bearer tk_secret");
+        assert_eq!(
+            req.json(),
+            serde_json::json!({
+                "topic": "mxu",
+                "title": "Title",
+                "message": "Line \"1\"\nLine 2",
+                "priority": 4,
+            })
+        );
+    }
+
+    #[test]
+    fn http_default_template_escapes_json() {
+        let (addr, handle) = mock_server();
+        let req = send(
+            NotificationChannelKind::Http {
+                url: format!("{}/hook", addr),
+                method: None,
+                headers: None,
+                body: None,
+            },
+            handle,
+        );
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.path, "/hook");
+        assert_eq!(req.headers["content-type"], "application/json");
+        assert_eq!(
+            req.json(),
+            serde_json::json!({ "title": "Title", "body": "Line \"1\"\nLine 2" })
+        );
+    }
+
+    #[test]
+    fn http_custom_method_headers_and_body() {
+        let (addr, handle) = mock_server();
+        let req = send(
+            NotificationChannelKind::Http {
+                url: format!("{}/notify?src=mxu", addr),
+                method: Some("put".to_string()),
+                headers: Some(HashMap::from([
+                    ("Content-Type".to_string(), "text/plain".to_string()),
+                    ("X-Token".to_string(), "abc".to_string()),
+                ])),
+                body: Some("[{title}] {body}".to_string()),
+            },
+            handle,
+        );
+        assert_eq!(req.method, "PUT");
+        assert_eq!(req.path, "/notify?src=mxu");
+        assert_eq!(req.headers["content-type"], "text/plain");
+        assert_eq!(req.headers["x-token"], "abc");
+        assert_eq!(req.body, "[Title] Line \"1\"\nLine 2");
+    }
+
+    #[test]
+    fn http_form_body_is_url_encoded() {
+        let (addr, handle) = mock_server();
+        let req = send(
+            NotificationChannelKind::Http {
+                url: format!("{}/form", addr),
+                method: None,
+                headers: Some(HashMap::from([(
+                    "content-type".to_string(),
+                    "application/x-www-form-urlencoded; charset=utf-8".to_string(),
+                )])),
+                body: Some("title={title}&body={body}".to_string()),
+            },
+            handle,
+        );
+        assert_eq!(req.body, "title=Title&body=Line%20%221%22%0ALine%202");
+    }
+
+    #[test]
+    fn body_escape_follows_content_type() {
+        assert_eq!(body_escape("application/json"), Escape::Json);
+        assert_eq!(
+            body_escape("Application/Problem+JSON; charset=utf-8"),
+            Escape::Json
+        );
+        assert_eq!(
+            body_escape("application/x-www-form-urlencoded"),
+            Escape::Url
+        );
+        assert_eq!(body_escape("text/plain; charset=utf-8"), Escape::Raw);
+    }
+
+    /// 启动只处理一次会话的模拟 SMTP 服务器（无加密、无认证），返回端口与收到的命令及邮件内容
+    fn mock_smtp_server() -> (u16, JoinHandle<(Vec<String>, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut commands = Vec::new();
+            let mut data = String::new();
+
+            writer.write_all(b"220 mock ESMTP\r\n").unwrap();
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 {
+                    break;
+                }
+                let line = line.trim_end().to_string();
+                let verb = line
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or_default()
+                    .to_ascii_uppercase();
+                commands.push(line);
+                let reply: &[u8] = match verb.as_str() {
+                    "DATA" => {
+                        writer.write_all(b"354 go ahead\r\n").unwrap();
+                        loop {
+                            let mut line = String::new();
+                            reader.read_line(&mut line).unwrap();
+                            if line == ".\r\n" {
+                                break;
+                            }
+                            data.push_str(&line);
+                        }
+                        b"250 queued\r\n"
+                    }
+                    "QUIT" => {
+                        writer.write_all(b"221 bye\r\n").unwrap();
+                        break;
+                    }
+                    _ => b"250 ok\r\n",
+                };
+                writer.write_all(reply).unwrap();
+            }
+            (commands, data)
+        });
+        (port, handle)
+    }
+
+    #[test]
+    fn smtp_sends_plain_text_mail_to_all_recipients() {
+        let (port, handle) = mock_smtp_server();
+        send_notification(
+            &channel(NotificationChannelKind::Smtp {
+                host: "127.0.0.1".to_string(),
+                port: Some(port),
+                security: Some("none".to_string()),
+                username: None,
+                password: None,
+                from: "MXU <mxu@example.com>".to_string(),
+                to: vec!["a@example.com".to_string(), "b@example.com".to_string()],
+            }),
+            "Title",
+            "Line 1\nLine 2",
+        )
+        .unwrap();
+
+        let (commands, data) = handle.join().unwrap();
+        for expected in [
+            "MAIL FROM:<mxu@example.com>",
+            "RCPT TO:<a@example.com>",
+            "RCPT TO:<b@example.com>",
+        ] {
+            assert!(
+                commands.iter().any(|c| c == expected),
+                "missing {}: {:?}",
+                expected,
+                commands
+            );
+        }
+        assert!(data.contains("Subject: Title\r\n"), "{}", data);
+        assert!(data.contains("Content-Type: text/plain"), "{}", data);
+        assert!(data.contains("Line 1\r\nLine 2"), "{}", data);
+    }
+
+    #[test]
+    fn smtp_rejects_invalid_config() {
+        let smtp = |security: &str, to: Vec<String>| {
+            channel(NotificationChannelKind::Smtp {
+                host: "127.0.0.1".to_string(),
+                port: Some(1),
+                security: Some(security.to_string()),
+                username: None,
+                password: None,
+                from: "mxu@example.com".to_string(),
+                to,
+            })
+        };
+        let to = vec!["a@example.com".to_string()];
+        assert_eq!(
+            send_notification(&smtp("none", Vec::new()), "T", "B").unwrap_err(),
+            "SMTP channel has no recipients"
+        );
+        assert_eq!(
+            send_notification(&smtp("ssl3", to.clone()), "T", "B").unwrap_err(),
+            "Unknown SMTP security 'ssl3'"
+        );
+        assert!(
+            send_notification(&smtp("none", vec!["not an address".to_string()]), "T", "B")
+                .unwrap_err()
+                .starts_with("Invalid recipient 'not an address'")
+        );
+    }
+
+    #[test]
+    fn server_chan_url_by_key_format() {
+        assert_eq!(
+            server_chan_url("sctp123tabc", None),
+            "https://123.push.ft07.com/send/sctp123tabc.send"
+        );
+        assert_eq!(
+            server_chan_url("SCT456", None),
+            "https://sctapi.ftqq.com/SCT456.send"
+        );
+        assert_eq!(
+            server_chan_url("SCT456", Some("http://localhost:8080/")),
+            "http://localhost:8080/SCT456.send"
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! 运行历史命令
+//!
+//! 每轮运行结束（成功、失败或被停止）时在日志目录的 `mxu-run-history.jsonl` 中追加一条记录，
+//! 包含运行结果以及 MXU_LOG 动作写入的日志，只保留最近 [`RUN_HISTORY_MAX_RUNS`] 轮。
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use log::warn;
+use once_cell::sync::Lazy;
+
+use super::types::RunHistoryEntry;
+use super::utils::get_logs_dir;
+
+/// 历史文件名（位于日志目录）
+const RUN_HISTORY_FILE_NAME: &str = "mxu-run-history.jsonl";
+/// 最多保留的运行轮数
+const RUN_HISTORY_MAX_RUNS: usize = 200;
+
+/// 串行化历史文件的读写
+static HISTORY_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+fn history_path() -> PathBuf {
+    get_logs_dir().join(RUN_HISTORY_FILE_NAME)
+}
+
+/// 读取全部历史（按时间从旧到新），跳过无法解析的行
+fn load_history() -> Vec<RunHistoryEntry> {
+    let Ok(content) = std::fs::read_to_string(history_path()) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// 追加一轮运行记录，超出上限时丢弃最旧的记录
+pub fn append_run(entry: RunHistoryEntry) {
+    let _guard = HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut runs = load_history();
+    runs.push(entry);
+    if runs.len() > RUN_HISTORY_MAX_RUNS {
+        runs.drain(..runs.len() - RUN_HISTORY_MAX_RUNS);
+    }
+
+    let path = history_path();
+    let mut content = String::new();
+    for run in &runs {
+        match serde_json::to_string(run) {
+            Ok(line) => {
+                content.push_str(&line);
+                content.push('\n');
+            }
+            Err(e) => warn!("Failed to serialize run history entry: {}", e),
+        }
+    }
+    // 先写临时文件再重命名，避免写入中断导致文件损坏
+    let tmp = path.with_extension("jsonl.tmp");
+    let result = std::fs::create_dir_all(get_logs_dir())
+        .and_then(|_| std::fs::write(&tmp, content))
+        .and_then(|_| std::fs::rename(&tmp, &path));
+    if let Err(e) = result {
+        warn!("Failed to save run history {}: {}", path.display(), e);
+    }
+}
+
+/// 获取运行历史（最新的在前），可按实例过滤并限制条数
+#[tauri::command]
+pub fn get_run_history(instance_id: Option<String>, limit: Option<usize>) -> Vec<RunHistoryEntry> {
+    let _guard = HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    load_history()
+        .into_iter()
+        .rev()
+        .filter(|run| instance_id.as_ref().is_none_or(|id| &run.instance_id == id))
+        .take(limit.unwrap_or(usize::MAX))
+        .collect()
+}
@@ -10,7 +10,9 @@ use tauri::State;
 
 use crate::maa_ffi::MAA_LIBRARY;
 
-use super::types::{AdbDevice, AllInstanceStates, InstanceState, MaaState, Win32Window};
+use super::types::{
+    AdbDevice, AgentOutputPage, AllInstanceStates, InstanceState, MaaState, Win32Window,
+};
 
 /// 获取单个实例的运行时状态
 #[tauri::command]
@@ -47,6 +49,7 @@ pub fn maa_get_instance_state(
     let is_running = instance.tasker.map_or(false, |tasker| unsafe {
         (lib.maa_tasker_running)(tasker) != 0
     });
+    let is_paused = is_running && instance.tasker.is_some_and(crate::mxu_actions::is_paused);
     if !is_running && instance.stop_in_progress {
         instance.stop_in_progress = false;
         instance.stop_started_at = None;
@@ -57,6 +60,7 @@ pub fn maa_get_instance_state(
         resource_loaded,
         tasker_inited,
         is_running,
+        is_paused,
         task_ids: instance.task_ids.clone(),
     })
 }
@@ -97,6 +101,8 @@ pub fn maa_get_all_states(state: State<Arc<MaaState>>) -> Result<AllInstanceStat
             let is_running = instance.tasker.map_or(false, |tasker| unsafe {
                 (lib.maa_tasker_running)(tasker) != 0
             });
+            let is_paused =
+                is_running && instance.tasker.is_some_and(crate::mxu_actions::is_paused);
             if !is_running && instance.stop_in_progress {
                 instance.stop_in_progress = false;
                 instance.stop_started_at = None;
@@ -109,6 +115,7 @@ pub fn maa_get_all_states(state: State<Arc<MaaState>>) -> Result<AllInstanceStat
                     resource_loaded,
                     tasker_inited,
                     is_running,
+                    is_paused,
                     task_ids: instance.task_ids.clone(),
                 },
             );
@@ -142,3 +149,34 @@ pub fn maa_get_cached_win32_windows(
         .map_err(|e| e.to_string())?;
     Ok(cached.clone())
 }
+
+/// 拉取实例最近的 agent 输出（用于前端刷新或窗口重新显示后回填日志）
+///
+/// `cursor` 为上次返回的 `next_cursor`，首次拉取传 0 或不传；
+/// `limit` 默认返回 500 条
+#[tauri::command]
+pub fn maa_get_agent_output(
+    state: State<Arc<MaaState>>,
+    instance_id: String,
+    cursor: Option<u64>,
+    limit: Option<usize>,
+) -> Result<AgentOutputPage, String> {
+    debug!(
+        "maa_get_agent_output called, instance_id: {}, cursor: {:?}, limit: {:?}",
+        instance_id, cursor, limit
+    );
+
+    let cursor = cursor.unwrap_or(0);
+    let limit = limit.unwrap_or(500);
+    let buffers = state.agent_output.lock().map_err(|e| e.to_string())?;
+
+    Ok(match buffers.get(&instance_id) {
+        Some(buffer) => buffer.since(cursor, limit),
+        None => AgentOutputPage {
+            entries: Vec::new(),
+            next_cursor: cursor,
+            has_more: false,
+            truncated: false,
+        },
+    })
+}
@@ -331,3 +331,12 @@ pub fn get_system_info() -> SystemInfo {
         tauri_version,
     }
 }
+
+/// 取消倒计时中的 MXU_POWER 操作（`id` 为倒计时事件中的 cancel_id，为空时取消全部），
+/// 没有可取消的操作时返回 false
+#[tauri::command]
+pub fn cancel_power_action(id: Option<u64>) -> bool {
+    let cancelled = crate::power::cancel_pending(id);
+    info!("cancel_power_action({:?}): {} cancelled", id, cancelled);
+    cancelled > 0
+}
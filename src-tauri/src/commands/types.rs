@@ -2,7 +2,8 @@
 //!
 //! 包含 Tauri 命令使用的数据结构和枚举
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::io::Write;
 use std::path::PathBuf;
 use std::process::Child;
 use std::sync::Mutex;
@@ -10,6 +11,7 @@ use std::time::Instant;
 
 use serde::{Deserialize, Serialize};
 
+use crate::agent_log::AgentLogRecord;
 use crate::maa_ffi::{MaaAgentClient, MaaController, MaaResource, MaaTasker, MAA_LIBRARY};
 
 // ============================================================================
@@ -117,6 +119,8 @@ pub struct InstanceState {
     pub tasker_inited: bool,
     /// 是否有任务正在运行（通过 MaaTaskerRunning API 查询）
     pub is_running: bool,
+    /// 任务是否已暂停
+    pub is_paused: bool,
     /// 当前运行的任务 ID 列表
     pub task_ids: Vec<i64>,
 }
@@ -134,6 +138,10 @@ pub struct InstanceRuntime {
     pub resource: Option<*mut MaaResource>,
     pub controller: Option<*mut MaaController>,
     pub tasker: Option<*mut MaaTasker>,
+    /// 最近一次连接使用的控制器配置（供 MXU_ADB_SHELL 等动作读取）
+    pub controller_config: Option<ControllerConfig>,
+    /// 已加载到 resource 的资源目录（供 MXU_SCRIPT 等动作查找资源文件）
+    pub resource_paths: Vec<String>,
     pub agent_clients: Vec<*mut MaaAgentClient>,
     pub agent_children: Vec<Child>,
     /// 当前运行的任务 ID 列表（用于刷新后恢复状态）
@@ -155,6 +163,8 @@ impl Default for InstanceRuntime {
             resource: None,
             controller: None,
             tasker: None,
+            controller_config: None,
+            resource_paths: Vec::new(),
             agent_clients: Vec::new(),
             agent_children: Vec::new(),
             task_ids: Vec::new(),
@@ -164,6 +174,22 @@ impl Default for InstanceRuntime {
     }
 }
 
+impl InstanceRuntime {
+    /// 向所有 agent 子进程的 stdin 写入一行事件通知，如 `{"event":"pause"}`
+    ///
+    /// 不关心通知的 agent 可以忽略 stdin，写入失败（如进程已退出）时忽略
+    pub fn notify_agents(&mut self, event: &str) {
+        let line = format!("{}\n", serde_json::json!({ "event": event }));
+        for child in &mut self.agent_children {
+            if let Some(stdin) = child.stdin.as_mut() {
+                if let Err(e) = stdin.write_all(line.as_bytes()).and_then(|_| stdin.flush()) {
+                    log::debug!("Failed to notify agent {}: {}", child.id(), e);
+                }
+            }
+        }
+    }
+}
+
 impl Drop for InstanceRuntime {
     fn drop(&mut self) {
         if let Ok(guard) = MAA_LIBRARY.lock() {
@@ -171,6 +197,7 @@ impl Drop for InstanceRuntime {
                 unsafe {
                     // 断开并销毁所有 agent
                     for agent in self.agent_clients.drain(..) {
+                        super::maa_agent::unwatch_attached_agent(agent);
                         (lib.maa_agent_client_disconnect)(agent);
                         (lib.maa_agent_client_destroy)(agent);
                     }
@@ -194,6 +221,98 @@ impl Drop for InstanceRuntime {
     }
 }
 
+/// 每个实例保留的 agent 输出行数上限
+pub const AGENT_OUTPUT_BUFFER_CAPACITY: usize = 2000;
+
+/// Agent 输出缓冲中的单条记录
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentOutputEntry {
+    /// 单调递增序号（作为拉取游标使用）
+    pub seq: u64,
+    /// 输出时间（毫秒时间戳）
+    pub timestamp: i64,
+    /// Agent 序号
+    pub agent_index: usize,
+    /// 输出流类型: "stdout" 或 "stderr"
+    pub stream: String,
+    /// 原始输出内容
+    pub line: String,
+    /// 若该行符合结构化日志协议，则为解析后的记录
+    pub record: Option<AgentLogRecord>,
+}
+
+/// 单个实例的 agent 输出环形缓冲
+pub struct AgentOutputBuffer {
+    entries: VecDeque<AgentOutputEntry>,
+    next_seq: u64,
+}
+
+impl Default for AgentOutputBuffer {
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::with_capacity(AGENT_OUTPUT_BUFFER_CAPACITY),
+            // 从 1 开始，游标 0 表示"从头拉取"
+            next_seq: 1,
+        }
+    }
+}
+
+impl AgentOutputBuffer {
+    /// 追加一行输出，超出容量时丢弃最旧的记录
+    pub fn push(
+        &mut self,
+        agent_index: usize,
+        stream: &str,
+        line: &str,
+        record: Option<AgentLogRecord>,
+    ) {
+        if self.entries.len() >= AGENT_OUTPUT_BUFFER_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(AgentOutputEntry {
+            seq: self.next_seq,
+            timestamp: chrono::Local::now().timestamp_millis(),
+            agent_index,
+            stream: stream.to_string(),
+            line: line.to_string(),
+            record,
+        });
+        self.next_seq += 1;
+    }
+
+    /// 获取序号大于 `cursor` 的记录（最多 `limit` 条）
+    pub fn since(&self, cursor: u64, limit: usize) -> AgentOutputPage {
+        let oldest_seq = self.entries.front().map(|e| e.seq).unwrap_or(self.next_seq);
+        let entries: Vec<AgentOutputEntry> = self
+            .entries
+            .iter()
+            .filter(|e| e.seq > cursor)
+            .take(limit)
+            .cloned()
+            .collect();
+        let next_cursor = entries.last().map(|e| e.seq).unwrap_or(cursor);
+        AgentOutputPage {
+            has_more: next_cursor + 1 < self.next_seq,
+            // 游标之后、最旧记录之前的内容已被覆盖
+            truncated: cursor + 1 < oldest_seq,
+            entries,
+            next_cursor,
+        }
+    }
+}
+
+/// Agent 输出拉取结果
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentOutputPage {
+    pub entries: Vec<AgentOutputEntry>,
+    /// 下次拉取时传入的游标
+    pub next_cursor: u64,
+    /// 是否还有更多记录（受 limit 限制未返回）
+    pub has_more: bool,
+    /// 游标之后是否有记录因缓冲已满而被丢弃
+    pub truncated: bool,
+}
+
 /// MaaFramework 运行时状态
 pub struct MaaState {
     pub lib_dir: Mutex<Option<PathBuf>>,
@@ -203,6 +322,8 @@ pub struct MaaState {
     pub cached_adb_devices: Mutex<Vec<AdbDevice>>,
     /// 缓存的 Win32 窗口列表（全局共享）
     pub cached_win32_windows: Mutex<Vec<Win32Window>>,
+    /// 各实例最近的 agent 输出（用于前端刷新/重新显示后回填日志）
+    pub agent_output: Mutex<HashMap<String, AgentOutputBuffer>>,
 }
 
 impl Default for MaaState {
@@ -213,11 +334,31 @@ impl Default for MaaState {
             instances: Mutex::new(HashMap::new()),
             cached_adb_devices: Mutex::new(Vec::new()),
             cached_win32_windows: Mutex::new(Vec::new()),
+            agent_output: Mutex::new(HashMap::new()),
         }
     }
 }
 
 impl MaaState {
+    /// 记录一行 agent 输出到对应实例的环形缓冲
+    pub fn push_agent_output(
+        &self,
+        instance_id: &str,
+        agent_index: usize,
+        stream: &str,
+        line: &str,
+        record: Option<AgentLogRecord>,
+    ) {
+        if let Ok(mut buffers) = self.agent_output.lock() {
+            buffers.entry(instance_id.to_string()).or_default().push(
+                agent_index,
+                stream,
+                line,
+                record,
+            );
+        }
+    }
+
     /// 清理所有实例的 agent 子进程
     pub fn cleanup_all_agent_children(&self) {
         if let Ok(mut instances) = self.instances.lock() {
@@ -242,11 +383,21 @@ impl MaaState {
 /// Agent 配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentConfig {
+    /// 附加模式下可为空
+    #[serde(default)]
     pub child_exec: String,
     pub child_args: Option<Vec<String>>,
     pub identifier: Option<String>,
     /// 连接超时时间（毫秒），-1 表示无限等待
     pub timeout: Option<i64>,
+    /// 附加模式：不启动子进程，等待已在运行的 agent 通过 `identifier` 或 `tcp_port` 连接，
+    /// 运行中断线时按 `timeout` 等待其重新连接
+    #[serde(default)]
+    pub attach: bool,
+    /// 附加模式下使用的固定 TCP 端口（优先于 `identifier`），只监听本机回环地址，
+    /// 远程 agent 需通过端口转发映射到本机
+    #[serde(default)]
+    pub tcp_port: Option<u16>,
 }
 
 /// 任务配置
@@ -307,3 +458,262 @@ pub struct SystemInfo {
     pub arch: String,
     pub tauri_version: String,
 }
+
+/// 日志保留策略（0 表示不限制对应项）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogRetentionSettings {
+    /// 日志保留天数
+    pub max_age_days: u32,
+    /// 最多保留的日志文件数
+    pub max_files: u32,
+    /// 日志总大小上限（MB）
+    pub max_total_size_mb: u64,
+    /// 单个日志文件大小上限（MB），超过后轮转
+    pub max_file_size_mb: u64,
+}
+
+impl Default for LogRetentionSettings {
+    fn default() -> Self {
+        Self {
+            max_age_days: 7,
+            max_files: 50,
+            max_total_size_mb: 200,
+            max_file_size_mb: 10,
+        }
+    }
+}
+
+impl LogRetentionSettings {
+    /// 单个日志文件大小上限（字节），0 表示不轮转
+    pub fn max_file_size_bytes(&self) -> u64 {
+        self.max_file_size_mb * 1024 * 1024
+    }
+}
+
+/// 日志清理结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LogRetentionResult {
+    pub deleted_files: usize,
+    pub freed_bytes: u64,
+    pub failed_files: usize,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// 通知渠道配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationChannel {
+    /// 渠道 ID（MXU_NOTIFY 的 channel 参数可使用 ID 或名称）
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// 自动触发该渠道的运行事件：`run_succeeded` / `run_failed`
+    #[serde(default)]
+    pub events: Vec<String>,
+    #[serde(flatten)]
+    pub kind: NotificationChannelKind,
+}
+
+/// 通知渠道类型及其参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NotificationChannelKind {
+    /// SMTP 邮件，security 为 `tls`（默认）/ `starttls` / `none`
+    Smtp {
+        host: String,
+        port: Option<u16>,
+        security: Option<String>,
+        username: Option<String>,
+        password: Option<String>,
+        from: String,
+        to: Vec<String>,
+    },
+    /// Telegram Bot，api_base 默认 `https://api.telegram.org`
+    Telegram {
+        bot_token: String,
+        chat_id: String,
+        api_base: Option<String>,
+    },
+    Discord {
+        webhook_url: String,
+    },
+    Slack {
+        webhook_url: String,
+    },
+    /// Bark，server 默认 `https://api.day.app`
+    Bark {
+        device_key: String,
+        server: Option<String>,
+        group: Option<String>,
+        sound: Option<String>,
+    },
+    /// Server 酱，server 默认按 SendKey 推断
+    ServerChan {
+        send_key: String,
+        server: Option<String>,
+    },
+    /// ntfy，server 默认 `https://ntfy.sh`
+    Ntfy {
+        topic: String,
+        server: Option<String>,
+        token: Option<String>,
+        priority: Option<u8>,
+    },
+    /// 通用 HTTP，body 为模板，可使用 `{title}`、`{body}` 占位符（按 JSON 字符串转义）
+    Http {
+        url: String,
+        method: Option<String>,
+        headers: Option<HashMap<String, String>>,
+        body: Option<String>,
+    },
+}
+
+/// 后端发送的通知文案，由前端按当前界面语言下发
+/// 运行结束通知可使用 `{instance}`、`{error}` 占位符，
+/// MXU_POWER 通知可使用 `{action}`、`{seconds}` 占位符
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationMessages {
+    pub title: String,
+    pub run_succeeded: String,
+    pub run_failed: String,
+    /// 带错误信息的失败文案
+    pub run_failed_with_error: String,
+    /// 电源操作倒计时开始
+    pub power_countdown: String,
+    /// 电源操作被用户取消
+    pub power_cancelled: String,
+    /// 其它实例仍在运行，跳过电源操作
+    pub power_skipped: String,
+    /// 电源操作名称，键为 `shutdown` / `restart` / `screenoff` / `sleep` / `hibernate` / `logoff`
+    pub power_actions: BTreeMap<String, String>,
+}
+
+impl Default for NotificationMessages {
+    fn default() -> Self {
+        let power_actions = [
+            ("shutdown", "Shut down"),
+            ("restart", "Restart"),
+            ("screenoff", "Turn off screen"),
+            ("sleep", "Sleep"),
+            ("hibernate", "Hibernate"),
+            ("logoff", "Log off"),
+        ];
+        Self {
+            title: "MXU".to_string(),
+            run_succeeded: "All tasks of {instance} have finished".to_string(),
+            run_failed: "Tasks of {instance} failed".to_string(),
+            run_failed_with_error: "Tasks of {instance} failed: {error}".to_string(),
+            power_countdown: "{action} in {seconds}s, you can cancel it in MXU".to_string(),
+            power_cancelled: "{action} cancelled".to_string(),
+            power_skipped: "Other instances are still running, {action} skipped".to_string(),
+            power_actions: power_actions
+                .into_iter()
+                .map(|(key, name)| (key.to_string(), name.to_string()))
+                .collect(),
+        }
+    }
+}
+
+/// 持久化变量
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistentVariable {
+    pub value: serde_json::Value,
+    /// 每日重置时间（本地时间的小时，0-23），为空表示不自动重置
+    #[serde(default)]
+    pub daily_reset_hour: Option<u8>,
+    /// 每日重置后的值
+    #[serde(default = "default_reset_value")]
+    pub reset_value: serde_json::Value,
+    /// 当前值所属的重置周期（按 daily_reset_hour 划分的日期，如 "2026-10-18"）
+    #[serde(default)]
+    pub period: Option<String>,
+    /// 最后修改时间（RFC 3339）
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+fn default_reset_value() -> serde_json::Value {
+    serde_json::Value::from(0)
+}
+
+/// 持久化变量存储：全局变量与按实例划分的变量
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PersistentVariables {
+    #[serde(default)]
+    pub global: BTreeMap<String, PersistentVariable>,
+    /// 实例 ID -> 变量
+    #[serde(default)]
+    pub instances: BTreeMap<String, BTreeMap<String, PersistentVariable>>,
+}
+
+/// MXU 内置动作 / 识别及插件动作的参数定义
+#[derive(Debug, Clone, Serialize)]
+pub struct MxuActionSchema {
+    /// 注册名，如 `MXU_SLEEP_ACTION`
+    pub name: String,
+    /// `action` 或 `recognition`
+    pub kind: String,
+    /// 参数的 JSON Schema
+    pub schema: serde_json::Value,
+    /// 来源插件名，内置动作为 None
+    pub plugin: Option<String>,
+    /// 插件声明的动作说明
+    pub description: Option<String>,
+}
+
+/// 插件目录中的一个插件及其加载状态
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginInfo {
+    /// 插件目录（允许列表中的标识）
+    pub dir: String,
+    pub name: String,
+    /// `library` / `executable`，plugin.json 无效时为 None
+    pub kind: Option<String>,
+    /// 用户是否允许加载
+    pub allowed: bool,
+    /// `loading` / `loaded` / `failed` / `not_loaded`
+    pub status: String,
+    /// 已注册的动作名
+    pub actions: Vec<String>,
+    pub error: Option<String>,
+    /// 允许设置与本次启动加载的插件不一致，需重启生效
+    pub restart_required: bool,
+}
+
+/// 运行历史中的一条日志（由 MXU_LOG 动作写入）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunLogEntry {
+    /// 本地时间，如 `2024-01-01 12:00:00`
+    pub time: String,
+    /// `trace` / `debug` / `info` / `warn` / `error`
+    pub level: String,
+    /// 所在 pipeline 节点
+    pub node: String,
+    pub message: String,
+    /// 附带的截图路径
+    #[serde(default)]
+    pub image: Option<String>,
+}
+
+/// 一轮运行的历史记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunHistoryEntry {
+    pub instance_id: String,
+    /// 开始时间（本地时间）
+    pub started_at: String,
+    /// 结束时间（本地时间）
+    pub finished_at: String,
+    /// `succeeded` / `failed` / `stopped`
+    pub result: String,
+    /// 最近一次失败信息
+    #[serde(default)]
+    pub last_error: String,
+    #[serde(default)]
+    pub logs: Vec<RunLogEntry>,
+}
@@ -361,7 +361,7 @@ fn copy_dir_contents(src: &str, dst: &str, skip_files: Option<&[&str]>) -> Resul
 }
 
 /// 递归复制整个目录
-fn copy_dir_recursive(src: &std::path::Path, dst: &std::path::Path) -> Result<(), String> {
+pub fn copy_dir_recursive(src: &std::path::Path, dst: &std::path::Path) -> Result<(), String> {
     std::fs::create_dir_all(dst).map_err(|e| format!("无法创建目录 [{}]: {}", dst.display(), e))?;
 
     for entry in
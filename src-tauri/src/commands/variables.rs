@@ -0,0 +1,421 @@
+//! 持久化变量命令
+//!
+//! 为 pipeline 提供跨运行保存的键值存储（如「今日已刷取次数」），
+//! 供 MXU_VARIABLE 动作、MXU_VARIABLE 识别以及前端查看/编辑使用。
+//! 变量分为全局变量与按实例划分的变量，保存在数据目录的 `config/mxu-variables.json`。
+//!
+//! 设置了 `daily_reset_hour` 的变量在每天该时刻（本地时间）之后首次访问时恢复为 `reset_value`。
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Local};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde_json::Value;
+
+use super::types::{PersistentVariable, PersistentVariables};
+use super::utils::get_app_data_dir;
+
+/// 变量文件名（位于数据目录的 config 子目录）
+const VARIABLES_FILE_NAME: &str = "mxu-variables.json";
+
+/// 内存中的变量存储（首次访问时从文件加载，每次修改后写回）
+static STORE: Lazy<Mutex<PersistentVariables>> = Lazy::new(|| Mutex::new(load_from_disk()));
+
+fn variables_path() -> Result<PathBuf, String> {
+    Ok(get_app_data_dir()?.join("config").join(VARIABLES_FILE_NAME))
+}
+
+fn load_from_disk() -> PersistentVariables {
+    let Ok(path) = variables_path() else {
+        return PersistentVariables::default();
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!("Invalid variables file {:?}: {}, starting empty", path, e);
+            PersistentVariables::default()
+        }),
+        Err(_) => PersistentVariables::default(),
+    }
+}
+
+/// 写回文件（先写临时文件再重命名，避免写入中断导致文件损坏）
+fn save_to_disk(vars: &PersistentVariables) -> Result<(), String> {
+    let path = variables_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("无法创建目录 [{}]: {}", parent.display(), e))?;
+    }
+    let content = serde_json::to_string_pretty(vars).map_err(|e| e.to_string())?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, content)
+        .and_then(|_| std::fs::rename(&tmp, &path))
+        .map_err(|e| format!("保存变量失败 [{}]: {}", path.display(), e))
+}
+
+/// 按重置时间划分的周期：重置时刻之前仍属于前一天
+fn current_period(reset_hour: u8, now: DateTime<Local>) -> String {
+    (now - chrono::Duration::hours(i64::from(reset_hour)))
+        .date_naive()
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+/// 跨过重置时刻时恢复为 reset_value，返回是否发生了重置
+fn apply_daily_reset(var: &mut PersistentVariable, now: DateTime<Local>) -> bool {
+    let Some(hour) = var.daily_reset_hour else {
+        return false;
+    };
+    let period = current_period(hour, now);
+    if var.period.as_deref() == Some(period.as_str()) {
+        return false;
+    }
+    var.value = var.reset_value.clone();
+    var.period = Some(period);
+    true
+}
+
+fn scope_mut<'a>(
+    vars: &'a mut PersistentVariables,
+    instance_id: Option<&str>,
+) -> &'a mut BTreeMap<String, PersistentVariable> {
+    match instance_id {
+        Some(id) => vars.instances.entry(id.to_string()).or_default(),
+        None => &mut vars.global,
+    }
+}
+
+fn validate_reset_hour(hour: Option<u8>) -> Result<(), String> {
+    match hour {
+        Some(h) if h > 23 => Err(format!("daily_reset_hour must be 0-23, got {}", h)),
+        _ => Ok(()),
+    }
+}
+
+/// 读取变量值，不存在时返回 None
+pub fn get_value(instance_id: Option<&str>, name: &str) -> Result<Option<Value>, String> {
+    let mut store = STORE.lock().map_err(|e| e.to_string())?;
+    let Some(var) = scope_mut(&mut store, instance_id).get_mut(name) else {
+        return Ok(None);
+    };
+    let value = if apply_daily_reset(var, Local::now()) {
+        let value = var.value.clone();
+        save_to_disk(&store)?;
+        value
+    } else {
+        var.value.clone()
+    };
+    Ok(Some(value))
+}
+
+/// 修改变量（不存在时创建），返回修改后的值
+///
+/// `daily_reset_hour` / `reset_value` 为 Some 时同时更新变量的重置设置
+pub fn update_value(
+    instance_id: Option<&str>,
+    name: &str,
+    daily_reset_hour: Option<u8>,
+    reset_value: Option<Value>,
+    modify: impl FnOnce(&mut PersistentVariable) -> Result<(), String>,
+) -> Result<Value, String> {
+    validate_reset_hour(daily_reset_hour)?;
+    let now = Local::now();
+    let mut store = STORE.lock().map_err(|e| e.to_string())?;
+    let var = scope_mut(&mut store, instance_id)
+        .entry(name.to_string())
+        .or_insert_with(|| PersistentVariable {
+            value: Value::Null,
+            daily_reset_hour: None,
+            reset_value: Value::from(0),
+            period: None,
+            updated_at: None,
+        });
+
+    apply_daily_reset(var, now);
+    if daily_reset_hour.is_some() {
+        var.daily_reset_hour = daily_reset_hour;
+    }
+    if let Some(reset_value) = reset_value {
+        var.reset_value = reset_value;
+    }
+    modify(var)?;
+    var.period = var.daily_reset_hour.map(|h| current_period(h, now));
+    var.updated_at = Some(now.to_rfc3339());
+
+    let value = var.value.clone();
+    save_to_disk(&store)?;
+    Ok(value)
+}
+
+/// 数值相加，两者都是整数时保持整数；空值视为 0
+pub fn add_values(current: &Value, delta: &Value) -> Result<Value, String> {
+    let zero = Value::from(0);
+    let current = if current.is_null() { &zero } else { current };
+    if let (Some(a), Some(b)) = (current.as_i64(), delta.as_i64()) {
+        return a
+            .checked_add(b)
+            .map(Value::from)
+            .ok_or_else(|| "Integer overflow".to_string());
+    }
+    match (current.as_f64(), delta.as_f64()) {
+        (Some(a), Some(b)) => Ok(Value::from(a + b)),
+        _ => Err(format!("Cannot add {} to {}", delta, current)),
+    }
+}
+
+/// 将值转换为数字用于比较：空值视为 0，字符串尝试按数字解析
+fn numeric(value: &Value) -> Option<f64> {
+    match value {
+        Value::Null => Some(0.0),
+        Value::Number(n) => n.as_f64(),
+        Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// 比较变量值与期望值
+///
+/// `op` 支持 `==`、`!=`、`<`、`<=`、`>`、`>=`、`exists`、`not_exists`；
+/// 两边都能转换为数字时按数值比较，否则按字符串比较。不存在的变量视为空值（数值比较时为 0）
+pub fn compare_value(actual: Option<&Value>, op: &str, expected: &Value) -> Result<bool, String> {
+    use std::cmp::Ordering;
+
+    match op {
+        "exists" => return Ok(actual.is_some_and(|v| !v.is_null())),
+        "not_exists" => return Ok(actual.is_none_or(|v| v.is_null())),
+        _ => {}
+    }
+
+    let actual = actual.unwrap_or(&Value::Null);
+    let ordering = match (numeric(actual), numeric(expected)) {
+        (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => display(actual).cmp(&display(expected)),
+    };
+    match op {
+        "==" | "eq" => Ok(ordering == Ordering::Equal),
+        "!=" | "ne" => Ok(ordering != Ordering::Equal),
+        "<" | "lt" => Ok(ordering == Ordering::Less),
+        "<=" | "le" => Ok(ordering != Ordering::Greater),
+        ">" | "gt" => Ok(ordering == Ordering::Greater),
+        ">=" | "ge" => Ok(ordering != Ordering::Less),
+        other => Err(format!("Unknown compare operator '{}'", other)),
+    }
+}
+
+// ============================================================================
+// Tauri 命令
+// ============================================================================
+
+/// 获取所有持久化变量（已按每日重置规则刷新）
+#[tauri::command]
+pub fn get_persistent_variables() -> Result<PersistentVariables, String> {
+    let mut store = STORE.lock().map_err(|e| e.to_string())?;
+    let vars = &mut *store;
+    let now = Local::now();
+    let mut changed = false;
+    for var in vars
+        .global
+        .values_mut()
+        .chain(vars.instances.values_mut().flat_map(|v| v.values_mut()))
+    {
+        changed |= apply_daily_reset(var, now);
+    }
+    if changed {
+        save_to_disk(&store)?;
+    }
+    Ok(store.clone())
+}
+
+/// 设置持久化变量，`instance_id` 为空时设置全局变量
+#[tauri::command]
+pub fn set_persistent_variable(
+    instance_id: Option<String>,
+    name: String,
+    value: Value,
+    daily_reset_hour: Option<u8>,
+    reset_value: Option<Value>,
+) -> Result<(), String> {
+    info!(
+        "set_persistent_variable: instance={:?}, name={}, value={}",
+        instance_id, name, value
+    );
+    if name.trim().is_empty() {
+        return Err("变量名不能为空".to_string());
+    }
+    update_value(
+        instance_id.as_deref(),
+        &name,
+        daily_reset_hour,
+        reset_value,
+        |var| {
+            var.value = value;
+            Ok(())
+        },
+    )
+    .map(|_| ())
+}
+
+/// 删除持久化变量，变量不存在时返回 false
+#[tauri::command]
+pub fn delete_persistent_variable(
+    instance_id: Option<String>,
+    name: String,
+) -> Result<bool, String> {
+    info!(
+        "delete_persistent_variable: instance={:?}, name={}",
+        instance_id, name
+    );
+    let mut store = STORE.lock().map_err(|e| e.to_string())?;
+    let removed = match instance_id.as_deref() {
+        Some(id) => {
+            let removed = store
+                .instances
+                .get_mut(id)
+                .is_some_and(|vars| vars.remove(&name).is_some());
+            if store.instances.get(id).is_some_and(|vars| vars.is_empty()) {
+                store.instances.remove(id);
+            }
+            removed
+        }
+        None => store.global.remove(&name).is_some(),
+    };
+    if removed {
+        save_to_disk(&store)?;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn local(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2026, 6, day, hour, minute, 0)
+            .single()
+            .unwrap()
+    }
+
+    #[test]
+    fn add_values_keeps_integers() {
+        assert_eq!(add_values(&json!(3), &json!(2)), Ok(json!(5)));
+        assert_eq!(add_values(&json!(3), &json!(-5)), Ok(json!(-2)));
+        assert_eq!(add_values(&Value::Null, &json!(1)), Ok(json!(1)));
+    }
+
+    #[test]
+    fn add_values_mixes_floats() {
+        assert_eq!(add_values(&json!(1), &json!(0.5)), Ok(json!(1.5)));
+        assert_eq!(add_values(&json!(0.25), &json!(0.25)), Ok(json!(0.5)));
+    }
+
+    #[test]
+    fn add_values_rejects_non_numbers_and_overflow() {
+        assert_eq!(
+            add_values(&json!("abc"), &json!(1)),
+            Err("Cannot add 1 to \"abc\"".to_string())
+        );
+        assert!(add_values(&json!(1), &json!(true)).is_err());
+        assert_eq!(
+            add_values(&json!(i64::MAX), &json!(1)),
+            Err("Integer overflow".to_string())
+        );
+    }
+
+    #[test]
+    fn compare_value_numeric() {
+        let five = json!(5);
+        assert_eq!(compare_value(Some(&five), ">=", &json!(5)), Ok(true));
+        assert_eq!(compare_value(Some(&five), "gt", &json!(5)), Ok(false));
+        assert_eq!(compare_value(Some(&five), "<", &json!("10")), Ok(true));
+        assert_eq!(
+            compare_value(Some(&json!("5.0")), "==", &json!(5)),
+            Ok(true)
+        );
+        assert_eq!(compare_value(Some(&json!(true)), "eq", &json!(1)), Ok(true));
+        // 不存在的变量按 0 比较
+        assert_eq!(compare_value(None, "<", &json!(1)), Ok(true));
+        assert_eq!(compare_value(None, "==", &json!(0)), Ok(true));
+    }
+
+    #[test]
+    fn compare_value_falls_back_to_strings() {
+        let done = json!("done");
+        assert_eq!(compare_value(Some(&done), "==", &json!("done")), Ok(true));
+        assert_eq!(compare_value(Some(&done), "!=", &json!("todo")), Ok(true));
+        assert_eq!(compare_value(Some(&done), "<", &json!("todo")), Ok(true));
+        assert_eq!(compare_value(Some(&done), "ne", &json!(1)), Ok(true));
+    }
+
+    #[test]
+    fn compare_value_existence() {
+        assert_eq!(
+            compare_value(Some(&json!(0)), "exists", &Value::Null),
+            Ok(true)
+        );
+        assert_eq!(
+            compare_value(Some(&Value::Null), "exists", &Value::Null),
+            Ok(false)
+        );
+        assert_eq!(compare_value(None, "not_exists", &Value::Null), Ok(true));
+        assert_eq!(
+            compare_value(Some(&json!("")), "not_exists", &Value::Null),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn compare_value_unknown_operator() {
+        assert_eq!(
+            compare_value(Some(&json!(1)), "~=", &json!(1)),
+            Err("Unknown compare operator '~='".to_string())
+        );
+    }
+
+    #[test]
+    fn current_period_shifts_by_reset_hour() {
+        assert_eq!(current_period(0, local(18, 0, 0)), "2026-06-18");
+        assert_eq!(current_period(4, local(18, 3, 59)), "2026-06-17");
+        assert_eq!(current_period(4, local(18, 4, 0)), "2026-06-18");
+        assert_eq!(current_period(23, local(18, 22, 30)), "2026-06-17");
+        assert_eq!(current_period(23, local(18, 23, 30)), "2026-06-18");
+    }
+
+    #[test]
+    fn daily_reset_applies_once_per_period() {
+        let mut var = PersistentVariable {
+            value: json!(7),
+            daily_reset_hour: Some(4),
+            reset_value: json!(0),
+            period: Some("2026-06-17".to_string()),
+            updated_at: None,
+        };
+        // 仍在前一个周期内
+        assert!(!apply_daily_reset(&mut var, local(18, 3, 0)));
+        assert_eq!(var.value, json!(7));
+
+        assert!(apply_daily_reset(&mut var, local(18, 5, 0)));
+        assert_eq!(var.value, json!(0));
+        assert_eq!(var.period.as_deref(), Some("2026-06-18"));
+
+        var.value = json!(2);
+        assert!(!apply_daily_reset(&mut var, local(18, 23, 0)));
+        assert_eq!(var.value, json!(2));
+
+        var.daily_reset_hour = None;
+        assert!(!apply_daily_reset(&mut var, local(20, 5, 0)));
+    }
+}
@@ -0,0 +1,786 @@
+//! 模拟器控制
+//!
+//! 通过各模拟器自带的命令行工具启动 / 关闭指定实例，并等待 ADB 可用：
+//! - MuMu：`MuMuManager control -v <index> launch|shutdown`，`info -v <index>` 查询状态与 ADB 端口
+//! - 雷电：`ldconsole launch|quit|isrunning --index <index>`
+//! - BlueStacks：`HD-Player --instance <name>` 启动，结束对应 HD-Player 进程关闭，
+//!   ADB 端口读取 `bluestacks.conf`
+//! - 夜神：`NoxConsole launch|quit -index:<index>`，`list` 查询状态
+//!
+//! 命令行工具路径可显式指定（也便于替换为模拟输出的假程序），
+//! 未指定时在 Windows 默认安装目录和 PATH 中查找。
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+
+use crate::process_util::{
+    find_processes, read_to_end_in_background, terminate_processes, ProcessMatcher,
+};
+
+/// 命令行工具的执行超时
+const CLI_TIMEOUT: Duration = Duration::from_secs(30);
+/// ADB 就绪检测间隔
+const ADB_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// 关闭 BlueStacks 时的宽限期
+const BLUESTACKS_STOP_GRACE: Duration = Duration::from_secs(5);
+
+/// 支持的模拟器
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmulatorKind {
+    MuMu,
+    LdPlayer,
+    BlueStacks,
+    Nox,
+}
+
+impl EmulatorKind {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "mumu" | "mumu12" => Some(Self::MuMu),
+            "ldplayer" | "ld" | "leidian" => Some(Self::LdPlayer),
+            "bluestacks" | "bluestacks5" => Some(Self::BlueStacks),
+            "nox" => Some(Self::Nox),
+            _ => None,
+        }
+    }
+
+    /// 命令行工具的文件名
+    fn cli_name(&self) -> &'static str {
+        match self {
+            Self::MuMu => "MuMuManager",
+            Self::LdPlayer => "ldconsole",
+            Self::BlueStacks => "HD-Player",
+            Self::Nox => "NoxConsole",
+        }
+    }
+
+    /// 命令行工具的默认安装位置（Windows）
+    fn default_cli_paths(&self) -> Vec<PathBuf> {
+        let program_files = [
+            std::env::var("ProgramFiles").unwrap_or_else(|_| r"C:\Program Files".to_string()),
+            std::env::var("ProgramFiles(x86)")
+                .unwrap_or_else(|_| r"C:\Program Files (x86)".to_string()),
+        ];
+        let relative: &[&str] = match self {
+            Self::MuMu => &[
+                r"Netease\MuMuPlayer-12.0\shell\MuMuManager.exe",
+                r"Netease\MuMu Player 12\shell\MuMuManager.exe",
+                r"Netease\MuMuPlayer\nx_main\MuMuManager.exe",
+                r"Netease\MuMu\nx_main\MuMuManager.exe",
+            ],
+            Self::LdPlayer => &[],
+            Self::BlueStacks => &[r"BlueStacks_nxt\HD-Player.exe"],
+            Self::Nox => &[r"Nox\bin\NoxConsole.exe"],
+        };
+        let mut paths: Vec<PathBuf> = program_files
+            .iter()
+            .flat_map(|base| relative.iter().map(move |r| Path::new(base).join(r)))
+            .collect();
+        if *self == Self::LdPlayer {
+            for dir in [r"C:\LDPlayer\LDPlayer9", r"C:\LDPlayer\LDPlayer4.0"] {
+                paths.push(Path::new(dir).join("ldconsole.exe"));
+            }
+        }
+        paths
+    }
+
+    /// 模拟器自带的 adb（相对命令行工具所在目录）
+    fn bundled_adb(&self) -> &'static [&'static str] {
+        match self {
+            Self::MuMu => &["adb.exe", r"..\shell\adb.exe"],
+            Self::LdPlayer => &["adb.exe"],
+            Self::BlueStacks => &["HD-Adb.exe"],
+            Self::Nox => &["nox_adb.exe", "adb.exe"],
+        }
+    }
+}
+
+/// 要控制的模拟器实例
+#[derive(Debug, Clone)]
+pub struct EmulatorTarget {
+    pub kind: EmulatorKind,
+    /// 实例序号，0 为主实例
+    pub index: u32,
+    /// BlueStacks 实例名，缺省按序号推断（`Nougat64` / `Nougat64_<index>`）
+    pub instance_name: Option<String>,
+    /// 命令行工具路径
+    pub cli: PathBuf,
+    /// adb 路径，缺省使用模拟器自带的 adb，找不到时使用 PATH 中的 adb
+    pub adb: PathBuf,
+}
+
+/// 模拟器实例状态
+#[derive(Debug, Clone)]
+pub struct EmulatorState {
+    pub running: bool,
+    pub adb_address: String,
+}
+
+impl EmulatorTarget {
+    /// 解析命令行工具与 adb 路径
+    pub fn new(
+        kind: EmulatorKind,
+        index: u32,
+        instance_name: Option<String>,
+        cli_path: Option<&str>,
+        adb_path: Option<&str>,
+    ) -> Result<Self, String> {
+        let cli = match cli_path {
+            Some(path) => {
+                let path = PathBuf::from(path);
+                if !path.is_file() {
+                    return Err(format!("Emulator CLI not found: {}", path.display()));
+                }
+                path
+            }
+            None => find_cli(kind)?,
+        };
+        let adb = match adb_path {
+            Some(path) => PathBuf::from(path),
+            None => {
+                let cli_dir = cli.parent().unwrap_or(Path::new("."));
+                kind.bundled_adb()
+                    .iter()
+                    .map(|name| cli_dir.join(name))
+                    .find(|p| p.is_file())
+                    .unwrap_or_else(|| PathBuf::from("adb"))
+            }
+        };
+        Ok(Self {
+            kind,
+            index,
+            instance_name,
+            cli,
+            adb,
+        })
+    }
+
+    fn bluestacks_instance(&self) -> String {
+        match &self.instance_name {
+            Some(name) => name.clone(),
+            None if self.index == 0 => "Nougat64".to_string(),
+            None => format!("Nougat64_{}", self.index),
+        }
+    }
+
+    /// 执行命令行工具并返回 stdout
+    fn run_cli(&self, args: &[String]) -> Result<String, String> {
+        info!(
+            "[EMULATOR] {} {}",
+            self.cli.display(),
+            shell_words::join(args)
+        );
+        let output = run_with_timeout(&self.cli, args, CLI_TIMEOUT)?;
+        if !output.status.success() {
+            return Err(format!(
+                "{} exited with {}: {}",
+                self.kind.cli_name(),
+                output.status,
+                output.stderr.trim()
+            ));
+        }
+        Ok(output.stdout)
+    }
+
+    /// 启动实例（命令返回即视为已发出，不等待系统启动完成）
+    pub fn start(&self) -> Result<(), String> {
+        let index = self.index.to_string();
+        match self.kind {
+            EmulatorKind::MuMu => {
+                self.run_cli(&args(["control", "-v", &index, "launch"]))?;
+            }
+            EmulatorKind::LdPlayer => {
+                self.run_cli(&args(["launch", "--index", &index]))?;
+            }
+            EmulatorKind::Nox => {
+                self.run_cli(&args(["launch", &format!("-index:{}", index)]))?;
+            }
+            EmulatorKind::BlueStacks => {
+                // HD-Player 在模拟器运行期间不会退出，只启动不等待
+                let instance = self.bluestacks_instance();
+                info!("[EMULATOR] {} --instance {}", self.cli.display(), instance);
+                let mut cmd = Command::new(&self.cli);
+                cmd.args(["--instance", &instance])
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null());
+                if let Some(dir) = self.cli.parent() {
+                    cmd.current_dir(dir);
+                }
+                cmd.spawn()
+                    .map_err(|e| format!("Failed to start BlueStacks: {}", e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 关闭实例
+    pub fn stop(&self) -> Result<(), String> {
+        let index = self.index.to_string();
+        match self.kind {
+            EmulatorKind::MuMu => {
+                self.run_cli(&args(["control", "-v", &index, "shutdown"]))?;
+            }
+            EmulatorKind::LdPlayer => {
+                self.run_cli(&args(["quit", "--index", &index]))?;
+            }
+            EmulatorKind::Nox => {
+                self.run_cli(&args(["quit", &format!("-index:{}", index)]))?;
+            }
+            EmulatorKind::BlueStacks => {
+                let pids = self.bluestacks_pids();
+                if pids.is_empty() {
+                    info!("[EMULATOR] BlueStacks instance is not running");
+                    return Ok(());
+                }
+                let report = terminate_processes(&pids, Some(BLUESTACKS_STOP_GRACE));
+                if !report.failed.is_empty() {
+                    return Err(format!(
+                        "Failed to terminate BlueStacks process(es): {:?}",
+                        report.failed
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 当前实例的 HD-Player 进程
+    fn bluestacks_pids(&self) -> Vec<u32> {
+        let instance = self.bluestacks_instance();
+        let matcher = ProcessMatcher {
+            name: Some("HD-Player".to_string()),
+            ..Default::default()
+        };
+        find_processes(&matcher)
+            .into_iter()
+            .filter(|p| bluestacks_cmdline_has_instance(&p.cmdline, &instance))
+            .map(|p| p.pid)
+            .collect()
+    }
+
+    /// 查询实例是否运行及其 ADB 地址
+    pub fn state(&self) -> Result<EmulatorState, String> {
+        let index = self.index.to_string();
+        match self.kind {
+            EmulatorKind::MuMu => {
+                let output = self.run_cli(&args(["info", "-v", &index]))?;
+                let info: serde_json::Value = serde_json::from_str(output.trim())
+                    .map_err(|e| format!("Invalid MuMuManager output: {}", e))?;
+                let running = info
+                    .get("is_process_started")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                let host = info
+                    .get("adb_host_ip")
+                    .and_then(|v| v.as_str())
+                    .filter(|s| !s.is_empty())
+                    .unwrap_or("127.0.0.1");
+                let port = info
+                    .get("adb_port")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(16384 + 32 * u64::from(self.index));
+                Ok(EmulatorState {
+                    running,
+                    adb_address: format!("{}:{}", host, port),
+                })
+            }
+            EmulatorKind::LdPlayer => {
+                let output = self.run_cli(&args(["isrunning", "--index", &index]))?;
+                Ok(EmulatorState {
+                    running: output.trim().eq_ignore_ascii_case("running"),
+                    adb_address: format!("127.0.0.1:{}", 5555 + 2 * self.index),
+                })
+            }
+            EmulatorKind::Nox => {
+                // 每行：序号,名称,标题,顶层窗口句柄,绑定窗口句柄,进程 PID,...
+                let output = self.run_cli(&args(["list"]))?;
+                let running = output.lines().any(|line| {
+                    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+                    fields.first() == Some(&index.as_str())
+                        && fields
+                            .get(5)
+                            .and_then(|pid| pid.parse::<i64>().ok())
+                            .is_some_and(|pid| pid > 0)
+                });
+                let port = if self.index == 0 {
+                    62001
+                } else {
+                    62024 + self.index
+                };
+                Ok(EmulatorState {
+                    running,
+                    adb_address: format!("127.0.0.1:{}", port),
+                })
+            }
+            EmulatorKind::BlueStacks => {
+                let running = !self.bluestacks_pids().is_empty();
+                let port = bluestacks_adb_port(&self.bluestacks_instance())
+                    .unwrap_or(5555 + 10 * self.index);
+                Ok(EmulatorState {
+                    running,
+                    adb_address: format!("127.0.0.1:{}", port),
+                })
+            }
+        }
+    }
+
+    /// 等待 ADB 可连接且系统启动完成
+    ///
+    /// `sleep` 用于两次检测之间的等待，返回 false 表示已取消；
+    /// 每次 adb 调用与等待都不超过剩余时间，整体不会超出 `timeout`
+    pub fn wait_adb_ready(
+        &self,
+        timeout: Duration,
+        sleep: &dyn Fn(Duration) -> bool,
+    ) -> Result<String, String> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            // MuMu 等模拟器的端口在启动后才确定，每次重新查询
+            let address = self.state()?.adb_address;
+            if self.adb_boot_completed(&address, deadline) {
+                info!("[EMULATOR] ADB ready at {}", address);
+                return Ok(address);
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(format!(
+                    "ADB at {} not ready after {}s",
+                    address,
+                    timeout.as_secs()
+                ));
+            }
+            if !sleep(ADB_POLL_INTERVAL.min(remaining)) {
+                return Err("Wait for ADB cancelled".to_string());
+            }
+        }
+    }
+
+    /// 连接 ADB 并检查系统是否启动完成，每次调用的超时不超过 `deadline`
+    fn adb_boot_completed(&self, address: &str, deadline: Instant) -> bool {
+        let run_adb = |args: &[String]| {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err("Wait for ADB timed out".to_string());
+            }
+            run_with_timeout(&self.adb, args, remaining.min(CLI_TIMEOUT))
+        };
+        if let Err(e) = run_adb(&args(["connect", address])) {
+            warn!("[EMULATOR] adb connect {} failed: {}", address, e);
+            return false;
+        }
+        run_adb(&args([
+            "-s",
+            address,
+            "shell",
+            "getprop",
+            "sys.boot_completed",
+        ]))
+        .is_ok_and(|output| output.status.success() && output.stdout.trim() == "1")
+    }
+}
+
+/// 两个 ADB 地址是否指向同一设备（`localhost` 与 `127.0.0.1` 视为相同）
+pub fn same_adb_address(a: &str, b: &str) -> bool {
+    let normalize = |s: &str| s.trim().replacen("localhost:", "127.0.0.1:", 1);
+    normalize(a) == normalize(b)
+}
+
+fn args<const N: usize>(items: [&str; N]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+/// 在默认安装目录和 PATH 中查找命令行工具
+fn find_cli(kind: EmulatorKind) -> Result<PathBuf, String> {
+    if let Some(path) = kind.default_cli_paths().into_iter().find(|p| p.is_file()) {
+        return Ok(path);
+    }
+    let file_name = format!("{}{}", kind.cli_name(), std::env::consts::EXE_SUFFIX);
+    std::env::var_os("PATH")
+        .iter()
+        .flat_map(std::env::split_paths)
+        .map(|dir| dir.join(&file_name))
+        .find(|p| p.is_file())
+        .ok_or_else(|| format!("{} not found, please specify cli_path", kind.cli_name()))
+}
+
+/// 命令行中 `--instance` 后是否紧跟实例名（参数可能带引号，如 `"--instance" "Nougat64"`）
+fn bluestacks_cmdline_has_instance(cmdline: &str, instance: &str) -> bool {
+    let args: Vec<&str> = cmdline
+        .split_whitespace()
+        .map(|arg| arg.trim_matches('"'))
+        .collect();
+    args.iter()
+        .any(|arg| arg.strip_prefix("--instance=") == Some(instance))
+        || args
+            .windows(2)
+            .any(|w| w[0] == "--instance" && w[1] == instance)
+}
+
+/// 从 `bluestacks.conf` 读取实例的 ADB 端口
+fn bluestacks_adb_port(instance: &str) -> Option<u32> {
+    let program_data =
+        std::env::var("ProgramData").unwrap_or_else(|_| r"C:\ProgramData".to_string());
+    let conf = std::fs::read_to_string(
+        Path::new(&program_data)
+            .join("BlueStacks_nxt")
+            .join("bluestacks.conf"),
+    )
+    .ok()?;
+    let key = format!("bst.instance.{}.status.adb_port=", instance);
+    conf.lines()
+        .find_map(|line| line.trim().strip_prefix(&key))
+        .and_then(|value| value.trim().trim_matches('"').parse().ok())
+}
+
+struct CliOutput {
+    status: std::process::ExitStatus,
+    stdout: String,
+    stderr: String,
+}
+
+/// 执行命令并等待退出，超时后结束进程
+fn run_with_timeout(
+    program: &Path,
+    args: &[String],
+    timeout: Duration,
+) -> Result<CliOutput, String> {
+    let mut cmd = Command::new(program);
+    cmd.args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", program.display(), e))?;
+    let stdout = child.stdout.take().map(read_to_end_in_background);
+    let stderr = child.stderr.take().map(read_to_end_in_background);
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child
+            .try_wait()
+            .map_err(|e| format!("Failed to wait for {}: {}", program.display(), e))?
+        {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!(
+                "{} timed out after {}s",
+                program.display(),
+                timeout.as_secs()
+            ));
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    };
+
+    Ok(CliOutput {
+        status,
+        stdout: stdout.and_then(|h| h.join().ok()).unwrap_or_default(),
+        stderr: stderr.and_then(|h| h.join().ok()).unwrap_or_default(),
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// 存放假命令行工具的临时目录，drop 时删除
+    struct FakeTools {
+        dir: PathBuf,
+    }
+
+    impl FakeTools {
+        fn new() -> Self {
+            static NEXT: AtomicU32 = AtomicU32::new(0);
+            let dir = std::env::temp_dir().join(format!(
+                "mxu-emulator-test-{}-{}",
+                std::process::id(),
+                NEXT.fetch_add(1, Ordering::Relaxed)
+            ));
+            std::fs::create_dir_all(&dir).unwrap();
+            Self { dir }
+        }
+
+        /// 创建记录参数、输出 `stdout` 并以 `exit_code` 退出的假程序
+        fn tool(&self, name: &str, stdout: &str, exit_code: i32) -> String {
+            let path = self.dir.join(name);
+            std::fs::write(self.dir.join(format!("{}.out", name)), stdout).unwrap();
+            let script = format!(
+                "#!/bin/sh\nprintf '%s\\n' \"$*\" >> '{dir}/{name}.log'\ncat '{dir}/{name}.out'\n\
+                 [ {code} -ne 0 ] && echo 'fake failure' >&2\nexit {code}\n",
+                dir = self.dir.display(),
+                name = name,
+                code = exit_code
+            );
+            std::fs::write(&path, script).unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+            path.to_string_lossy().to_string()
+        }
+
+        /// 假程序收到的参数，每次调用一行
+        fn calls(&self, name: &str) -> Vec<String> {
+            std::fs::read_to_string(self.dir.join(format!("{}.log", name)))
+                .unwrap_or_default()
+                .lines()
+                .map(str::to_string)
+                .collect()
+        }
+
+        fn target(&self, kind: EmulatorKind, index: u32, stdout: &str) -> EmulatorTarget {
+            let cli = self.tool("cli", stdout, 0);
+            EmulatorTarget::new(kind, index, None, Some(&cli), Some("adb")).unwrap()
+        }
+    }
+
+    impl Drop for FakeTools {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    #[test]
+    fn parse_aliases() {
+        assert_eq!(EmulatorKind::parse(" MuMu12 "), Some(EmulatorKind::MuMu));
+        assert_eq!(EmulatorKind::parse("leidian"), Some(EmulatorKind::LdPlayer));
+        assert_eq!(
+            EmulatorKind::parse("BlueStacks5"),
+            Some(EmulatorKind::BlueStacks)
+        );
+        assert_eq!(EmulatorKind::parse("nox"), Some(EmulatorKind::Nox));
+        assert_eq!(EmulatorKind::parse("genymotion"), None);
+    }
+
+    #[test]
+    fn mumu_start_stop_args() {
+        let tools = FakeTools::new();
+        let target = tools.target(EmulatorKind::MuMu, 2, "");
+        target.start().unwrap();
+        target.stop().unwrap();
+        assert_eq!(
+            tools.calls("cli"),
+            ["control -v 2 launch", "control -v 2 shutdown"]
+        );
+    }
+
+    #[test]
+    fn mumu_state_parses_json() {
+        let tools = FakeTools::new();
+        let target = tools.target(
+            EmulatorKind::MuMu,
+            1,
+            r#"{"is_process_started":true,"adb_host_ip":"127.0.0.1","adb_port":16416}"#,
+        );
+        let state = target.state().unwrap();
+        assert!(state.running);
+        assert_eq!(state.adb_address, "127.0.0.1:16416");
+        assert_eq!(tools.calls("cli"), ["info -v 1"]);
+
+        // 未启动时没有端口信息，按序号推算
+        let target = tools.target(EmulatorKind::MuMu, 1, r#"{"is_process_started":false}"#);
+        let state = target.state().unwrap();
+        assert!(!state.running);
+        assert_eq!(state.adb_address, "127.0.0.1:16416");
+
+        let target = tools.target(EmulatorKind::MuMu, 1, "not json");
+        assert!(target
+            .state()
+            .unwrap_err()
+            .starts_with("Invalid MuMuManager output"));
+    }
+
+    #[test]
+    fn ldplayer_args_and_isrunning() {
+        let tools = FakeTools::new();
+        let target = tools.target(EmulatorKind::LdPlayer, 3, "running\r\n");
+        target.start().unwrap();
+        let state = target.state().unwrap();
+        target.stop().unwrap();
+        assert!(state.running);
+        assert_eq!(state.adb_address, "127.0.0.1:5561");
+        assert_eq!(
+            tools.calls("cli"),
+            ["launch --index 3", "isrunning --index 3", "quit --index 3"]
+        );
+
+        let target = tools.target(EmulatorKind::LdPlayer, 0, "stop\r\n");
+        let state = target.state().unwrap();
+        assert!(!state.running);
+        assert_eq!(state.adb_address, "127.0.0.1:5555");
+    }
+
+    #[test]
+    fn nox_args_and_list_csv() {
+        let tools = FakeTools::new();
+        let list = "0,Nox,NoxPlayer,00000000,00000000,-1,-1\r\n\
+                    1,Nox_1,NoxPlayer1,000A0B2C,000D0E1F,4321,5678\r\n";
+        let target = tools.target(EmulatorKind::Nox, 1, list);
+        target.start().unwrap();
+        let state = target.state().unwrap();
+        target.stop().unwrap();
+        assert!(state.running);
+        assert_eq!(state.adb_address, "127.0.0.1:62025");
+        assert_eq!(
+            tools.calls("cli"),
+            ["launch -index:1", "list", "quit -index:1"]
+        );
+
+        let target = tools.target(EmulatorKind::Nox, 0, list);
+        let state = target.state().unwrap();
+        assert!(!state.running);
+        assert_eq!(state.adb_address, "127.0.0.1:62001");
+    }
+
+    #[test]
+    fn cli_failure_reports_stderr() {
+        let tools = FakeTools::new();
+        let cli = tools.tool("ldconsole", "", 2);
+        let target =
+            EmulatorTarget::new(EmulatorKind::LdPlayer, 0, None, Some(&cli), None).unwrap();
+        let err = target.start().unwrap_err();
+        assert!(err.starts_with("ldconsole exited with"), "{}", err);
+        assert!(err.ends_with("fake failure"), "{}", err);
+    }
+
+    #[test]
+    fn resolves_bundled_adb_next_to_cli() {
+        let tools = FakeTools::new();
+        let cli = tools.tool("NoxConsole", "", 0);
+        let adb = tools.tool("nox_adb.exe", "", 0);
+        let target = EmulatorTarget::new(EmulatorKind::Nox, 0, None, Some(&cli), None).unwrap();
+        assert_eq!(target.adb, PathBuf::from(adb));
+
+        let target = EmulatorTarget::new(EmulatorKind::MuMu, 0, None, Some(&cli), None).unwrap();
+        assert_eq!(target.adb, PathBuf::from("adb"));
+
+        let missing = tools.dir.join("missing");
+        let err = EmulatorTarget::new(
+            EmulatorKind::MuMu,
+            0,
+            None,
+            Some(&missing.to_string_lossy()),
+            None,
+        )
+        .unwrap_err();
+        assert!(err.starts_with("Emulator CLI not found"));
+    }
+
+    #[test]
+    fn wait_adb_ready_checks_boot_completed() {
+        let tools = FakeTools::new();
+        let cli = tools.tool("cli", "running", 0);
+        let adb = tools.tool("adb", "1\n", 0);
+        let target =
+            EmulatorTarget::new(EmulatorKind::LdPlayer, 1, None, Some(&cli), Some(&adb)).unwrap();
+        let address = target
+            .wait_adb_ready(Duration::from_secs(5), &|_| panic!("should be ready"))
+            .unwrap();
+        assert_eq!(address, "127.0.0.1:5557");
+        assert_eq!(
+            tools.calls("adb"),
+            [
+                "connect 127.0.0.1:5557",
+                "-s 127.0.0.1:5557 shell getprop sys.boot_completed"
+            ]
+        );
+
+        // 尚未启动完成时等待，取消后返回错误
+        let adb = tools.tool("adb", "0\n", 0);
+        let target =
+            EmulatorTarget::new(EmulatorKind::LdPlayer, 1, None, Some(&cli), Some(&adb)).unwrap();
+        let err = target
+            .wait_adb_ready(Duration::from_secs(60), &|_| false)
+            .unwrap_err();
+        assert_eq!(err, "Wait for ADB cancelled");
+    }
+
+    #[test]
+    fn compares_adb_addresses() {
+        assert!(same_adb_address("127.0.0.1:16384", " 127.0.0.1:16384"));
+        assert!(same_adb_address("localhost:5555", "127.0.0.1:5555"));
+        assert!(!same_adb_address("127.0.0.1:5555", "127.0.0.1:5557"));
+    }
+
+    #[test]
+    fn wait_adb_ready_caps_attempts_at_timeout() {
+        let tools = FakeTools::new();
+        let cli = tools.tool("cli", "running", 0);
+        let adb = tools.dir.join("slow_adb");
+        std::fs::write(&adb, "#!/bin/sh\nexec sleep 10\n").unwrap();
+        std::fs::set_permissions(&adb, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let target = EmulatorTarget::new(
+            EmulatorKind::LdPlayer,
+            0,
+            None,
+            Some(&cli),
+            Some(&adb.to_string_lossy()),
+        )
+        .unwrap();
+
+        let started = Instant::now();
+        let err = target
+            .wait_adb_ready(Duration::from_secs(1), &|d| {
+                std::thread::sleep(d);
+                true
+            })
+            .unwrap_err();
+        assert_eq!(err, "ADB at 127.0.0.1:5555 not ready after 1s");
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn bluestacks_instance_names() {
+        let tools = FakeTools::new();
+        let cli = tools.tool("HD-Player", "", 0);
+        let target = |index, name: Option<&str>| {
+            EmulatorTarget::new(
+                EmulatorKind::BlueStacks,
+                index,
+                name.map(str::to_string),
+                Some(&cli),
+                None,
+            )
+            .unwrap()
+            .bluestacks_instance()
+        };
+        assert_eq!(target(0, None), "Nougat64");
+        assert_eq!(target(2, None), "Nougat64_2");
+        assert_eq!(target(2, Some("Pie64_1")), "Pie64_1");
+    }
+
+    #[test]
+    fn bluestacks_cmdline_matching() {
+        let exe = r#""C:\Program Files\BlueStacks_nxt\HD-Player.exe""#;
+        assert!(bluestacks_cmdline_has_instance(
+            &format!("{} --instance Nougat64", exe),
+            "Nougat64"
+        ));
+        assert!(bluestacks_cmdline_has_instance(
+            &format!(r#"{} "--instance" "Nougat64_1" "--cmd" "launchApp""#, exe),
+            "Nougat64_1"
+        ));
+        assert!(bluestacks_cmdline_has_instance(
+            &format!("{} --instance=Pie64", exe),
+            "Pie64"
+        ));
+        assert!(!bluestacks_cmdline_has_instance(
+            &format!(r#"{} "--instance" "Nougat64_1""#, exe),
+            "Nougat64"
+        ));
+        assert!(!bluestacks_cmdline_has_instance(exe, "Nougat64"));
+    }
+}
@@ -1,13 +1,19 @@
+mod agent_log;
 pub mod commands;
+mod emulator;
 mod maa_ffi;
 mod mxu_actions;
+mod power;
+mod process_util;
+mod run_context;
 mod tray;
+mod wait_target;
 
 use commands::MaaState;
 use maa_ffi::MaaLibraryError;
 use std::sync::Arc;
 use tauri::Manager;
-use tauri_plugin_log::{Target, TargetKind, TimezoneStrategy};
+use tauri_plugin_log::{RotationStrategy, Target, TargetKind, TimezoneStrategy};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -17,6 +23,12 @@ pub fn run() {
     // 确保日志目录存在
     let _ = std::fs::create_dir_all(&logs_dir);
 
+    // 单个日志文件大小上限（来自日志保留策略，0 表示不轮转）
+    let log_max_file_size = match commands::logs::current_settings().max_file_size_bytes() {
+        0 => u128::MAX,
+        size => size as u128,
+    };
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_fs::init())
@@ -40,6 +52,9 @@ pub fn run() {
                     }),
                 ])
                 .timezone_strategy(TimezoneStrategy::UseLocal)
+                // 超过大小上限后保留轮转文件，由日志保留策略统一清理
+                .max_file_size(log_max_file_size)
+                .rotation_strategy(RotationStrategy::KeepAll)
                 .level(log::LevelFilter::Debug)
                 .build(),
         )
@@ -81,6 +96,12 @@ pub fn run() {
                 }
             }
 
+            // 启动时及之后每小时按保留策略清理旧日志
+            std::thread::spawn(|| loop {
+                commands::logs::run_log_retention();
+                std::thread::sleep(commands::logs::LOG_RETENTION_INTERVAL);
+            });
+
             // 启动时自动加载 MaaFramework DLL
             if let Ok(maafw_dir) = commands::get_maafw_dir() {
                 if maafw_dir.exists() {
@@ -129,6 +150,8 @@ pub fn run() {
             commands::maa_core::maa_run_task,
             commands::maa_core::maa_get_task_status,
             commands::maa_core::maa_stop_task,
+            commands::maa_core::maa_pause_task,
+            commands::maa_core::maa_resume_task,
             commands::maa_core::maa_override_pipeline,
             commands::maa_core::maa_is_running,
             commands::maa_core::maa_post_screencap,
@@ -146,11 +169,30 @@ pub fn run() {
             commands::file_ops::check_exe_path,
             commands::file_ops::set_executable,
             commands::file_ops::export_logs,
+            commands::file_ops::set_file_action_roots,
+            commands::logs::get_log_retention_settings,
+            commands::logs::set_log_retention_settings,
+            commands::logs::apply_log_retention_now,
+            commands::run_history::get_run_history,
+            commands::notification::get_notification_channels,
+            commands::notification::save_notification_channels,
+            commands::notification::test_notification_channel,
+            commands::notification::set_notification_messages,
+            // 持久化变量命令
+            commands::variables::get_persistent_variables,
+            commands::variables::set_persistent_variable,
+            commands::variables::delete_persistent_variable,
+            // MXU 内置动作
+            commands::actions::get_mxu_action_schemas,
+            commands::actions::get_plugins,
+            commands::actions::set_allowed_plugins,
+            // 模拟器控制
             // 状态查询命令
             commands::state::maa_get_instance_state,
             commands::state::maa_get_all_states,
             commands::state::maa_get_cached_adb_devices,
             commands::state::maa_get_cached_win32_windows,
+            commands::state::maa_get_agent_output,
             // 更新安装命令
             commands::update::extract_zip,
             commands::update::check_changes_json,
@@ -173,6 +215,7 @@ pub fn run() {
             commands::system::retry_load_maa_library,
             commands::system::check_vcredist_missing,
             commands::system::get_arch,
+            commands::system::cancel_power_action,
             commands::system::get_system_info,
             // 托盘相关命令
             commands::tray::set_minimize_to_tray,
@@ -13,7 +13,7 @@
 use std::ffi::{CStr, CString};
 use std::os::raw::{c_char, c_void};
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::{Mutex, RwLock};
 
 use libloading::Library;
 use log::{debug, info, warn};
@@ -21,6 +21,7 @@ use once_cell::sync::Lazy;
 use serde::Serialize;
 use tauri::{AppHandle, Emitter};
 
+use crate::agent_log::AgentLogRecord;
 use crate::commands::utils::get_app_data_dir;
 
 // 类型定义 (对应 MaaDef.h)
@@ -160,6 +161,33 @@ pub type MaaCustomActionCallback = Option<
     ) -> MaaBool,
 >;
 
+// Custom Recognition 回调类型
+// MaaBool (*MaaCustomRecognitionCallback)(
+//     MaaContext* context,
+//     MaaTaskId task_id,
+//     const char* node_name,
+//     const char* custom_recognition_name,
+//     const char* custom_recognition_param,
+//     const MaaImageBuffer* image,
+//     const MaaRect* roi,
+//     void* trans_arg,
+//     /* out */ MaaRect* out_box,
+//     /* out */ MaaStringBuffer* out_detail);
+pub type MaaCustomRecognitionCallback = Option<
+    extern "C" fn(
+        context: *mut MaaContext,
+        task_id: MaaId,
+        node_name: *const c_char,
+        custom_recognition_name: *const c_char,
+        custom_recognition_param: *const c_char,
+        image: *const MaaImageBuffer,
+        roi: *const MaaRect,
+        trans_arg: *mut c_void,
+        out_box: *mut MaaRect,
+        out_detail: *mut MaaStringBuffer,
+    ) -> MaaBool,
+>;
+
 // 函数指针类型定义
 type FnMaaVersion = unsafe extern "C" fn() -> *const c_char;
 type FnMaaGlobalSetOption =
@@ -167,6 +195,7 @@ type FnMaaGlobalSetOption =
 type FnMaaStringBufferCreate = unsafe extern "C" fn() -> *mut MaaStringBuffer;
 type FnMaaStringBufferDestroy = unsafe extern "C" fn(*mut MaaStringBuffer);
 type FnMaaStringBufferGet = unsafe extern "C" fn(*const MaaStringBuffer) -> *const c_char;
+type FnMaaStringBufferSet = unsafe extern "C" fn(*mut MaaStringBuffer, *const c_char) -> MaaBool;
 
 type FnMaaResourceCreate = unsafe extern "C" fn() -> *mut MaaResource;
 type FnMaaResourceDestroy = unsafe extern "C" fn(*mut MaaResource);
@@ -179,6 +208,12 @@ type FnMaaResourceRegisterCustomAction = unsafe extern "C" fn(
     MaaCustomActionCallback,
     *mut c_void,
 ) -> MaaBool;
+type FnMaaResourceRegisterCustomRecognition = unsafe extern "C" fn(
+    *mut MaaResource,
+    *const c_char,
+    MaaCustomRecognitionCallback,
+    *mut c_void,
+) -> MaaBool;
 type FnMaaResourceLoaded = unsafe extern "C" fn(*mut MaaResource) -> MaaBool;
 type FnMaaResourceAddSink =
     unsafe extern "C" fn(*mut MaaResource, MaaEventCallback, *mut c_void) -> MaaId;
@@ -212,6 +247,7 @@ type FnMaaControllerConnected = unsafe extern "C" fn(*mut MaaController) -> MaaB
 type FnMaaControllerSetOption =
     unsafe extern "C" fn(*mut MaaController, MaaCtrlOption, *const c_void, MaaSize) -> MaaBool;
 type FnMaaControllerPostScreencap = unsafe extern "C" fn(*mut MaaController) -> MaaId;
+type FnMaaControllerPostClick = unsafe extern "C" fn(*mut MaaController, i32, i32) -> MaaId;
 type FnMaaControllerCachedImage =
     unsafe extern "C" fn(*mut MaaController, *mut MaaImageBuffer) -> MaaBool;
 type FnMaaControllerAddSink =
@@ -242,6 +278,14 @@ type FnMaaTaskerAddContextSink =
 type FnMaaTaskerOverridePipeline =
     unsafe extern "C" fn(*mut MaaTasker, MaaId, *const c_char) -> MaaBool;
 
+type FnMaaTaskerGetController = unsafe extern "C" fn(*mut MaaTasker) -> *mut MaaController;
+
+// Context
+type FnMaaContextGetTasker = unsafe extern "C" fn(*mut MaaContext) -> *mut MaaTasker;
+type FnMaaContextOverridePipeline = unsafe extern "C" fn(*mut MaaContext, *const c_char) -> MaaBool;
+type FnMaaContextGetNodeData =
+    unsafe extern "C" fn(*mut MaaContext, *const c_char, *mut MaaStringBuffer) -> MaaBool;
+
 type FnMaaToolkitAdbDeviceListCreate = unsafe extern "C" fn() -> *mut MaaToolkitAdbDeviceList;
 type FnMaaToolkitAdbDeviceListDestroy = unsafe extern "C" fn(*mut MaaToolkitAdbDeviceList);
 type FnMaaToolkitAdbDeviceFind = unsafe extern "C" fn(*mut MaaToolkitAdbDeviceList) -> MaaBool;
@@ -294,6 +338,7 @@ type FnMaaAgentClientBindResource =
 type FnMaaAgentClientConnect = unsafe extern "C" fn(*mut MaaAgentClient) -> MaaBool;
 type FnMaaAgentClientDisconnect = unsafe extern "C" fn(*mut MaaAgentClient) -> MaaBool;
 type FnMaaAgentClientSetTimeout = unsafe extern "C" fn(*mut MaaAgentClient, i64) -> MaaBool;
+type FnMaaAgentClientAlive = unsafe extern "C" fn(*mut MaaAgentClient) -> MaaBool;
 type FnMaaAgentClientRegisterResourceSink =
     unsafe extern "C" fn(*mut MaaAgentClient, *mut MaaResource) -> MaaBool;
 type FnMaaAgentClientRegisterControllerSink =
@@ -315,6 +360,7 @@ pub struct MaaLibrary {
     pub maa_string_buffer_create: FnMaaStringBufferCreate,
     pub maa_string_buffer_destroy: FnMaaStringBufferDestroy,
     pub maa_string_buffer_get: FnMaaStringBufferGet,
+    pub maa_string_buffer_set: FnMaaStringBufferSet,
 
     // Resource
     pub maa_resource_create: FnMaaResourceCreate,
@@ -325,6 +371,7 @@ pub struct MaaLibrary {
     pub maa_resource_loaded: FnMaaResourceLoaded,
     pub maa_resource_add_sink: FnMaaResourceAddSink,
     pub maa_resource_register_custom_action: FnMaaResourceRegisterCustomAction,
+    pub maa_resource_register_custom_recognition: FnMaaResourceRegisterCustomRecognition,
 
     // Controller
     pub maa_adb_controller_create: FnMaaAdbControllerCreate,
@@ -338,6 +385,7 @@ pub struct MaaLibrary {
     pub maa_controller_connected: FnMaaControllerConnected,
     pub maa_controller_set_option: FnMaaControllerSetOption,
     pub maa_controller_post_screencap: FnMaaControllerPostScreencap,
+    pub maa_controller_post_click: FnMaaControllerPostClick,
     pub maa_controller_cached_image: FnMaaControllerCachedImage,
     pub maa_controller_add_sink: FnMaaControllerAddSink,
 
@@ -362,6 +410,16 @@ pub struct MaaLibrary {
     pub maa_tasker_add_context_sink: FnMaaTaskerAddContextSink,
     /// 可选函数：旧版本 MaaFramework 可能不支持
     pub maa_tasker_override_pipeline: Option<FnMaaTaskerOverridePipeline>,
+    /// 可选函数：用于在 custom action 中获取控制器
+    pub maa_tasker_get_controller: Option<FnMaaTaskerGetController>,
+
+    // Context
+    /// 可选函数：用于在 custom action 中定位所属 tasker
+    pub maa_context_get_tasker: Option<FnMaaContextGetTasker>,
+    /// 可选函数：用于在 custom action 中修改当前任务的 pipeline（如 MXU_GATE 跳转）
+    pub maa_context_override_pipeline: Option<FnMaaContextOverridePipeline>,
+    /// 可选函数：用于在 custom action 中读取节点当前的 pipeline（如 MXU_GATE 恢复 next）
+    pub maa_context_get_node_data: Option<FnMaaContextGetNodeData>,
 
     // Toolkit - ADB Device
     pub maa_toolkit_adb_device_list_create: FnMaaToolkitAdbDeviceListCreate,
@@ -399,6 +457,8 @@ pub struct MaaLibrary {
     pub maa_agent_client_connect: FnMaaAgentClientConnect,
     pub maa_agent_client_disconnect: FnMaaAgentClientDisconnect,
     pub maa_agent_client_set_timeout: FnMaaAgentClientSetTimeout,
+    /// 旧版本 MaaFramework 可能不存在，附加模式的断线重连依赖此函数
+    pub maa_agent_client_alive: Option<FnMaaAgentClientAlive>,
     pub maa_agent_client_register_resource_sink: FnMaaAgentClientRegisterResourceSink,
     pub maa_agent_client_register_controller_sink: FnMaaAgentClientRegisterControllerSink,
     pub maa_agent_client_register_tasker_sink: FnMaaAgentClientRegisterTaskerSink,
@@ -513,6 +573,7 @@ impl MaaLibrary {
                 maa_string_buffer_create: load_fn!(framework_lib, "MaaStringBufferCreate"),
                 maa_string_buffer_destroy: load_fn!(framework_lib, "MaaStringBufferDestroy"),
                 maa_string_buffer_get: load_fn!(framework_lib, "MaaStringBufferGet"),
+                maa_string_buffer_set: load_fn!(framework_lib, "MaaStringBufferSet"),
 
                 // Resource
                 maa_resource_create: load_fn!(framework_lib, "MaaResourceCreate"),
@@ -526,6 +587,10 @@ impl MaaLibrary {
                     framework_lib,
                     "MaaResourceRegisterCustomAction"
                 ),
+                maa_resource_register_custom_recognition: load_fn!(
+                    framework_lib,
+                    "MaaResourceRegisterCustomRecognition"
+                ),
 
                 // Controller
                 maa_adb_controller_create: load_fn!(framework_lib, "MaaAdbControllerCreate"),
@@ -551,6 +616,7 @@ impl MaaLibrary {
                     framework_lib,
                     "MaaControllerPostScreencap"
                 ),
+                maa_controller_post_click: load_fn!(framework_lib, "MaaControllerPostClick"),
                 maa_controller_cached_image: load_fn!(framework_lib, "MaaControllerCachedImage"),
                 maa_controller_add_sink: load_fn!(framework_lib, "MaaControllerAddSink"),
 
@@ -580,6 +646,21 @@ impl MaaLibrary {
                     framework_lib,
                     "MaaTaskerOverridePipeline"
                 ),
+                maa_tasker_get_controller: load_fn_optional!(
+                    framework_lib,
+                    "MaaTaskerGetController"
+                ),
+
+                // Context
+                maa_context_get_tasker: load_fn_optional!(framework_lib, "MaaContextGetTasker"),
+                maa_context_override_pipeline: load_fn_optional!(
+                    framework_lib,
+                    "MaaContextOverridePipeline"
+                ),
+                maa_context_get_node_data: load_fn_optional!(
+                    framework_lib,
+                    "MaaContextGetNodeData"
+                ),
 
                 // Toolkit - ADB Device
                 maa_toolkit_adb_device_list_create: load_fn!(
@@ -676,6 +757,7 @@ impl MaaLibrary {
                     agent_client_lib,
                     "MaaAgentClientSetTimeout"
                 ),
+                maa_agent_client_alive: load_fn_optional!(agent_client_lib, "MaaAgentClientAlive"),
                 maa_agent_client_register_resource_sink: load_fn!(
                     agent_client_lib,
                     "MaaAgentClientRegisterResourceSink"
@@ -710,6 +792,77 @@ impl MaaLibrary {
 /// 全局 MaaLibrary 实例
 pub static MAA_LIBRARY: Lazy<Mutex<Option<MaaLibrary>>> = Lazy::new(|| Mutex::new(None));
 
+/// 工作线程回调（custom action / recognition、context sink）中用到的函数指针
+#[derive(Clone, Copy)]
+pub struct CallbackFns {
+    pub string_buffer_create: FnMaaStringBufferCreate,
+    pub string_buffer_destroy: FnMaaStringBufferDestroy,
+    pub string_buffer_get: FnMaaStringBufferGet,
+    pub string_buffer_set: FnMaaStringBufferSet,
+    pub tasker_running: FnMaaTaskerRunning,
+    pub tasker_get_controller: Option<FnMaaTaskerGetController>,
+    pub controller_wait: FnMaaControllerWait,
+    pub controller_post_connection: FnMaaControllerPostConnection,
+    pub controller_post_screencap: FnMaaControllerPostScreencap,
+    pub controller_post_click: FnMaaControllerPostClick,
+    pub controller_cached_image: FnMaaControllerCachedImage,
+    pub image_buffer_create: FnMaaImageBufferCreate,
+    pub image_buffer_destroy: FnMaaImageBufferDestroy,
+    pub image_buffer_get_encoded: FnMaaImageBufferGetEncoded,
+    pub image_buffer_get_encoded_size: FnMaaImageBufferGetEncodedSize,
+    pub context_get_tasker: Option<FnMaaContextGetTasker>,
+    pub context_override_pipeline: Option<FnMaaContextOverridePipeline>,
+    pub context_get_node_data: Option<FnMaaContextGetNodeData>,
+}
+
+impl CallbackFns {
+    fn from_library(lib: &MaaLibrary) -> Self {
+        Self {
+            string_buffer_create: lib.maa_string_buffer_create,
+            string_buffer_destroy: lib.maa_string_buffer_destroy,
+            string_buffer_get: lib.maa_string_buffer_get,
+            string_buffer_set: lib.maa_string_buffer_set,
+            tasker_running: lib.maa_tasker_running,
+            tasker_get_controller: lib.maa_tasker_get_controller,
+            controller_wait: lib.maa_controller_wait,
+            controller_post_connection: lib.maa_controller_post_connection,
+            controller_post_screencap: lib.maa_controller_post_screencap,
+            controller_post_click: lib.maa_controller_post_click,
+            controller_cached_image: lib.maa_controller_cached_image,
+            image_buffer_create: lib.maa_image_buffer_create,
+            image_buffer_destroy: lib.maa_image_buffer_destroy,
+            image_buffer_get_encoded: lib.maa_image_buffer_get_encoded,
+            image_buffer_get_encoded_size: lib.maa_image_buffer_get_encoded_size,
+            context_get_tasker: lib.maa_context_get_tasker,
+            context_override_pipeline: lib.maa_context_override_pipeline,
+            context_get_node_data: lib.maa_context_get_node_data,
+        }
+    }
+}
+
+/// 加载时缓存的回调函数指针，供工作线程回调使用而无需锁定 MAA_LIBRARY
+///
+/// 销毁实例时会持有 MAA_LIBRARY 等待工作线程退出，回调中再锁定它会死锁
+static CALLBACK_FNS: RwLock<Option<CallbackFns>> = RwLock::new(None);
+
+/// 获取缓存的回调函数指针，不锁定 MAA_LIBRARY
+pub fn callback_fns() -> Result<CallbackFns, String> {
+    CALLBACK_FNS
+        .read()
+        .ok()
+        .and_then(|fns| *fns)
+        .ok_or_else(|| "MaaFramework not initialized".to_string())
+}
+
+/// 通过 context 获取所属 tasker，不锁定 MAA_LIBRARY
+pub fn context_get_tasker(context: *mut MaaContext) -> *mut MaaTasker {
+    let get_tasker = callback_fns().ok().and_then(|fns| fns.context_get_tasker);
+    match get_tasker {
+        Some(get_tasker) if !context.is_null() => unsafe { get_tasker(context) },
+        _ => std::ptr::null_mut(),
+    }
+}
+
 /// 标记是否检测到可能缺少 VC++ 运行库（DLL 存在但加载失败）
 static VCREDIST_MISSING_DETECTED: Lazy<Mutex<bool>> = Lazy::new(|| Mutex::new(false));
 
@@ -808,6 +961,9 @@ pub fn init_maa_library(lib_dir: &Path) -> Result<(), MaaLibraryError> {
     let mut guard = MAA_LIBRARY
         .lock()
         .map_err(|e| MaaLibraryError::Other(e.to_string()))?;
+    if let Ok(mut fns) = CALLBACK_FNS.write() {
+        *fns = Some(CallbackFns::from_library(&lib));
+    }
     *guard = Some(lib);
     Ok(())
 }
@@ -899,6 +1055,11 @@ pub fn set_app_handle(handle: AppHandle) {
     }
 }
 
+/// 获取全局 AppHandle（用于在 MXU 动作中访问应用状态）
+pub fn get_app_handle() -> Option<AppHandle> {
+    APP_HANDLE.lock().ok().and_then(|guard| guard.clone())
+}
+
 /// MaaFramework 回调事件载荷
 #[derive(Clone, Serialize)]
 pub struct MaaCallbackEvent {
@@ -919,6 +1080,75 @@ pub struct AgentOutputEvent {
     pub line: String,
 }
 
+/// Agent 结构化日志事件载荷（见 `agent_log` 模块的协议说明）
+#[derive(Clone, Serialize)]
+pub struct AgentLogEvent {
+    /// 实例 ID
+    pub instance_id: String,
+    /// 解析后的日志记录
+    #[serde(flatten)]
+    pub record: AgentLogRecord,
+}
+
+/// MXU_LOG 日志事件载荷，日志字段与 [`AgentLogEvent`] 相同
+#[derive(Clone, Serialize)]
+pub struct ActionLogEvent {
+    /// 实例 ID
+    pub instance_id: String,
+    /// 所属任务 ID
+    pub task_id: MaaId,
+    /// 日志记录
+    #[serde(flatten)]
+    pub record: AgentLogRecord,
+}
+
+/// MXU_KILLPROC 的结果事件载荷
+#[derive(Clone, Serialize)]
+pub struct KillProcResultEvent {
+    /// 所属任务 ID
+    pub task_id: MaaId,
+    /// 当前 pipeline 节点名
+    pub node: String,
+    /// 是否仅报告匹配结果
+    pub dry_run: bool,
+    /// 匹配到的 PID
+    pub matched: Vec<u32>,
+    /// 已退出或被结束的 PID
+    pub affected: Vec<u32>,
+    /// 无法结束的 PID
+    pub failed: Vec<u32>,
+}
+
+/// MXU 等待类动作（MXU_SLEEP / MXU_WAITUNTIL / MXU_POWER 等）的倒计时事件载荷
+#[derive(Clone, Serialize)]
+pub struct ActionCountdownEvent {
+    /// 所属任务 ID
+    pub task_id: MaaId,
+    /// 动作名称，如 "MXU_SLEEP"
+    pub action: String,
+    /// 当前 pipeline 节点名
+    pub node: String,
+    /// 剩余秒数，0 表示等待结束
+    pub remaining_secs: u64,
+    /// 总等待秒数
+    pub total_secs: u64,
+    /// 可传给 `cancel_power_action` 的待执行操作 ID，不可取消时为 None
+    pub cancel_id: Option<u64>,
+}
+
+/// Agent 启动进度事件载荷
+#[derive(Clone, Serialize)]
+pub struct AgentProgressEvent {
+    /// 实例 ID
+    pub instance_id: String,
+    /// Agent 序号（对应 agent_configs 中的下标）
+    pub agent_index: usize,
+    /// 阶段: "starting" | "waiting" | "connecting" | "connected" | "disconnected" | "failed"
+    pub stage: String,
+    /// 附加信息（如失败原因）
+    pub message: Option<String>,
+}
+
 /// 用于过滤 ANSI 转义序列的正则表达式
 static ANSI_ESCAPE_RE: Lazy<regex::Regex> = Lazy::new(|| {
     // 匹配 ANSI CSI 序列（颜色、光标控制等）和 OSC 序列（终端标题等）
@@ -960,11 +1190,170 @@ pub fn emit_agent_output(instance_id: &str, stream: &str, line: &str) {
     }
 }
 
+/// 发送 Agent 结构化日志事件到前端
+pub fn emit_agent_log(instance_id: &str, record: &AgentLogRecord) {
+    let handle = match APP_HANDLE.lock() {
+        Ok(guard) => guard.clone(),
+        Err(e) => {
+            log::error!("[agent_log] Failed to lock APP_HANDLE: {}", e);
+            return;
+        }
+    };
+
+    if let Some(handle) = handle {
+        let mut record = record.clone();
+        record.message = strip_ansi_escapes(&record.message);
+        let event = AgentLogEvent {
+            instance_id: instance_id.to_string(),
+            record,
+        };
+        if let Err(e) = handle.emit("maa-agent-log", event) {
+            log::error!("[agent_log] Failed to emit event: {}", e);
+        }
+    }
+}
+
+/// 发送 MXU_LOG 日志事件到前端任务日志
+pub fn emit_action_log(instance_id: &str, task_id: MaaId, record: &AgentLogRecord) {
+    let handle = match APP_HANDLE.lock() {
+        Ok(guard) => guard.clone(),
+        Err(e) => {
+            log::error!("[action_log] Failed to lock APP_HANDLE: {}", e);
+            return;
+        }
+    };
+
+    if let Some(handle) = handle {
+        let event = ActionLogEvent {
+            instance_id: instance_id.to_string(),
+            task_id,
+            record: record.clone(),
+        };
+        if let Err(e) = handle.emit("maa-action-log", event) {
+            log::error!("[action_log] Failed to emit event: {}", e);
+        }
+    }
+}
+
+/// 发送 MXU 等待类动作的倒计时事件到前端
+pub fn emit_action_countdown(
+    task_id: MaaId,
+    action: &str,
+    node: &str,
+    remaining_secs: u64,
+    total_secs: u64,
+) {
+    emit_countdown_event(ActionCountdownEvent {
+        task_id,
+        action: action.to_string(),
+        node: node.to_string(),
+        remaining_secs,
+        total_secs,
+        cancel_id: None,
+    });
+}
+
+/// 发送可取消的倒计时事件（MXU_POWER），`cancel_id` 为待执行操作 ID
+pub fn emit_cancellable_countdown(
+    task_id: MaaId,
+    action: &str,
+    node: &str,
+    remaining_secs: u64,
+    total_secs: u64,
+    cancel_id: u64,
+) {
+    emit_countdown_event(ActionCountdownEvent {
+        task_id,
+        action: action.to_string(),
+        node: node.to_string(),
+        remaining_secs,
+        total_secs,
+        cancel_id: Some(cancel_id),
+    });
+}
+
+fn emit_countdown_event(event: ActionCountdownEvent) {
+    let handle = match APP_HANDLE.lock() {
+        Ok(guard) => guard.clone(),
+        Err(e) => {
+            log::error!("[action_countdown] Failed to lock APP_HANDLE: {}", e);
+            return;
+        }
+    };
+
+    if let Some(handle) = handle {
+        if let Err(e) = handle.emit("maa-action-countdown", event) {
+            log::error!("[action_countdown] Failed to emit event: {}", e);
+        }
+    }
+}
+
+/// 发送 MXU_KILLPROC 结果事件到前端
+pub fn emit_killproc_result(
+    task_id: MaaId,
+    node: &str,
+    dry_run: bool,
+    matched: &[u32],
+    affected: &[u32],
+    failed: &[u32],
+) {
+    let handle = match APP_HANDLE.lock() {
+        Ok(guard) => guard.clone(),
+        Err(e) => {
+            log::error!("[killproc_result] Failed to lock APP_HANDLE: {}", e);
+            return;
+        }
+    };
+
+    if let Some(handle) = handle {
+        let event = KillProcResultEvent {
+            task_id,
+            node: node.to_string(),
+            dry_run,
+            matched: matched.to_vec(),
+            affected: affected.to_vec(),
+            failed: failed.to_vec(),
+        };
+        if let Err(e) = handle.emit("maa-killproc-result", event) {
+            log::error!("[killproc_result] Failed to emit event: {}", e);
+        }
+    }
+}
+
+/// 发送 Agent 启动 / 连接状态事件到前端
+pub fn emit_agent_progress(
+    instance_id: &str,
+    agent_index: usize,
+    stage: &str,
+    message: Option<&str>,
+) {
+    let handle = match APP_HANDLE.lock() {
+        Ok(guard) => guard.clone(),
+        Err(e) => {
+            log::error!("[agent_progress] Failed to lock APP_HANDLE: {}", e);
+            return;
+        }
+    };
+
+    if let Some(handle) = handle {
+        let event = AgentProgressEvent {
+            instance_id: instance_id.to_string(),
+            agent_index,
+            stage: stage.to_string(),
+            message: message.map(|m| m.to_string()),
+        };
+        if let Err(e) = handle.emit("maa-agent-progress", event) {
+            log::error!("[agent_progress] Failed to emit event: {}", e);
+        }
+    }
+}
+
 /// MaaFramework 回调处理函数
 /// 由 MaaFramework 在工作线程中调用，将消息转发到前端
-/// 注意：此函数必须尽快返回，避免阻塞 MaaFramework 的工作线程
+/// 注意：此函数必须尽快返回，避免阻塞 MaaFramework 的工作线程；
+/// 唯一的例外是实例暂停时，在 `Node.Action.Starting` 处阻塞直到恢复或停止
 extern "C" fn maa_event_callback(
-    _handle: *mut c_void,
+    source: *mut c_void,
     message: *const c_char,
     details_json: *const c_char,
     _trans_arg: *mut c_void,
@@ -992,6 +1381,10 @@ extern "C" fn maa_event_callback(
             details_str
         );
 
+        // 记录失败信息，供 MXU 动作引用
+        crate::run_context::record_callback(&message_str, &details_str);
+        let action_starting = message_str == "Node.Action.Starting";
+
         // 快速克隆 AppHandle 后立即释放锁，避免阻塞 MaaFramework 工作线程
         let handle = match APP_HANDLE.lock() {
             Ok(guard) => guard.clone(),
@@ -1013,6 +1406,11 @@ extern "C" fn maa_event_callback(
         } else {
             log::warn!("[callback] APP_HANDLE is None, cannot emit event");
         }
+
+        // context sink 的 source 为 MaaContext，在动作执行前响应暂停
+        if action_starting && !source.is_null() {
+            crate::mxu_actions::hold_if_paused(source as *mut MaaContext);
+        }
     });
 
     if let Err(e) = result {
@@ -0,0 +1,139 @@
+//! MXU_ADB_SHELL：通过当前实例的 ADB 控制器执行 shell 命令
+
+use std::time::{Duration, Instant};
+
+use log::info;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::launch::LAUNCH_POLL_INTERVAL;
+use super::template::{Escape, Template};
+use super::{non_empty, sleep_unless_cancelled, with_tasker_instance, ActionContext, MxuAction};
+use crate::commands::types::ControllerConfig;
+use crate::process_util::read_to_end_in_background;
+
+/// adb shell 的默认超时（秒）
+const ADB_SHELL_DEFAULT_TIMEOUT: u64 = 20;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct AdbShellParam {
+    /// shell 命令，如 `am force-stop com.example` / `input keyevent 3`，
+    /// 可使用占位符（见 [`super::template`]），替换值会加引号
+    command: String,
+    /// 超时（秒），默认 20，超时后结束 adb 进程并返回失败
+    #[serde(default)]
+    timeout: Option<u64>,
+    /// stdout 需匹配的正则，缺省时只检查退出码
+    #[serde(default)]
+    success_regex: Option<String>,
+    /// 忽略 adb 的退出码
+    #[serde(default)]
+    ignore_exit_code: bool,
+}
+
+/// 查找 tasker 所属实例当前连接使用的控制器配置
+fn instance_controller_config(tasker: usize) -> Result<ControllerConfig, String> {
+    with_tasker_instance(tasker, |instance| instance.controller_config.clone())?
+        .ok_or_else(|| "Instance has no connected controller".to_string())
+}
+
+/// 使用当前实例 ADB 控制器的 `adb_path` / `address` 执行 `adb -s <address> shell <command>`，
+/// 当前实例不是 ADB 控制器时返回失败
+pub struct AdbShellAction;
+
+impl MxuAction for AdbShellAction {
+    const NAME: &'static str = "MXU_ADB_SHELL_ACTION";
+    const TAG: &'static str = "MXU_ADB_SHELL";
+    type Param = AdbShellParam;
+
+    fn run(ctx: &ActionContext, param: AdbShellParam) -> Result<(), String> {
+        let command = non_empty(Some(param.command)).ok_or("Empty 'command' parameter")?;
+        let command = Template::new(ctx).render(&command, Escape::Shell);
+        let timeout = Duration::from_secs(
+            param
+                .timeout
+                .filter(|t| *t > 0)
+                .unwrap_or(ADB_SHELL_DEFAULT_TIMEOUT),
+        );
+        let success_regex = param
+            .success_regex
+            .filter(|s| !s.is_empty())
+            .map(|s| regex::Regex::new(&s))
+            .transpose()
+            .map_err(|e| format!("Invalid success_regex: {}", e))?;
+
+        let tasker = ctx.tasker().ok_or("Cannot resolve tasker from context")?;
+        let (adb_path, address) = match instance_controller_config(tasker)? {
+            ControllerConfig::Adb {
+                adb_path, address, ..
+            } => (adb_path, address),
+            _ => return Err("Current controller is not an ADB controller".to_string()),
+        };
+
+        info!(
+            "[MXU_ADB_SHELL] {} -s {} shell {}",
+            adb_path, address, command
+        );
+
+        let mut cmd = std::process::Command::new(&adb_path);
+        cmd.args(["-s", &address, "shell", &command])
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped());
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            cmd.creation_flags(CREATE_NO_WINDOW);
+        }
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| format!("Failed to run adb: {}", e))?;
+        let stdout = child.stdout.take().map(read_to_end_in_background);
+        let stderr = child.stderr.take().map(read_to_end_in_background);
+
+        let deadline = Instant::now() + timeout;
+        let status = loop {
+            if let Some(status) = child
+                .try_wait()
+                .map_err(|e| format!("Failed to wait for adb: {}", e))?
+            {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("Command timed out after {}s", timeout.as_secs()));
+            }
+            if !sleep_unless_cancelled(Some(tasker), LAUNCH_POLL_INTERVAL) {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err("Stop requested, killed adb".to_string());
+            }
+        };
+
+        let stdout = stdout.and_then(|h| h.join().ok()).unwrap_or_default();
+        let stderr = stderr.and_then(|h| h.join().ok()).unwrap_or_default();
+        info!(
+            "[MXU_ADB_SHELL] Exit code: {:?}, stdout: {}, stderr: {}",
+            status.code(),
+            stdout.trim(),
+            stderr.trim()
+        );
+
+        if !param.ignore_exit_code && !status.success() {
+            return Err(format!("Command failed with {}", status));
+        }
+        if let Some(re) = success_regex {
+            if !re.is_match(&stdout) {
+                return Err(format!(
+                    "Output does not match success_regex '{}'",
+                    re.as_str()
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+//! MXU_EMULATOR：启动 / 关闭 / 重启模拟器实例，等待 ADB 可用后重新连接当前控制器
+
+use std::time::{Duration, Instant};
+
+use log::info;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::template::{Escape, Template};
+use super::{non_empty, sleep_unless_cancelled, with_tasker_instance, ActionContext, MxuAction};
+use crate::commands::types::ControllerConfig;
+use crate::emulator::{same_adb_address, EmulatorKind, EmulatorTarget};
+use crate::maa_ffi::{callback_fns, MaaTasker, MAA_STATUS_SUCCEEDED};
+
+/// 等待 ADB 就绪的默认超时（秒）
+const EMULATOR_DEFAULT_TIMEOUT: u64 = 180;
+/// 重启时等待实例关闭的超时
+const EMULATOR_STOP_TIMEOUT: Duration = Duration::from_secs(60);
+/// 等待实例关闭时的轮询间隔
+const EMULATOR_STOP_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 模拟器操作
+#[derive(Deserialize, JsonSchema, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum EmulatorOp {
+    /// 启动，已在运行时只等待 ADB 就绪
+    #[default]
+    Start,
+    /// 关闭
+    Stop,
+    /// 关闭后重新启动
+    Restart,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct EmulatorParam {
+    /// `mumu` / `ldplayer` / `bluestacks` / `nox`
+    emulator: String,
+    /// 操作，默认 `start`
+    #[serde(default)]
+    op: EmulatorOp,
+    /// 实例序号，默认 0（主实例）
+    #[serde(default)]
+    index: u32,
+    /// BlueStacks 实例名，缺省按序号推断（`Nougat64` / `Nougat64_<index>`）
+    #[serde(default)]
+    instance_name: Option<String>,
+    /// 模拟器命令行工具路径（MuMuManager / ldconsole / HD-Player / NoxConsole），缺省自动查找
+    #[serde(default)]
+    cli_path: Option<String>,
+    /// adb 路径，缺省使用模拟器自带的 adb
+    #[serde(default)]
+    adb_path: Option<String>,
+    /// 启动后是否等待 ADB 就绪，默认 true
+    #[serde(default = "default_true")]
+    wait_ready: bool,
+    /// 等待 ADB 就绪的超时（秒），默认 180
+    #[serde(default)]
+    timeout: Option<u64>,
+    /// ADB 就绪后是否重新连接当前任务的控制器，默认 true
+    #[serde(default = "default_true")]
+    reconnect: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// 模拟器就绪后重新连接当前 tasker 的 ADB 控制器
+///
+/// 运行中的任务不能更换控制器，因此要求控制器配置的地址与模拟器的 ADB 地址一致
+fn reconnect_controller(tasker: usize, address: &str) -> Result<(), String> {
+    let configured = with_tasker_instance(tasker, |instance| match &instance.controller_config {
+        Some(ControllerConfig::Adb { address, .. }) => Some(address.clone()),
+        _ => None,
+    })?;
+    let Some(configured) = configured else {
+        info!("[MXU_EMULATOR] Controller is not an ADB controller, skip reconnecting");
+        return Ok(());
+    };
+    if !same_adb_address(&configured, address) {
+        return Err(format!(
+            "Controller is configured for {} but the emulator ADB address is {}",
+            configured, address
+        ));
+    }
+
+    // 在工作线程上运行，使用缓存的函数指针而不锁定 MAA_LIBRARY
+    let fns = callback_fns()?;
+    let get_controller = fns
+        .tasker_get_controller
+        .ok_or("MaaTaskerGetController not available, please update MaaFramework")?;
+    unsafe {
+        let controller = get_controller(tasker as *mut MaaTasker);
+        if controller.is_null() {
+            return Err("Tasker has no controller".to_string());
+        }
+        let conn_id = (fns.controller_post_connection)(controller);
+        if (fns.controller_wait)(controller, conn_id) != MAA_STATUS_SUCCEEDED {
+            return Err(format!("Failed to reconnect controller to {}", address));
+        }
+    }
+    info!("[MXU_EMULATOR] Controller reconnected to {}", address);
+    Ok(())
+}
+
+/// 启动 / 关闭模拟器实例；启动并就绪后重新连接当前任务的控制器
+pub struct EmulatorAction;
+
+impl MxuAction for EmulatorAction {
+    const NAME: &'static str = "MXU_EMULATOR_ACTION";
+    const TAG: &'static str = "MXU_EMULATOR";
+    type Param = EmulatorParam;
+
+    fn run(ctx: &ActionContext, param: EmulatorParam) -> Result<(), String> {
+        let kind = EmulatorKind::parse(&param.emulator)
+            .ok_or_else(|| format!("Unknown emulator: {}", param.emulator))?;
+        let template = Template::new(ctx);
+        let cli_path = non_empty(param.cli_path).map(|p| template.render(&p, Escape::Raw));
+        let adb_path = non_empty(param.adb_path).map(|p| template.render(&p, Escape::Raw));
+        let target = EmulatorTarget::new(
+            kind,
+            param.index,
+            non_empty(param.instance_name),
+            cli_path.as_deref(),
+            adb_path.as_deref(),
+        )?;
+        let tasker = ctx.tasker();
+
+        info!(
+            "[MXU_EMULATOR] {:?} {:?} #{} via {}",
+            param.op,
+            kind,
+            param.index,
+            target.cli.display()
+        );
+
+        if matches!(param.op, EmulatorOp::Stop | EmulatorOp::Restart) {
+            target.stop()?;
+            if param.op == EmulatorOp::Stop {
+                return Ok(());
+            }
+            // 等待实例完全关闭后再启动
+            let deadline = Instant::now() + EMULATOR_STOP_TIMEOUT;
+            while target.state()?.running {
+                if Instant::now() >= deadline {
+                    return Err(format!(
+                        "Emulator still running after {}s",
+                        EMULATOR_STOP_TIMEOUT.as_secs()
+                    ));
+                }
+                if !sleep_unless_cancelled(tasker, EMULATOR_STOP_POLL_INTERVAL) {
+                    return Err("Stop requested".to_string());
+                }
+            }
+        }
+
+        if target.state()?.running {
+            info!("[MXU_EMULATOR] Emulator is already running");
+        } else {
+            target.start()?;
+        }
+
+        if !param.wait_ready {
+            return Ok(());
+        }
+        let timeout = Duration::from_secs(
+            param
+                .timeout
+                .filter(|t| *t > 0)
+                .unwrap_or(EMULATOR_DEFAULT_TIMEOUT),
+        );
+        let address = target.wait_adb_ready(timeout, &|d| sleep_unless_cancelled(tasker, d))?;
+        if !param.reconnect {
+            return Ok(());
+        }
+        let tasker = tasker.ok_or("Cannot resolve tasker from context")?;
+        reconnect_controller(tasker, &address)
+    }
+}
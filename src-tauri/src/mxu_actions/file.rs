@@ -0,0 +1,666 @@
+//! MXU_FILE：复制、移动、删除文件，创建目录，打包与解压
+//!
+//! 所有路径（包括 glob 匹配结果与目标路径）必须位于允许的根目录内：
+//! 程序目录、数据目录以及用户在设置中添加的目录，防止错误的 pipeline 误删任意文件。
+
+use std::path::{Path, PathBuf};
+
+use log::{info, warn};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::template::{Escape, Template};
+use super::{is_cancelled, ActionContext, MxuAction};
+use crate::commands::file_ops::file_action_roots;
+use crate::commands::update::{copy_dir_recursive, extract_zip, move_to_old_folder};
+use crate::commands::utils::{get_app_data_dir, normalize_path};
+
+/// 文件操作类型
+#[derive(Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum FileOp {
+    /// 复制到 `dest`
+    Copy,
+    /// 移动到 `dest`
+    Move,
+    /// 删除（目录会递归删除）
+    Delete,
+    /// 创建目录（含父目录），已存在时视为成功
+    Mkdir,
+    /// 将匹配的文件 / 目录打包为 `dest` 指定的 zip
+    Zip,
+    /// 将压缩包（zip / tar.gz）解压到 `dest` 目录
+    Unzip,
+}
+
+/// 目标已存在时的处理方式
+#[derive(Deserialize, JsonSchema, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum Overwrite {
+    /// 返回失败（默认）
+    #[default]
+    Error,
+    /// 跳过该项
+    Skip,
+    /// 删除原目标后写入；unzip 时直接解压覆盖同名文件
+    Replace,
+    /// 将原目标移动到程序目录的 cache/old 后写入（与更新时处理旧文件的方式相同）
+    Backup,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct FileParam {
+    /// 操作：`copy` / `move` / `delete` / `mkdir` / `zip` / `unzip`
+    op: FileOp,
+    /// 源路径，mkdir 时为要创建的目录；支持 glob（`*`、`?`、`[...]`、`**`）与占位符，
+    /// 相对路径基于数据目录
+    path: String,
+    /// 目标路径：copy / move 为目标目录或新路径（源为 glob、匹配多项、目标是已存在的目录或以
+    /// 路径分隔符结尾时放入该目录），zip 为压缩包路径，unzip 为解压目录
+    #[serde(default)]
+    dest: Option<String>,
+    /// 目标已存在时的处理方式，默认 `error`
+    #[serde(default)]
+    overwrite: Overwrite,
+    /// 只在日志中输出将要执行的操作，不修改任何文件
+    #[serde(default)]
+    dry_run: bool,
+    /// 未匹配到任何文件时视为成功，默认失败
+    #[serde(default)]
+    allow_empty: bool,
+}
+
+/// 渲染占位符并转换为绝对路径（相对路径基于数据目录），去除 `.` / `..`
+fn resolve_path(template: &Template, raw: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(template.render(raw.trim(), Escape::Raw));
+    let path = if path.is_absolute() {
+        path
+    } else {
+        get_app_data_dir()?.join(path)
+    };
+    Ok(normalize_path(&path.to_string_lossy()))
+}
+
+/// 解析符号链接后的真实路径；路径不存在时解析最近的已存在祖先，再拼接剩余部分
+fn real_path(path: &Path) -> Result<PathBuf, String> {
+    let mut existing = path;
+    let mut rest = Vec::new();
+    loop {
+        if let Ok(real) = existing.canonicalize() {
+            return Ok(rest.iter().rev().fold(real, |p, name| p.join(name)));
+        }
+        let name = existing
+            .file_name()
+            .ok_or_else(|| format!("Invalid path: {}", path.display()))?;
+        rest.push(name);
+        existing = existing
+            .parent()
+            .ok_or_else(|| format!("Invalid path: {}", path.display()))?;
+    }
+}
+
+/// 检查路径位于允许的根目录内，返回其真实路径
+pub(super) fn ensure_allowed(path: &Path, roots: &[PathBuf]) -> Result<PathBuf, String> {
+    let real = real_path(path)?;
+    if roots.iter().any(|root| real.starts_with(root)) {
+        Ok(real)
+    } else {
+        Err(format!(
+            "Path is outside the allowed folders: {}",
+            path.display()
+        ))
+    }
+}
+
+fn path_exists(path: &Path) -> bool {
+    path.symlink_metadata().is_ok()
+}
+
+/// 展开 glob，返回匹配的路径；不含通配符时按普通路径处理
+fn expand_sources(path: &Path) -> Result<(Vec<PathBuf>, bool), String> {
+    let pattern = path.to_string_lossy();
+    if !pattern.contains(['*', '?', '[']) {
+        let sources = if path_exists(path) {
+            vec![path.to_path_buf()]
+        } else {
+            Vec::new()
+        };
+        return Ok((sources, false));
+    }
+
+    let entries =
+        glob::glob(&pattern).map_err(|e| format!("Invalid glob pattern '{}': {}", pattern, e))?;
+    let sources = entries
+        .filter_map(|entry| match entry {
+            Ok(path) => Some(path),
+            Err(e) => {
+                warn!("[MXU_FILE] Skipping unreadable entry: {}", e);
+                None
+            }
+        })
+        .collect();
+    Ok((sources, true))
+}
+
+/// 删除文件或目录（符号链接只删除链接本身）
+fn remove_path(path: &Path) -> Result<(), String> {
+    let is_dir = path
+        .symlink_metadata()
+        .map(|m| m.file_type().is_dir())
+        .unwrap_or(false);
+    let result = if is_dir {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    };
+    result.map_err(|e| format!("Failed to delete {}: {}", path.display(), e))
+}
+
+fn create_parent(path: &Path) -> Result<(), String> {
+    match path.parent() {
+        Some(parent) => std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory {}: {}", parent.display(), e)),
+        None => Ok(()),
+    }
+}
+
+fn copy_path(src: &Path, dst: &Path) -> Result<(), String> {
+    create_parent(dst)?;
+    if src.is_dir() {
+        copy_dir_recursive(src, dst)
+    } else {
+        std::fs::copy(src, dst).map(|_| ()).map_err(|e| {
+            format!(
+                "Failed to copy {} -> {}: {}",
+                src.display(),
+                dst.display(),
+                e
+            )
+        })
+    }
+}
+
+fn move_path(src: &Path, dst: &Path) -> Result<(), String> {
+    create_parent(dst)?;
+    if std::fs::rename(src, dst).is_ok() {
+        return Ok(());
+    }
+    // 跨磁盘时 rename 会失败，改为复制后删除源
+    copy_path(src, dst)?;
+    remove_path(src)
+}
+
+/// 将文件或目录（递归）写入 zip，`name` 为包内路径
+fn add_to_zip(
+    zip: &mut zip::ZipWriter<std::fs::File>,
+    path: &Path,
+    name: &str,
+    archive: &Path,
+    options: zip::write::SimpleFileOptions,
+) -> Result<usize, String> {
+    if path == archive {
+        return Ok(0);
+    }
+    if path.is_dir() {
+        zip.add_directory(format!("{}/", name), options)
+            .map_err(|e| format!("Failed to add {} to zip: {}", name, e))?;
+        let mut entries: Vec<_> = std::fs::read_dir(path)
+            .map_err(|e| format!("Failed to read directory {}: {}", path.display(), e))?
+            .flatten()
+            .map(|entry| entry.path())
+            .collect();
+        entries.sort();
+        let mut count = 0;
+        for entry in entries {
+            let child = entry.file_name().unwrap_or_default().to_string_lossy();
+            count += add_to_zip(
+                zip,
+                &entry,
+                &format!("{}/{}", name, child),
+                archive,
+                options,
+            )?;
+        }
+        Ok(count)
+    } else {
+        let mut file = std::fs::File::open(path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        zip.start_file(name, options)
+            .map_err(|e| format!("Failed to add {} to zip: {}", name, e))?;
+        std::io::copy(&mut file, zip)
+            .map_err(|e| format!("Failed to write {} to zip: {}", name, e))?;
+        Ok(1)
+    }
+}
+
+/// 将多个源打包为 zip，每个源以自身名称作为包内顶层路径，返回写入的文件数
+fn write_zip(sources: &[PathBuf], archive: &Path) -> Result<usize, String> {
+    create_parent(archive)?;
+    let file = std::fs::File::create(archive)
+        .map_err(|e| format!("Failed to create {}: {}", archive.display(), e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let mut count = 0;
+    for src in sources {
+        let name = src.file_name().unwrap_or_default().to_string_lossy();
+        count += add_to_zip(&mut zip, src, &name, archive, options)?;
+    }
+    zip.finish()
+        .map_err(|e| format!("Failed to finish {}: {}", archive.display(), e))?;
+    Ok(count)
+}
+
+/// 单次文件操作的执行状态
+struct FileRun {
+    overwrite: Overwrite,
+    dry_run: bool,
+    roots: Vec<PathBuf>,
+    done: usize,
+    skipped: usize,
+}
+
+impl FileRun {
+    /// 输出操作日志，dry-run 时加前缀
+    fn log(&self, message: &str) {
+        if self.dry_run {
+            info!("[MXU_FILE] [dry-run] {}", message);
+        } else {
+            info!("[MXU_FILE] {}", message);
+        }
+    }
+
+    /// 按覆盖策略处理已存在的目标，返回是否继续写入
+    fn prepare_target(&mut self, target: &Path, replace_in_place: bool) -> Result<bool, String> {
+        if !path_exists(target) {
+            return Ok(true);
+        }
+        match self.overwrite {
+            Overwrite::Error => Err(format!("Target already exists: {}", target.display())),
+            Overwrite::Skip => {
+                self.log(&format!("Skip existing {}", target.display()));
+                self.skipped += 1;
+                Ok(false)
+            }
+            Overwrite::Replace if replace_in_place => Ok(true),
+            Overwrite::Replace => {
+                self.log(&format!("Delete existing {}", target.display()));
+                if !self.dry_run {
+                    remove_path(target)?;
+                }
+                Ok(true)
+            }
+            Overwrite::Backup => {
+                self.log(&format!(
+                    "Back up existing {} to cache/old",
+                    target.display()
+                ));
+                if !self.dry_run {
+                    move_to_old_folder(target)?;
+                }
+                Ok(true)
+            }
+        }
+    }
+
+    /// 检查单个源及其目标是否允许操作，不修改任何文件
+    fn check(&self, op: FileOp, src: &Path, dst: &Path) -> Result<(), String> {
+        let real_src = ensure_allowed(src, &self.roots)?;
+        match op {
+            FileOp::Delete => {
+                // 允许目录本身不可删除，只能删除其中的内容
+                if self.roots.contains(&real_src) {
+                    return Err(format!(
+                        "Refusing to delete an allowed root folder: {}",
+                        src.display()
+                    ));
+                }
+            }
+            FileOp::Copy | FileOp::Move => {
+                let real_dst = ensure_allowed(dst, &self.roots)?;
+                if real_dst.starts_with(&real_src) {
+                    return Err(format!("Cannot {:?} {} into itself", op, src.display()));
+                }
+            }
+            _ => {
+                ensure_allowed(dst, &self.roots)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 先检查所有源与目标，全部允许后再依次执行，避免部分项被拒绝时已修改其它文件；
+    /// 每项之间检查停止请求
+    fn apply(
+        &mut self,
+        op: FileOp,
+        items: &[(PathBuf, PathBuf)],
+        cancelled: impl Fn() -> bool,
+    ) -> Result<(), String> {
+        for (src, dst) in items {
+            self.check(op, src, dst)?;
+        }
+        for (src, dst) in items {
+            if cancelled() {
+                return Err(format!(
+                    "Stopped after {} item(s) by stop request",
+                    self.done
+                ));
+            }
+            match op {
+                FileOp::Delete => self.delete(src)?,
+                FileOp::Unzip => self.unzip(src, dst)?,
+                _ => self.transfer(op, src, dst)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// 复制或移动单个源
+    fn transfer(&mut self, op: FileOp, src: &Path, dst: &Path) -> Result<(), String> {
+        if !self.prepare_target(dst, false)? {
+            return Ok(());
+        }
+
+        self.log(&format!("{:?} {} -> {}", op, src.display(), dst.display()));
+        if !self.dry_run {
+            match op {
+                FileOp::Move => move_path(src, dst)?,
+                _ => copy_path(src, dst)?,
+            }
+        }
+        self.done += 1;
+        Ok(())
+    }
+
+    fn delete(&mut self, path: &Path) -> Result<(), String> {
+        self.log(&format!("Delete {}", path.display()));
+        if !self.dry_run {
+            remove_path(path)?;
+        }
+        self.done += 1;
+        Ok(())
+    }
+
+    fn unzip(&mut self, archive: &Path, dest: &Path) -> Result<(), String> {
+        if !self.prepare_target(dest, true)? {
+            return Ok(());
+        }
+        self.log(&format!(
+            "Extract {} -> {}",
+            archive.display(),
+            dest.display()
+        ));
+        if !self.dry_run {
+            extract_zip(
+                archive.to_string_lossy().to_string(),
+                dest.to_string_lossy().to_string(),
+            )?;
+        }
+        self.done += 1;
+        Ok(())
+    }
+}
+
+/// 在允许的目录范围内执行文件操作，支持 glob、覆盖策略与 dry-run，
+/// 处理多个匹配项时会在每项之间检查停止请求
+pub struct FileAction;
+
+impl MxuAction for FileAction {
+    const NAME: &'static str = "MXU_FILE_ACTION";
+    const TAG: &'static str = "MXU_FILE";
+    type Param = FileParam;
+
+    fn run(ctx: &ActionContext, param: FileParam) -> Result<(), String> {
+        if param.path.trim().is_empty() {
+            return Err("Empty 'path' parameter".to_string());
+        }
+        let template = Template::new(ctx);
+        let path = resolve_path(&template, &param.path)?;
+        let dest_raw = param
+            .dest
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty());
+        let dest = dest_raw
+            .map(|raw| resolve_path(&template, raw))
+            .transpose()?;
+
+        let mut run = FileRun {
+            overwrite: param.overwrite,
+            dry_run: param.dry_run,
+            roots: file_action_roots()?,
+            done: 0,
+            skipped: 0,
+        };
+
+        if param.op == FileOp::Mkdir {
+            ensure_allowed(&path, &run.roots)?;
+            run.log(&format!("Create directory {}", path.display()));
+            if !run.dry_run {
+                std::fs::create_dir_all(&path)
+                    .map_err(|e| format!("Failed to create directory {}: {}", path.display(), e))?;
+            }
+            return Ok(());
+        }
+
+        let (sources, is_glob) = expand_sources(&path)?;
+        if sources.is_empty() {
+            if param.allow_empty {
+                info!("[MXU_FILE] Nothing matches {}", path.display());
+                return Ok(());
+            }
+            return Err(format!("Nothing matches {}", path.display()));
+        }
+        if param.op != FileOp::Delete && dest.is_none() {
+            return Err(format!("Missing 'dest' parameter for {:?}", param.op));
+        }
+        let dest = dest.unwrap_or_default();
+
+        if param.op == FileOp::Zip {
+            let archive = dest;
+            ensure_allowed(&archive, &run.roots)?;
+            for src in &sources {
+                ensure_allowed(src, &run.roots)?;
+            }
+            if !run.prepare_target(&archive, false)? {
+                return Ok(());
+            }
+            run.log(&format!(
+                "Zip {} item(s) matching {} -> {}",
+                sources.len(),
+                path.display(),
+                archive.display()
+            ));
+            if !run.dry_run {
+                let count = write_zip(&sources, &archive)?;
+                info!(
+                    "[MXU_FILE] Wrote {} file(s) to {}",
+                    count,
+                    archive.display()
+                );
+            }
+            return Ok(());
+        }
+
+        // copy / move 的目标是否为放置源的目录
+        let into_dir = is_glob
+            || sources.len() > 1
+            || dest.is_dir()
+            || dest_raw.is_some_and(|raw| raw.ends_with(['/', '\\']));
+
+        let items = sources
+            .into_iter()
+            .map(|src| {
+                let target = match param.op {
+                    FileOp::Copy | FileOp::Move if into_dir => {
+                        let name = src
+                            .file_name()
+                            .ok_or_else(|| format!("Invalid source path: {}", src.display()))?;
+                        dest.join(name)
+                    }
+                    _ => dest.clone(),
+                };
+                Ok((src, target))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let tasker = ctx.tasker();
+        run.apply(param.op, &items, || is_cancelled(tasker))?;
+
+        info!(
+            "[MXU_FILE] {:?} finished: {} processed, {} skipped{}",
+            param.op,
+            run.done,
+            run.skipped,
+            if run.dry_run { " (dry-run)" } else { "" }
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// 测试用临时目录，drop 时删除
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            static NEXT: AtomicU32 = AtomicU32::new(0);
+            let dir = std::env::temp_dir().join(format!(
+                "mxu-file-test-{}-{}",
+                std::process::id(),
+                NEXT.fetch_add(1, Ordering::Relaxed)
+            ));
+            std::fs::create_dir_all(&dir).unwrap();
+            Self(dir.canonicalize().unwrap())
+        }
+
+        /// 创建文件（含父目录）
+        fn file(&self, rel: &str) -> PathBuf {
+            let path = self.0.join(rel);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, rel).unwrap();
+            path
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn file_run(roots: Vec<PathBuf>) -> FileRun {
+        FileRun {
+            overwrite: Overwrite::Error,
+            dry_run: false,
+            roots,
+            done: 0,
+            skipped: 0,
+        }
+    }
+
+    fn items(pattern: &Path, dest: &Path, into_dir: bool) -> Vec<(PathBuf, PathBuf)> {
+        let (sources, _) = expand_sources(pattern).unwrap();
+        sources
+            .into_iter()
+            .map(|src| {
+                let target = if into_dir {
+                    dest.join(src.file_name().unwrap())
+                } else {
+                    dest.to_path_buf()
+                };
+                (src, target)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn mixed_glob_deletes_nothing() {
+        let tmp = TempDir::new();
+        let inside = tmp.file("a/cache/x.txt");
+        let outside = tmp.file("b/cache/y.txt");
+        let mut run = file_run(vec![tmp.0.join("a")]);
+
+        let items = items(&tmp.0.join("*/cache"), Path::new(""), false);
+        assert_eq!(items.len(), 2);
+        let err = run.apply(FileOp::Delete, &items, || false).unwrap_err();
+        assert!(err.contains("outside the allowed folders"), "{}", err);
+        assert!(inside.exists());
+        assert!(outside.exists());
+        assert_eq!(run.done, 0);
+    }
+
+    #[test]
+    fn move_with_target_outside_moves_nothing() {
+        let tmp = TempDir::new();
+        let first = tmp.file("a/src/1.txt");
+        let second = tmp.file("a/src/2.txt");
+        let mut run = file_run(vec![tmp.0.join("a")]);
+
+        let items = items(&tmp.0.join("a/src/*.txt"), &tmp.0.join("b"), true);
+        let err = run.apply(FileOp::Move, &items, || false).unwrap_err();
+        assert!(err.contains("outside the allowed folders"), "{}", err);
+        assert!(first.exists() && second.exists());
+        assert!(!tmp.0.join("b").exists());
+    }
+
+    #[test]
+    fn refuses_to_delete_root_before_deleting_siblings() {
+        let tmp = TempDir::new();
+        let sibling = tmp.file("a/1.txt");
+        let root = tmp.file("a/b/2.txt").parent().unwrap().to_path_buf();
+        let mut run = file_run(vec![tmp.0.join("a"), root.clone()]);
+
+        let items = vec![
+            (sibling.clone(), PathBuf::new()),
+            (root.clone(), PathBuf::new()),
+        ];
+        let err = run.apply(FileOp::Delete, &items, || false).unwrap_err();
+        assert!(err.contains("allowed root folder"), "{}", err);
+        assert!(sibling.exists() && root.exists());
+    }
+
+    #[test]
+    fn copies_all_matches_inside_roots() {
+        let tmp = TempDir::new();
+        tmp.file("a/src/1.txt");
+        tmp.file("a/src/2.txt");
+        let mut run = file_run(vec![tmp.0.join("a")]);
+
+        let dest = tmp.0.join("a/dst");
+        let items = items(&tmp.0.join("a/src/*.txt"), &dest, true);
+        run.apply(FileOp::Copy, &items, || false).unwrap();
+        assert_eq!(run.done, 2);
+        assert_eq!(
+            std::fs::read_to_string(dest.join("1.txt")).unwrap(),
+            "a/src/1.txt"
+        );
+        assert!(dest.join("2.txt").exists());
+    }
+
+    #[test]
+    fn stop_request_is_checked_between_items() {
+        let tmp = TempDir::new();
+        tmp.file("a/1.txt");
+        tmp.file("a/2.txt");
+        let mut run = file_run(vec![tmp.0.join("a")]);
+
+        let items = items(&tmp.0.join("a/*.txt"), Path::new(""), false);
+        let calls = std::cell::Cell::new(0);
+        let err = run
+            .apply(FileOp::Delete, &items, || {
+                calls.set(calls.get() + 1);
+                calls.get() > 1
+            })
+            .unwrap_err();
+        assert!(err.contains("Stopped after 1 item(s)"), "{}", err);
+        assert!(!tmp.0.join("a/1.txt").exists());
+        assert!(tmp.0.join("a/2.txt").exists());
+    }
+}
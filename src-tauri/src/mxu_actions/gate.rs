@@ -0,0 +1,294 @@
+//! MXU_GATE：按条件表达式决定继续、失败或跳转到指定节点
+//!
+//! 条件为一个 Rhai 表达式（只允许表达式，不允许语句与循环），可用的函数：
+//!
+//! - `hour()` / `minute()` / `weekday()`（1-7 对应周一至周日）/ `day()` / `month()` / `year()`
+//! - `date()` 返回 `YYYY-MM-DD`，`time()` 返回 `HH:MM`
+//! - `time_between(start, end)`：当前时间是否在 `HH:MM` 区间内，`start > end` 时表示跨越午夜
+//! - `get_var(name[, scope])`：读取持久化变量，scope 为 `instance`（默认）或 `global`，不存在时为 `()`
+//! - `file_exists(path)`：相对路径基于数据目录
+//! - `process_running(name)`：进程名精确匹配（忽略大小写，可省略 .exe）
+//! - `window_exists(title)`：存在标题包含 `title` 的可见窗口
+//! - `port_open(address)`：端口号或 `host:port` 可以连接
+//! - `url_ok(url)`：GET 请求返回 2xx
+//!
+//! 例如 `weekday() <= 5 && time_between("08:00", "23:00") && !file_exists("pause.flag")`
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{Datelike, Local, NaiveTime, Timelike};
+use log::info;
+use once_cell::sync::Lazy;
+use rhai::{Dynamic, Engine, EvalAltResult};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::script::get_variable;
+use super::{is_cancelled, non_empty, ActionContext, MxuAction};
+use crate::maa_ffi::{callback_fns, from_cstr, to_cstring, MaaContext, MaaId};
+use crate::process_util::{find_processes, is_port_open, window_title_exists, ProcessMatcher};
+
+/// 条件求值时间上限（秒），包括网络检查
+const GATE_DEFAULT_TIMEOUT: u64 = 10;
+/// 表达式的操作数上限
+const GATE_MAX_OPERATIONS: u64 = 100_000;
+
+/// 条件不满足时的处理方式
+#[derive(Deserialize, JsonSchema, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum GateOutcome {
+    /// 节点失败（默认）
+    #[default]
+    Fail,
+    /// 节点成功，按原 next 继续
+    Succeed,
+    /// 节点成功，并将当前节点的 next 改为 `skip_to`；同一任务中再次执行该节点时恢复原 next
+    Skip,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct GateParam {
+    /// 条件表达式，结果必须为 bool
+    condition: String,
+    /// 条件不满足时：`fail`（默认）/ `succeed` / `skip`
+    #[serde(default)]
+    on_false: GateOutcome,
+    /// `on_false` 为 `skip` 时跳转的节点
+    #[serde(default)]
+    skip_to: Option<String>,
+    /// 求值时间上限（秒），默认 10
+    #[serde(default)]
+    timeout: Option<u64>,
+}
+
+fn gate_error(e: impl std::fmt::Display) -> Box<EvalAltResult> {
+    e.to_string().into()
+}
+
+/// 解析 `HH:MM`
+fn parse_hhmm(s: &str) -> Result<NaiveTime, Box<EvalAltResult>> {
+    NaiveTime::parse_from_str(s.trim(), "%H:%M")
+        .map_err(|_| gate_error(format!("Invalid time '{}', expected HH:MM", s)))
+}
+
+/// 当前时间是否位于区间内，`start > end` 时区间跨越午夜
+fn time_between(start: &str, end: &str) -> Result<bool, Box<EvalAltResult>> {
+    let (start, end) = (parse_hhmm(start)?, parse_hhmm(end)?);
+    let now = Local::now().time();
+    Ok(if start <= end {
+        start <= now && now < end
+    } else {
+        now >= start || now < end
+    })
+}
+
+/// GET 请求是否返回 2xx，超时不超过剩余时间
+fn url_ok(deadline: Instant, url: &str) -> Result<bool, Box<EvalAltResult>> {
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    if remaining.is_zero() {
+        return Err(gate_error("Condition time limit exceeded"));
+    }
+    let client = reqwest::blocking::Client::builder()
+        .timeout(remaining)
+        .build()
+        .map_err(gate_error)?;
+    match client.get(url).send() {
+        Ok(response) => Ok(response.status().is_success()),
+        Err(e) => {
+            info!("[MXU_GATE] url_ok({}) failed: {}", url, e);
+            Ok(false)
+        }
+    }
+}
+
+/// 创建只注册了条件函数的表达式引擎
+fn build_engine(ctx: &ActionContext, deadline: Instant) -> Engine {
+    let mut engine = Engine::new();
+    let tasker = ctx.tasker();
+    let task_id = ctx.task_id;
+
+    engine.set_module_resolver(rhai::module_resolvers::DummyModuleResolver::new());
+    engine.disable_symbol("eval");
+    engine.set_max_operations(GATE_MAX_OPERATIONS);
+    engine.set_max_expr_depths(32, 16);
+    engine.on_progress(move |_| {
+        if Instant::now() >= deadline || is_cancelled(tasker) {
+            Some(Dynamic::UNIT)
+        } else {
+            None
+        }
+    });
+
+    // 时间
+    engine.register_fn("hour", || Local::now().hour() as i64);
+    engine.register_fn("minute", || Local::now().minute() as i64);
+    engine.register_fn("weekday", || {
+        Local::now().weekday().number_from_monday() as i64
+    });
+    engine.register_fn("day", || Local::now().day() as i64);
+    engine.register_fn("month", || Local::now().month() as i64);
+    engine.register_fn("year", || Local::now().year() as i64);
+    engine.register_fn("date", || Local::now().format("%Y-%m-%d").to_string());
+    engine.register_fn("time", || Local::now().format("%H:%M").to_string());
+    engine.register_fn("time_between", time_between);
+
+    // 持久化变量
+    engine.register_fn("get_var", move |name: &str| get_variable(task_id, name, ""));
+    engine.register_fn("get_var", move |name: &str, scope: &str| {
+        get_variable(task_id, name, scope)
+    });
+
+    // 主机状态
+    engine.register_fn("file_exists", |path: &str| {
+        let path = std::path::PathBuf::from(path);
+        let path = if path.is_absolute() {
+            path
+        } else {
+            crate::commands::utils::get_app_data_dir()
+                .map_err(gate_error)?
+                .join(path)
+        };
+        Ok::<_, Box<EvalAltResult>>(path.exists())
+    });
+    engine.register_fn("process_running", |name: &str| {
+        let matcher = ProcessMatcher {
+            name: Some(name.to_string()),
+            ..Default::default()
+        };
+        !find_processes(&matcher).is_empty()
+    });
+    engine.register_fn("window_exists", window_title_exists);
+    engine.register_fn("port_open", |address: Dynamic| {
+        is_port_open(&address.to_string()).map_err(gate_error)
+    });
+    engine.register_fn("url_ok", move |url: &str| url_ok(deadline, url));
+
+    engine
+}
+
+/// 被 `skip` 改写过 next 的节点及其原 next，按 (task_id, 节点名) 区分
+///
+/// 对 context 的 pipeline 修改在整个任务内有效，节点再次执行（循环、重试）前需恢复
+static SKIPPED_NEXT: Lazy<Mutex<HashMap<(MaaId, String), Value>>> = Lazy::new(Default::default);
+
+fn lock_skipped() -> std::sync::MutexGuard<'static, HashMap<(MaaId, String), Value>> {
+    SKIPPED_NEXT.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 任务结束后丢弃其记录的原 next
+pub(super) fn forget_task(task_id: MaaId) {
+    lock_skipped().retain(|(id, _), _| *id != task_id);
+}
+
+/// 通过 MaaContext 读取节点当前的 next
+fn node_next(context: *mut MaaContext, node: &str) -> Result<Value, String> {
+    let fns = callback_fns()?;
+    let get_node_data = fns
+        .context_get_node_data
+        .ok_or("MaaContextGetNodeData not available, please update MaaFramework")?;
+    let node_c = to_cstring(node);
+    let data = unsafe {
+        let buffer = (fns.string_buffer_create)();
+        if buffer.is_null() {
+            return Err("Failed to create string buffer".to_string());
+        }
+        let data = (get_node_data(context, node_c.as_ptr(), buffer) != 0)
+            .then(|| from_cstr((fns.string_buffer_get)(buffer)));
+        (fns.string_buffer_destroy)(buffer);
+        data
+    };
+    let data = data.ok_or_else(|| format!("Failed to get node data of {}", node))?;
+    let data: Value =
+        serde_json::from_str(&data).map_err(|e| format!("Invalid node data of {}: {}", node, e))?;
+    Ok(data
+        .get("next")
+        .cloned()
+        .unwrap_or_else(|| Value::Array(Vec::new())))
+}
+
+/// 通过 MaaContext 将当前节点的 next 改为 `next`（仅对本次任务生效）
+fn override_next(context: *mut MaaContext, node: &str, next: Value) -> Result<(), String> {
+    let override_fn = callback_fns()?
+        .context_override_pipeline
+        .ok_or("MaaContextOverridePipeline not available, please update MaaFramework")?;
+    let pipeline = serde_json::json!({ node: { "next": next } }).to_string();
+    let pipeline = to_cstring(&pipeline);
+    if unsafe { override_fn(context, pipeline.as_ptr()) } == 0 {
+        return Err(format!("Failed to override next of {}", node));
+    }
+    Ok(())
+}
+
+/// 将当前节点的 next 改为 `target`，并记录原 next 以便再次执行时恢复
+fn skip_next(ctx: &ActionContext, target: &str) -> Result<(), String> {
+    let original = node_next(ctx.context, &ctx.node)?;
+    override_next(ctx.context, &ctx.node, serde_json::json!([target]))?;
+    lock_skipped().insert((ctx.task_id, ctx.node.clone()), original);
+    Ok(())
+}
+
+/// 恢复本任务中此前被 `skip` 改写的 next
+fn restore_next(ctx: &ActionContext) -> Result<(), String> {
+    let original = lock_skipped().remove(&(ctx.task_id, ctx.node.clone()));
+    if let Some(next) = original {
+        info!("[MXU_GATE] Restoring next of {}", ctx.node);
+        override_next(ctx.context, &ctx.node, next)?;
+    }
+    Ok(())
+}
+
+/// 对条件表达式求值：满足时成功，不满足时按 `on_false` 失败、成功或跳转到 `skip_to`
+pub struct GateAction;
+
+impl MxuAction for GateAction {
+    const NAME: &'static str = "MXU_GATE_ACTION";
+    const TAG: &'static str = "MXU_GATE";
+    type Param = GateParam;
+
+    fn run(ctx: &ActionContext, param: GateParam) -> Result<(), String> {
+        // 上次执行时的跳转对整个任务有效，先恢复原 next，条件仍不满足时再重新跳转
+        restore_next(ctx)?;
+
+        let condition = non_empty(Some(param.condition)).ok_or("Empty 'condition' parameter")?;
+        let skip_to = non_empty(param.skip_to);
+        if param.on_false == GateOutcome::Skip && skip_to.is_none() {
+            return Err("Missing 'skip_to' parameter for on_false = skip".to_string());
+        }
+
+        let timeout = param
+            .timeout
+            .filter(|t| *t > 0)
+            .unwrap_or(GATE_DEFAULT_TIMEOUT);
+        let deadline = Instant::now() + Duration::from_secs(timeout);
+        let engine = build_engine(ctx, deadline);
+
+        let passed = engine
+            .eval_expression::<bool>(&condition)
+            .map_err(|e| match *e {
+                EvalAltResult::ErrorTerminated(..) if is_cancelled(ctx.tasker()) => {
+                    "Stop requested".to_string()
+                }
+                EvalAltResult::ErrorTerminated(..) => {
+                    format!("Condition timed out after {}s", timeout)
+                }
+                other => format!("Condition error: {}", other),
+            })?;
+        info!("[MXU_GATE] {} => {}", condition, passed);
+        if passed {
+            return Ok(());
+        }
+
+        match param.on_false {
+            GateOutcome::Fail => Err(format!("Condition not met: {}", condition)),
+            GateOutcome::Succeed => Ok(()),
+            GateOutcome::Skip => {
+                let target = skip_to.unwrap_or_default();
+                info!("[MXU_GATE] Skipping to {}", target);
+                skip_next(ctx, &target)
+            }
+        }
+    }
+}
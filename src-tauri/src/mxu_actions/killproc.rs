@@ -0,0 +1,238 @@
+//! MXU_KILLPROC：按名称 / 正则 / 路径 / 命令行结束进程，或结束 MXU 自身
+
+use std::time::Duration;
+
+use log::{info, warn};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::{non_empty, ActionContext, MxuAction};
+use crate::maa_ffi::emit_killproc_result;
+use crate::process_util::{find_processes, terminate_processes, ProcessMatcher};
+
+/// graceful 模式的默认宽限期（秒）
+const KILLPROC_DEFAULT_GRACE_SECS: f64 = 5.0;
+
+/// 多个匹配条件需同时满足
+#[derive(Deserialize, JsonSchema)]
+pub struct KillProcParam {
+    /// 结束 MXU 自身（及同名进程），未提供任何匹配条件时默认为 true
+    #[serde(default)]
+    kill_self: Option<bool>,
+    /// 精确进程名（忽略大小写，可省略 .exe）
+    #[serde(default)]
+    process_name: Option<String>,
+    /// 进程名正则
+    #[serde(default)]
+    name_regex: Option<String>,
+    /// 可执行文件完整路径
+    #[serde(default)]
+    exe_path: Option<String>,
+    /// 命令行包含的子串
+    #[serde(default)]
+    cmdline_contains: Option<String>,
+    /// 先请求正常退出，等待宽限期后再强制结束
+    #[serde(default)]
+    graceful: bool,
+    /// graceful 模式的宽限期（秒），默认 5
+    #[serde(default)]
+    grace_period: Option<f64>,
+    /// 只报告匹配的进程，不结束
+    #[serde(default)]
+    dry_run: bool,
+}
+
+/// 受影响的 PID 写入日志并通过 `maa-killproc-result` 事件发送到前端
+pub struct KillProcAction;
+
+impl MxuAction for KillProcAction {
+    const NAME: &'static str = "MXU_KILLPROC_ACTION";
+    const TAG: &'static str = "MXU_KILLPROC";
+    type Param = KillProcParam;
+
+    fn run(ctx: &ActionContext, param: KillProcParam) -> Result<(), String> {
+        let mut matcher = ProcessMatcher {
+            name: non_empty(param.process_name),
+            name_regex: None,
+            exe_path: non_empty(param.exe_path).map(std::path::PathBuf::from),
+            cmdline_contains: non_empty(param.cmdline_contains),
+        };
+        if let Some(pattern) = non_empty(param.name_regex) {
+            let re = regex::Regex::new(&pattern)
+                .map_err(|e| format!("Invalid name_regex '{}': {}", pattern, e))?;
+            matcher.name_regex = Some(re);
+        }
+
+        let kill_self = param.kill_self.unwrap_or(matcher.is_empty());
+        let dry_run = param.dry_run;
+        let grace_period = param.graceful.then(|| {
+            Duration::from_secs_f64(
+                param
+                    .grace_period
+                    .unwrap_or(KILLPROC_DEFAULT_GRACE_SECS)
+                    .max(0.0),
+            )
+        });
+
+        let self_pid = std::process::id();
+        if kill_self {
+            // 获取当前可执行文件名
+            let exe_name = std::env::current_exe()
+                .ok()
+                .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()));
+            match exe_name {
+                Some(name) => {
+                    info!("[MXU_KILLPROC] Killing self process, current exe: {}", name);
+                    matcher = ProcessMatcher {
+                        name: Some(name),
+                        ..Default::default()
+                    };
+                }
+                None => {
+                    warn!(
+                        "[MXU_KILLPROC] Could not determine current exe name, using process::exit"
+                    );
+                    std::process::exit(0);
+                }
+            }
+        } else if matcher.is_empty() {
+            return Err("No match condition provided (process_name / name_regex / exe_path / cmdline_contains)".to_string());
+        }
+
+        // 除非 kill_self，否则不会结束 MXU 自身
+        let matched: Vec<u32> = find_processes(&matcher)
+            .into_iter()
+            .inspect(|p| {
+                info!(
+                    "[MXU_KILLPROC] Matched pid={} name={} exe={:?} cmdline={}",
+                    p.pid, p.name, p.exe, p.cmdline
+                )
+            })
+            .map(|p| p.pid)
+            .filter(|pid| kill_self || *pid != self_pid)
+            .collect();
+
+        if matched.is_empty() {
+            emit_killproc_result(ctx.task_id, &ctx.node, dry_run, &[], &[], &[]);
+            return Err("No matching process found".to_string());
+        }
+
+        if dry_run {
+            info!("[MXU_KILLPROC] Dry run, matched PIDs: {:?}", matched);
+            emit_killproc_result(ctx.task_id, &ctx.node, true, &matched, &[], &[]);
+            return Ok(());
+        }
+
+        // 自身最后处理，保证其它进程的结果能够记录下来
+        let others: Vec<u32> = matched
+            .iter()
+            .copied()
+            .filter(|pid| *pid != self_pid)
+            .collect();
+        let report = terminate_processes(&others, grace_period);
+        let mut affected = report.exited.clone();
+        affected.extend(&report.killed);
+        info!(
+            "[MXU_KILLPROC] Exited gracefully: {:?}, killed: {:?}, failed: {:?}",
+            report.exited, report.killed, report.failed
+        );
+        emit_killproc_result(
+            ctx.task_id,
+            &ctx.node,
+            false,
+            &matched,
+            &affected,
+            &report.failed,
+        );
+
+        if matched.contains(&self_pid) {
+            info!("[MXU_KILLPROC] Exiting self process (pid={})", self_pid);
+            std::process::exit(0);
+        }
+
+        if report.failed.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Failed to terminate: {:?}", report.failed))
+        }
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::process::{Child, Command};
+    use std::time::Instant;
+
+    fn ctx() -> ActionContext {
+        ActionContext {
+            context: std::ptr::null_mut(),
+            task_id: 0,
+            node: "KillProcTest".to_string(),
+            box_rect: None,
+        }
+    }
+
+    fn run(param: serde_json::Value) -> Result<(), String> {
+        KillProcAction::run(&ctx(), serde_json::from_value(param).unwrap())
+    }
+
+    /// 启动带唯一命令行标记的 sleep 子进程
+    fn spawn_sleep(tag: u32) -> (Child, String) {
+        let marker = format!("3600.{}{}", tag, std::process::id());
+        let child = Command::new("sleep").arg(&marker).spawn().unwrap();
+        (child, marker)
+    }
+
+    fn wait_exit(child: &mut Child) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            if child.try_wait().unwrap().is_some() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        false
+    }
+
+    #[test]
+    fn dry_run_keeps_process() {
+        let (mut child, marker) = spawn_sleep(11);
+        let result = run(serde_json::json!({
+            "name_regex": "^sleep$",
+            "cmdline_contains": marker,
+            "dry_run": true,
+        }));
+        assert_eq!(result, Ok(()));
+        assert!(child.try_wait().unwrap().is_none());
+        child.kill().unwrap();
+        child.wait().unwrap();
+    }
+
+    #[test]
+    fn graceful_kill_by_cmdline() {
+        let (mut child, marker) = spawn_sleep(12);
+        let result = run(serde_json::json!({
+            "process_name": "sleep",
+            "cmdline_contains": marker,
+            "graceful": true,
+            "grace_period": 5,
+        }));
+        assert_eq!(result, Ok(()));
+        assert!(wait_exit(&mut child));
+    }
+
+    #[test]
+    fn no_match_fails() {
+        let result = run(serde_json::json!({
+            "cmdline_contains": format!("mxu-killproc-none-{}", std::process::id()),
+        }));
+        assert_eq!(result, Err("No matching process found".to_string()));
+    }
+
+    #[test]
+    fn invalid_regex_fails() {
+        let result = run(serde_json::json!({ "name_regex": "(" }));
+        assert!(result.unwrap_err().starts_with("Invalid name_regex"));
+    }
+}
@@ -0,0 +1,309 @@
+//! MXU_LAUNCH：启动外部程序，可等待退出或等待就绪
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::template::{Escape, Template};
+use super::{non_empty, sleep_unless_cancelled, ActionContext, MxuAction, PollOutcome};
+
+/// 等待进程退出时的轮询间隔
+pub(super) const LAUNCH_POLL_INTERVAL: Duration = Duration::from_millis(200);
+/// 就绪检测的默认超时（秒）
+const LAUNCH_READY_DEFAULT_TIMEOUT: u64 = 120;
+
+/// program、args、cwd 与 env 的值中可使用占位符（见 [`super::template`]），
+/// args 中的替换值会加引号，始终作为单个参数
+#[derive(Deserialize, JsonSchema)]
+pub struct LaunchParam {
+    /// 要启动的程序
+    program: String,
+    /// 程序参数，按 shell 规则拆分
+    #[serde(default)]
+    args: String,
+    /// 工作目录，默认为程序所在目录
+    #[serde(default)]
+    cwd: Option<String>,
+    /// 额外环境变量，值为 null 时移除该变量
+    #[serde(default)]
+    env: Option<BTreeMap<String, serde_json::Value>>,
+    /// 是否等待进程退出
+    #[serde(default)]
+    wait_for_exit: bool,
+    /// 等待退出的超时（秒），超时后结束进程并返回失败，0 或缺省表示不限
+    #[serde(default)]
+    timeout: Option<u64>,
+    /// 视为成功的退出码列表，缺省时任何退出码都视为成功
+    #[serde(default)]
+    success_exit_codes: Option<Vec<i64>>,
+    /// 是否将 stdout/stderr 写入 MXU 日志
+    #[serde(default)]
+    capture_output: bool,
+    /// 就绪条件，启动后轮询直到全部满足
+    #[serde(default)]
+    ready: Option<LaunchReadyParam>,
+}
+
+/// 端口可写作数字或 `host:port` 字符串
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub(super) enum PortTarget {
+    Port(u16),
+    Address(String),
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct LaunchReadyParam {
+    /// 需存在的进程名
+    #[serde(default)]
+    process: Option<String>,
+    /// 需可连接的端口，纯数字表示本机端口
+    #[serde(default)]
+    port: Option<PortTarget>,
+    /// 需出现的窗口标题（包含匹配，忽略大小写）
+    #[serde(default)]
+    window: Option<String>,
+    /// 超时（秒），默认 120
+    #[serde(default)]
+    timeout: Option<u64>,
+    /// 检测间隔（秒），默认 1
+    #[serde(default)]
+    interval: Option<f64>,
+}
+
+/// 将子进程的输出逐行写入 MXU 日志
+fn forward_launch_output<R: std::io::Read + Send + 'static>(
+    reader: R,
+    pid: u32,
+    stream: &'static str,
+) {
+    std::thread::spawn(move || {
+        use std::io::BufRead;
+        let mut reader = std::io::BufReader::new(reader);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let line = String::from_utf8_lossy(&buf);
+                    let line = line.trim_end();
+                    if !line.is_empty() {
+                        info!("[MXU_LAUNCH] [{}] {}: {}", pid, stream, line);
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// 就绪条件：所有已配置的条件同时满足才视为就绪
+struct LaunchReadiness {
+    process: Option<String>,
+    port: Option<String>,
+    window: Option<String>,
+    timeout: Duration,
+    interval: Duration,
+}
+
+impl LaunchReadiness {
+    /// 从 `ready` 参数解析，未配置任何条件时返回 None
+    fn from_param(param: Option<LaunchReadyParam>) -> Option<Self> {
+        let param = param?;
+        let readiness = Self {
+            process: non_empty(param.process),
+            port: match param.port {
+                Some(PortTarget::Port(port)) => Some(port.to_string()),
+                Some(PortTarget::Address(address)) => non_empty(Some(address)),
+                None => None,
+            },
+            window: non_empty(param.window),
+            timeout: Duration::from_secs(param.timeout.unwrap_or(LAUNCH_READY_DEFAULT_TIMEOUT)),
+            interval: Duration::from_millis(
+                (param.interval.unwrap_or(1.0).max(0.1) * 1000.0) as u64,
+            ),
+        };
+        (readiness.process.is_some() || readiness.port.is_some() || readiness.window.is_some())
+            .then_some(readiness)
+    }
+
+    fn is_ready(&self) -> Result<bool, String> {
+        if let Some(name) = &self.process {
+            if !crate::process_util::is_process_running(name) {
+                return Ok(false);
+            }
+        }
+        if let Some(port) = &self.port {
+            if !crate::process_util::is_port_open(port)? {
+                return Ok(false);
+            }
+        }
+        if let Some(title) = &self.window {
+            if !crate::process_util::window_title_exists(title) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// 轮询直到就绪，超时、检测出错或被停止时返回错误
+    fn wait(&self, ctx: &ActionContext) -> Result<(), String> {
+        info!(
+            "[MXU_LAUNCH] Waiting for readiness: process={:?}, port={:?}, window={:?}, timeout={}s",
+            self.process,
+            self.port,
+            self.window,
+            self.timeout.as_secs()
+        );
+        let outcome = ctx.poll_until("MXU_LAUNCH", Some(self.timeout), self.interval, || {
+            self.is_ready()
+                .map_err(|e| format!("Readiness check failed: {}", e))
+        })?;
+        match outcome {
+            PollOutcome::Satisfied => {
+                info!("[MXU_LAUNCH] Target is ready");
+                Ok(())
+            }
+            PollOutcome::TimedOut => Err("Timed out waiting for readiness".to_string()),
+            PollOutcome::Cancelled => Err("Readiness wait cancelled by stop request".to_string()),
+        }
+    }
+}
+
+/// 启动程序；`wait_for_exit` 时等待退出并检查退出码，配置了 `ready` 时等待就绪
+pub struct LaunchAction;
+
+impl MxuAction for LaunchAction {
+    const NAME: &'static str = "MXU_LAUNCH_ACTION";
+    const TAG: &'static str = "MXU_LAUNCH";
+    type Param = LaunchParam;
+
+    fn run(ctx: &ActionContext, param: LaunchParam) -> Result<(), String> {
+        let template = Template::new(ctx);
+        let program = non_empty(Some(param.program)).ok_or("Empty 'program' parameter")?;
+        let program = template.render(&program, Escape::Raw);
+        let args_str = template.render(&param.args, Escape::Shell);
+        let wait_for_exit = param.wait_for_exit;
+        let timeout = param.timeout.filter(|t| *t > 0).map(Duration::from_secs);
+        let readiness = LaunchReadiness::from_param(param.ready);
+
+        info!(
+            "[MXU_LAUNCH] Launching: program={}, args={}, wait_for_exit={}",
+            program, args_str, wait_for_exit
+        );
+
+        let args_vec: Vec<String> = if args_str.trim().is_empty() {
+            Vec::new()
+        } else {
+            match shell_words::split(&args_str) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    warn!(
+                        "[MXU_LAUNCH] Failed to parse arguments with shell_words ({}); falling back to whitespace split: {}",
+                        e, args_str
+                    );
+                    args_str.split_whitespace().map(|s| s.to_string()).collect()
+                }
+            }
+        };
+
+        let mut cmd = std::process::Command::new(&program);
+
+        if !args_vec.is_empty() {
+            cmd.args(&args_vec);
+        }
+
+        match non_empty(param.cwd) {
+            Some(cwd) => {
+                cmd.current_dir(template.render(&cwd, Escape::Raw));
+            }
+            None => {
+                // 默认使用程序所在目录作为工作目录
+                if let Some(parent) = std::path::Path::new(&program).parent() {
+                    if parent.exists() {
+                        cmd.current_dir(parent);
+                    }
+                }
+            }
+        }
+
+        for (key, value) in param.env.unwrap_or_default() {
+            match value {
+                serde_json::Value::Null => {
+                    cmd.env_remove(key);
+                }
+                serde_json::Value::String(s) => {
+                    cmd.env(key, template.render(&s, Escape::Raw));
+                }
+                other => {
+                    cmd.env(key, other.to_string());
+                }
+            }
+        }
+
+        if param.capture_output {
+            cmd.stdout(std::process::Stdio::piped())
+                .stderr(std::process::Stdio::piped());
+        }
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| format!("Failed to spawn program: {}", e))?;
+        let pid = child.id();
+        info!("[MXU_LAUNCH] Process spawned, pid={}", pid);
+
+        if let Some(stdout) = child.stdout.take() {
+            forward_launch_output(stdout, pid, "stdout");
+        }
+        if let Some(stderr) = child.stderr.take() {
+            forward_launch_output(stderr, pid, "stderr");
+        }
+
+        if wait_for_exit {
+            let tasker = ctx.tasker();
+            let deadline = timeout.map(|t| Instant::now() + t);
+            let status = loop {
+                if let Some(status) = child
+                    .try_wait()
+                    .map_err(|e| format!("Failed to wait for process: {}", e))?
+                {
+                    break status;
+                }
+                if deadline.is_some_and(|d| Instant::now() >= d) {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(format!(
+                        "Process {} timed out after {}s, killed",
+                        pid,
+                        timeout.unwrap_or_default().as_secs()
+                    ));
+                }
+                if !sleep_unless_cancelled(tasker, LAUNCH_POLL_INTERVAL) {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(format!("Stop requested, killed process {}", pid));
+                }
+            };
+
+            let exit_code = status.code().map(i64::from);
+            info!("[MXU_LAUNCH] Process exited with code: {:?}", exit_code);
+            if let Some(codes) = &param.success_exit_codes {
+                if !exit_code.is_some_and(|c| codes.contains(&c)) {
+                    return Err(format!(
+                        "Exit code {:?} is not in success_exit_codes {:?}",
+                        exit_code, codes
+                    ));
+                }
+            }
+        }
+
+        if let Some(readiness) = readiness {
+            readiness.wait(ctx)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,733 @@
+//! MXU 内置 Custom Actions
+//!
+//! 提供 MXU 特有的自定义动作实现，如 MXU_SLEEP 等
+//!
+//! 每个动作实现 [`MxuAction`]：声明动作名、带 `serde` / `schemars` 派生的参数结构体和执行逻辑，
+//! 由通用的 FFI 回调 [`action_trampoline`] 负责空指针检查、参数解析与 panic 捕获。
+//! 新增动作只需在 [`builtin_actions`] 中登记，注册与参数 JSON Schema 导出会自动覆盖。
+//! `plugins/` 目录中的外部插件动作见 [`plugin`] 模块。
+
+mod adb_shell;
+mod emulator;
+mod file;
+mod gate;
+mod killproc;
+mod launch;
+mod notify;
+pub mod plugin;
+mod power;
+mod screenshot;
+mod script;
+mod sleep;
+mod task_log;
+pub mod template;
+mod variable;
+mod wait_condition;
+mod wait_until;
+mod webhook;
+
+use std::collections::HashSet;
+use std::os::raw::{c_char, c_void};
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+
+use crate::maa_ffi::{
+    callback_fns, context_get_tasker, emit_action_countdown, from_cstr, to_cstring, MaaBool,
+    MaaContext, MaaCustomActionCallback, MaaCustomRecognitionCallback, MaaId, MaaImageBuffer,
+    MaaLibrary, MaaRect, MaaResource, MaaStringBuffer, MaaTasker,
+};
+
+// ============================================================================
+// 可取消 / 可暂停等待
+// ============================================================================
+
+/// tasker 的停止与暂停标记（按指针地址区分）
+#[derive(Default)]
+struct TaskerSignals {
+    /// 已请求停止的 tasker
+    cancelled: HashSet<usize>,
+    /// 已暂停的 tasker
+    paused: HashSet<usize>,
+}
+
+impl TaskerSignals {
+    fn is_cancelled(&self, tasker: Option<usize>) -> bool {
+        tasker.is_some_and(|t| self.cancelled.contains(&t))
+    }
+
+    fn is_paused(&self, tasker: Option<usize>) -> bool {
+        tasker.is_some_and(|t| self.paused.contains(&t))
+    }
+}
+
+/// 停止 / 暂停标记，配合条件变量唤醒正在等待的动作
+static TASKER_SIGNALS: Lazy<(Mutex<TaskerSignals>, Condvar)> =
+    Lazy::new(|| (Mutex::new(TaskerSignals::default()), Condvar::new()));
+
+/// 倒计时事件的发送间隔
+const COUNTDOWN_INTERVAL: Duration = Duration::from_secs(1);
+
+fn lock_signals() -> std::sync::MutexGuard<'static, TaskerSignals> {
+    TASKER_SIGNALS.0.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 标记 tasker 已请求停止，并唤醒其正在等待的 MXU 动作（由 maa_stop_task 调用）
+pub fn request_cancel(tasker: *mut MaaTasker) {
+    let mut signals = lock_signals();
+    signals.cancelled.insert(tasker as usize);
+    signals.paused.remove(&(tasker as usize));
+    TASKER_SIGNALS.1.notify_all();
+}
+
+/// 清除 tasker 的停止与暂停标记（提交新任务前调用）
+pub fn clear_cancel(tasker: *mut MaaTasker) {
+    let mut signals = lock_signals();
+    signals.cancelled.remove(&(tasker as usize));
+    signals.paused.remove(&(tasker as usize));
+}
+
+/// 暂停或恢复 tasker：暂停后在下一个节点执行动作前阻塞，MXU 等待类动作停止计时
+/// （由 maa_pause_task / maa_resume_task 调用）
+pub fn set_paused(tasker: *mut MaaTasker, paused: bool) {
+    let mut signals = lock_signals();
+    if paused {
+        signals.paused.insert(tasker as usize);
+    } else {
+        signals.paused.remove(&(tasker as usize));
+    }
+    TASKER_SIGNALS.1.notify_all();
+}
+
+/// 清理任务结束后不再需要的动作状态（由任务结束回调调用）
+pub fn forget_task(task_id: MaaId) {
+    gate::forget_task(task_id);
+}
+
+/// tasker 是否处于暂停状态
+pub fn is_paused(tasker: *mut MaaTasker) -> bool {
+    lock_signals().is_paused(Some(tasker as usize))
+}
+
+/// 暂停期间阻塞，直到恢复或停止；返回 None 表示已停止，否则返回暂停的时长
+fn wait_while_paused(tasker: Option<usize>) -> Option<Duration> {
+    let started = Instant::now();
+    let mut signals = lock_signals();
+    while signals.is_paused(tasker) && !signals.is_cancelled(tasker) {
+        signals = match TASKER_SIGNALS.1.wait(signals) {
+            Ok(guard) => guard,
+            Err(e) => e.into_inner(),
+        };
+    }
+    (!signals.is_cancelled(tasker)).then(|| started.elapsed())
+}
+
+/// 节点动作执行前的暂停点（由 context sink 收到 `Node.Action.Starting` 时调用）
+///
+/// tasker 已暂停时阻塞 MaaFramework 的工作线程，恢复后从同一节点继续执行动作。
+/// 此处在工作线程上运行，不能锁定 MAA_LIBRARY
+pub fn hold_if_paused(context: *mut MaaContext) {
+    // 绝大多数情况下没有暂停的 tasker，无需解析 context
+    if lock_signals().paused.is_empty() {
+        return;
+    }
+    let Some(tasker) = context_tasker(context) else {
+        return;
+    };
+    if !lock_signals().is_paused(Some(tasker)) {
+        return;
+    }
+    info!("Tasker paused, holding before node action");
+    match wait_while_paused(Some(tasker)) {
+        Some(paused) => info!("Tasker resumed after {:?}", paused),
+        None => info!("Tasker stopped while paused"),
+    }
+}
+
+/// 获取 context 所属的 tasker 地址
+fn context_tasker(context: *mut MaaContext) -> Option<usize> {
+    let tasker = context_get_tasker(context);
+    (!tasker.is_null()).then_some(tasker as usize)
+}
+
+/// 可被停止请求打断的等待，期间每秒发送一次倒计时事件
+///
+/// `extend_on_pause` 为 true 时暂停期间停止计时（截止时间顺延），
+/// 否则截止时间固定，恢复后只等待剩余的时间。返回 false 表示等待被取消
+fn cancellable_wait(
+    tag: &str,
+    context: *mut MaaContext,
+    task_id: MaaId,
+    node: &str,
+    total: Duration,
+    extend_on_pause: bool,
+) -> bool {
+    let tasker = context_tasker(context);
+    if tasker.is_none() {
+        warn!(
+            "[{}] Cannot resolve tasker from context, wait cannot be cancelled",
+            tag
+        );
+    }
+
+    let total_secs = total.as_secs();
+    let mut deadline = Instant::now() + total;
+    let mut paused_since: Option<Instant> = None;
+    let cvar = &TASKER_SIGNALS.1;
+    let mut signals = lock_signals();
+
+    loop {
+        if signals.is_cancelled(tasker) {
+            info!("[{}] Wait cancelled by stop request", tag);
+            return false;
+        }
+
+        // 暂停期间不发送倒计时，按需顺延截止时间
+        if signals.is_paused(tasker) {
+            if paused_since.is_none() {
+                info!("[{}] Wait paused", tag);
+                paused_since = Some(Instant::now());
+            }
+            signals = match cvar.wait(signals) {
+                Ok(guard) => guard,
+                Err(e) => e.into_inner(),
+            };
+            continue;
+        }
+        if let Some(since) = paused_since.take() {
+            info!("[{}] Wait resumed", tag);
+            if extend_on_pause {
+                deadline += since.elapsed();
+            }
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            emit_action_countdown(task_id, tag, node, 0, total_secs);
+            return true;
+        }
+
+        let remaining = deadline - now;
+        emit_action_countdown(
+            task_id,
+            tag,
+            node,
+            remaining.as_secs_f64().ceil() as u64,
+            total_secs,
+        );
+
+        signals = match cvar.wait_timeout(signals, remaining.min(COUNTDOWN_INTERVAL)) {
+            Ok((guard, _)) => guard,
+            Err(e) => e.into_inner().0,
+        };
+    }
+}
+
+/// 在不超过 `duration` 的时间内等待，期间收到停止请求则返回 false
+fn sleep_unless_cancelled(tasker: Option<usize>, duration: Duration) -> bool {
+    let signals = lock_signals();
+    if signals.is_cancelled(tasker) {
+        return false;
+    }
+    let signals = match TASKER_SIGNALS.1.wait_timeout(signals, duration) {
+        Ok((guard, _)) => guard,
+        Err(e) => e.into_inner().0,
+    };
+    !signals.is_cancelled(tasker)
+}
+
+/// tasker 是否已请求停止
+fn is_cancelled(tasker: Option<usize>) -> bool {
+    lock_signals().is_cancelled(tasker)
+}
+
+/// 查找 tasker 所属实例的运行时并读取其中的信息
+fn with_tasker_instance<T>(
+    tasker: usize,
+    f: impl FnOnce(&crate::commands::types::InstanceRuntime) -> T,
+) -> Result<T, String> {
+    use tauri::Manager;
+
+    let handle = crate::maa_ffi::get_app_handle().ok_or("App handle not available")?;
+    let state = handle
+        .try_state::<std::sync::Arc<crate::commands::types::MaaState>>()
+        .ok_or("MaaState not available")?;
+    let instances = state.instances.lock().map_err(|e| e.to_string())?;
+    instances
+        .values()
+        .find(|instance| instance.tasker.is_some_and(|t| t as usize == tasker))
+        .map(f)
+        .ok_or_else(|| "Cannot find the instance of current task".to_string())
+}
+
+// ============================================================================
+// 动作框架
+// ============================================================================
+
+/// 动作执行时的上下文（由 FFI 回调参数整理而来）
+pub struct ActionContext {
+    pub context: *mut MaaContext,
+    pub task_id: MaaId,
+    /// 当前节点名
+    pub node: String,
+    /// 识别结果区域，DirectHit 等无区域时为 None
+    pub box_rect: Option<MaaRect>,
+}
+
+impl ActionContext {
+    /// context 所属的 tasker 地址
+    pub fn tasker(&self) -> Option<usize> {
+        context_tasker(self.context)
+    }
+
+    /// 可被停止请求打断的等待，暂停期间停止计时，见 [`cancellable_wait`]
+    pub fn wait(&self, tag: &str, total: Duration) -> bool {
+        cancellable_wait(tag, self.context, self.task_id, &self.node, total, true)
+    }
+
+    /// 等待到固定的截止时刻，暂停不会推迟截止时间；恢复时已过截止时刻则立即返回
+    pub fn wait_until(&self, tag: &str, deadline: Instant) -> bool {
+        let total = deadline.saturating_duration_since(Instant::now());
+        cancellable_wait(tag, self.context, self.task_id, &self.node, total, false)
+    }
+
+    /// 每隔 `interval` 检查一次 `check`，直到返回 true、超时或被停止
+    ///
+    /// 设置了 `timeout` 时每次检查后发送剩余时间的倒计时事件；`check` 出错时立即返回错误
+    pub fn poll_until(
+        &self,
+        tag: &str,
+        timeout: Option<Duration>,
+        interval: Duration,
+        mut check: impl FnMut() -> Result<bool, String>,
+    ) -> Result<PollOutcome, String> {
+        let tasker = self.tasker();
+        let total_secs = timeout.map(|t| t.as_secs()).unwrap_or_default();
+        let mut deadline = timeout.map(|t| Instant::now() + t);
+        loop {
+            // 暂停期间不检测，恢复后顺延截止时间
+            match wait_while_paused(tasker) {
+                Some(paused) => {
+                    if let Some(deadline) = deadline.as_mut() {
+                        *deadline += paused;
+                    }
+                }
+                None => return Ok(PollOutcome::Cancelled),
+            }
+            if check()? {
+                if timeout.is_some() {
+                    emit_action_countdown(self.task_id, tag, &self.node, 0, total_secs);
+                }
+                return Ok(PollOutcome::Satisfied);
+            }
+
+            let mut wait = interval;
+            if let Some(deadline) = deadline {
+                let now = Instant::now();
+                if now >= deadline {
+                    return Ok(PollOutcome::TimedOut);
+                }
+                let remaining = deadline - now;
+                emit_action_countdown(
+                    self.task_id,
+                    tag,
+                    &self.node,
+                    remaining.as_secs_f64().ceil() as u64,
+                    total_secs,
+                );
+                wait = wait.min(remaining);
+            }
+            if !sleep_unless_cancelled(tasker, wait) {
+                return Ok(PollOutcome::Cancelled);
+            }
+        }
+    }
+}
+
+/// [`ActionContext::poll_until`] 的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollOutcome {
+    Satisfied,
+    TimedOut,
+    Cancelled,
+}
+
+/// MXU 内置动作
+///
+/// 返回 `Err` 时节点失败，错误信息由框架统一写入日志
+pub trait MxuAction {
+    /// 注册到 MaaFramework 的动作名，与前端 `custom_action` 字段一致
+    const NAME: &'static str;
+    /// 日志前缀，如 `MXU_SLEEP`
+    const TAG: &'static str;
+    /// 由 `custom_action_param` 反序列化的参数
+    type Param: DeserializeOwned + JsonSchema;
+
+    fn run(ctx: &ActionContext, param: Self::Param) -> Result<(), String>;
+
+    /// 参数无法解析时改用的参数，返回 None（默认）时节点失败
+    ///
+    /// 仅用于保持旧版动作对错误参数的宽松处理（如 MXU_SLEEP 回退到 5 秒）
+    fn fallback_param() -> Option<Self::Param> {
+        None
+    }
+}
+
+/// MXU 内置识别
+///
+/// 返回 `Ok(Some((box, detail)))` 表示命中，`Ok(None)` 表示未命中
+pub trait MxuRecognition {
+    /// 注册到 MaaFramework 的识别名，与 pipeline 中 `custom_recognition` 字段一致
+    const NAME: &'static str;
+    /// 日志前缀
+    const TAG: &'static str;
+    /// 由 `custom_recognition_param` 反序列化的参数
+    type Param: DeserializeOwned + JsonSchema;
+
+    fn recognize(
+        ctx: &ActionContext,
+        roi: MaaRect,
+        param: Self::Param,
+    ) -> Result<Option<(MaaRect, serde_json::Value)>, String>;
+}
+
+/// 解析参数 JSON，空字符串和 null 视为空对象
+fn parse_param<P: DeserializeOwned>(param_str: &str) -> Result<P, String> {
+    let value = if param_str.trim().is_empty() {
+        serde_json::Value::Null
+    } else {
+        serde_json::from_str(param_str).map_err(|e| format!("Invalid param JSON: {}", e))?
+    };
+    let value = if value.is_null() {
+        serde_json::Value::Object(Default::default())
+    } else {
+        value
+    };
+    serde_json::from_value(value).map_err(|e| format!("Invalid param: {}", e))
+}
+
+/// 解析动作参数，失败时使用动作的回退参数，没有回退参数时返回 None
+fn parse_action_param<A: MxuAction>(param_str: &str) -> Option<A::Param> {
+    match parse_param::<A::Param>(param_str) {
+        Ok(param) => Some(param),
+        Err(e) => match A::fallback_param() {
+            Some(param) => {
+                warn!("[{}] {}, using default param", A::TAG, e);
+                Some(param)
+            }
+            None => {
+                warn!("[{}] {}", A::TAG, e);
+                None
+            }
+        },
+    }
+}
+
+/// 所有 MXU 动作共用的 custom action 回调
+extern "C" fn action_trampoline<A: MxuAction>(
+    context: *mut MaaContext,
+    task_id: MaaId,
+    current_task_name: *const c_char,
+    _custom_action_name: *const c_char,
+    custom_action_param: *const c_char,
+    _reco_id: MaaId,
+    box_rect: *const MaaRect,
+    _trans_arg: *mut c_void,
+) -> MaaBool {
+    // 使用 catch_unwind 捕获潜在的 panic，防止其跨越 FFI 边界
+    let result = std::panic::catch_unwind(|| {
+        let param_str = if custom_action_param.is_null() {
+            String::new()
+        } else {
+            unsafe { from_cstr(custom_action_param) }
+        };
+
+        info!("[{}] Received param: {}", A::TAG, param_str);
+
+        let Some(param) = parse_action_param::<A>(&param_str) else {
+            return 0u8;
+        };
+
+        let ctx = ActionContext {
+            context,
+            task_id,
+            node: unsafe { from_cstr(current_task_name) },
+            box_rect: (!box_rect.is_null()).then(|| unsafe { *box_rect }),
+        };
+
+        match A::run(&ctx, param) {
+            Ok(()) => 1u8,
+            Err(e) => {
+                log::error!("[{}] {}", A::TAG, e);
+                0u8
+            }
+        }
+    });
+
+    match result {
+        Ok(ret) => ret,
+        Err(e) => {
+            log::error!("[{}] Panic caught: {:?}", A::TAG, e);
+            0
+        }
+    }
+}
+
+/// 所有 MXU 识别共用的 custom recognition 回调
+extern "C" fn recognition_trampoline<R: MxuRecognition>(
+    context: *mut MaaContext,
+    task_id: MaaId,
+    node_name: *const c_char,
+    _custom_recognition_name: *const c_char,
+    custom_recognition_param: *const c_char,
+    _image: *const MaaImageBuffer,
+    roi: *const MaaRect,
+    _trans_arg: *mut c_void,
+    out_box: *mut MaaRect,
+    out_detail: *mut MaaStringBuffer,
+) -> MaaBool {
+    let result = std::panic::catch_unwind(|| {
+        let param_str = if custom_recognition_param.is_null() {
+            String::new()
+        } else {
+            unsafe { from_cstr(custom_recognition_param) }
+        };
+
+        let param = match parse_param::<R::Param>(&param_str) {
+            Ok(param) => param,
+            Err(e) => {
+                warn!("[{}] {}", R::TAG, e);
+                return 0u8;
+            }
+        };
+
+        let roi = if roi.is_null() {
+            MaaRect {
+                x: 0,
+                y: 0,
+                w: 0,
+                h: 0,
+            }
+        } else {
+            unsafe { *roi }
+        };
+        let ctx = ActionContext {
+            context,
+            task_id,
+            node: unsafe { from_cstr(node_name) },
+            box_rect: Some(roi),
+        };
+
+        match R::recognize(&ctx, roi, param) {
+            Ok(Some((hit_box, detail))) => {
+                if !out_box.is_null() {
+                    unsafe {
+                        *out_box = hit_box;
+                    }
+                }
+                if !out_detail.is_null() {
+                    // 在工作线程上运行，使用缓存的函数指针而不锁定 MAA_LIBRARY
+                    if let Ok(fns) = callback_fns() {
+                        let detail = to_cstring(&detail.to_string());
+                        unsafe {
+                            (fns.string_buffer_set)(out_detail, detail.as_ptr());
+                        }
+                    }
+                }
+                1u8
+            }
+            Ok(None) => 0u8,
+            Err(e) => {
+                log::error!("[{}] {}", R::TAG, e);
+                0u8
+            }
+        }
+    });
+
+    match result {
+        Ok(ret) => ret,
+        Err(e) => {
+            log::error!("[{}] Panic caught in recognition: {:?}", R::TAG, e);
+            0
+        }
+    }
+}
+
+/// 将可选字符串参数中的空白值视为未设置
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+// ============================================================================
+// 注册表
+// ============================================================================
+
+/// 注册表中的一项：名称、FFI 回调与参数 Schema
+pub struct RegistryEntry<C> {
+    pub name: &'static str,
+    pub callback: C,
+    pub param_schema: fn() -> schemars::Schema,
+}
+
+fn param_schema<P: JsonSchema>() -> schemars::Schema {
+    schemars::schema_for!(P)
+}
+
+fn action_entry<A: MxuAction>() -> RegistryEntry<MaaCustomActionCallback> {
+    RegistryEntry {
+        name: A::NAME,
+        callback: Some(action_trampoline::<A>),
+        param_schema: param_schema::<A::Param>,
+    }
+}
+
+fn recognition_entry<R: MxuRecognition>() -> RegistryEntry<MaaCustomRecognitionCallback> {
+    RegistryEntry {
+        name: R::NAME,
+        callback: Some(recognition_trampoline::<R>),
+        param_schema: param_schema::<R::Param>,
+    }
+}
+
+/// 所有 MXU 内置 custom actions
+pub fn builtin_actions() -> Vec<RegistryEntry<MaaCustomActionCallback>> {
+    vec![
+        action_entry::<sleep::SleepAction>(),
+        action_entry::<wait_until::WaitUntilAction>(),
+        action_entry::<launch::LaunchAction>(),
+        action_entry::<webhook::WebhookAction>(),
+        action_entry::<notify::NotifyAction>(),
+        action_entry::<killproc::KillProcAction>(),
+        action_entry::<power::PowerAction>(),
+        action_entry::<screenshot::ScreenshotAction>(),
+        action_entry::<adb_shell::AdbShellAction>(),
+        action_entry::<variable::VariableAction>(),
+        action_entry::<script::ScriptAction>(),
+        action_entry::<emulator::EmulatorAction>(),
+        action_entry::<wait_condition::WaitProcessAction>(),
+        action_entry::<wait_condition::WaitPortAction>(),
+        action_entry::<file::FileAction>(),
+        action_entry::<task_log::LogAction>(),
+        action_entry::<gate::GateAction>(),
+    ]
+}
+
+/// 所有 MXU 内置 custom recognitions
+pub fn builtin_recognitions() -> Vec<RegistryEntry<MaaCustomRecognitionCallback>> {
+    vec![recognition_entry::<variable::VariableRecognition>()]
+}
+
+/// 为资源注册所有 MXU 内置 custom actions（及 custom recognitions）与插件动作
+/// 在资源创建后调用此函数
+pub fn register_all_mxu_actions(
+    lib: &MaaLibrary,
+    resource: *mut MaaResource,
+) -> Result<(), String> {
+    for entry in builtin_actions() {
+        let name = to_cstring(entry.name);
+        let result = unsafe {
+            (lib.maa_resource_register_custom_action)(
+                resource,
+                name.as_ptr(),
+                entry.callback,
+                std::ptr::null_mut(),
+            )
+        };
+        if result != 0 {
+            info!("[MXU] Custom action {} registered successfully", entry.name);
+        } else {
+            warn!("[MXU] Failed to register custom action {}", entry.name);
+        }
+    }
+
+    if plugin::plugins_loading() {
+        warn!(
+            "[MXU] Plugins are still loading, plugin actions are not registered for this resource"
+        );
+    }
+    for action in plugin::plugin_actions() {
+        let name = to_cstring(&action.name);
+        let result = unsafe {
+            (lib.maa_resource_register_custom_action)(
+                resource,
+                name.as_ptr(),
+                Some(plugin::plugin_action_callback),
+                action as *const plugin::PluginAction as *mut c_void,
+            )
+        };
+        if result != 0 {
+            info!(
+                "[MXU] Plugin action {} ({}) registered successfully",
+                action.name,
+                action.plugin_name()
+            );
+        } else {
+            warn!("[MXU] Failed to register plugin action {}", action.name);
+        }
+    }
+
+    for entry in builtin_recognitions() {
+        let name = to_cstring(entry.name);
+        let result = unsafe {
+            (lib.maa_resource_register_custom_recognition)(
+                resource,
+                name.as_ptr(),
+                entry.callback,
+                std::ptr::null_mut(),
+            )
+        };
+        if result != 0 {
+            info!(
+                "[MXU] Custom recognition {} registered successfully",
+                entry.name
+            );
+        } else {
+            warn!("[MXU] Failed to register custom recognition {}", entry.name);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, serde::Deserialize, PartialEq)]
+    struct Param {
+        #[serde(default)]
+        count: u32,
+    }
+
+    #[test]
+    fn parse_param_treats_empty_and_null_as_empty_object() {
+        assert_eq!(parse_param::<Param>(""), Ok(Param { count: 0 }));
+        assert_eq!(parse_param::<Param>("  null "), Ok(Param { count: 0 }));
+        assert_eq!(
+            parse_param::<Param>(r#"{"count":3}"#),
+            Ok(Param { count: 3 })
+        );
+    }
+
+    #[test]
+    fn parse_param_rejects_malformed_json_and_wrong_types() {
+        assert!(parse_param::<Param>("{count:3")
+            .unwrap_err()
+            .starts_with("Invalid param JSON"));
+        assert!(parse_param::<Param>(r#"{"count":"3"}"#)
+            .unwrap_err()
+            .starts_with("Invalid param"));
+    }
+
+    #[test]
+    fn actions_without_fallback_fail_on_bad_param() {
+        assert!(parse_action_param::<killproc::KillProcAction>("{dry_run:true").is_none());
+        assert!(parse_action_param::<killproc::KillProcAction>(r#"{"dry_run":"yes"}"#).is_none());
+    }
+}
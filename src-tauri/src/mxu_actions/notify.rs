@@ -0,0 +1,95 @@
+//! MXU_NOTIFY：发送系统通知或通过已配置的通知渠道发送
+
+use log::info;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::template::{Escape, Template};
+use super::{ActionContext, MxuAction};
+
+/// 单个渠道或渠道列表
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum ChannelSelector {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct NotifyParam {
+    /// 通知标题，默认 `MXU`，可使用占位符（见 [`super::template`]）
+    #[serde(default = "default_title")]
+    title: String,
+    /// 通知内容，可使用占位符
+    #[serde(default)]
+    body: String,
+    /// 通知渠道（渠道 ID、名称、数组或 `all`），缺省时发送系统通知
+    #[serde(default)]
+    channel: Option<ChannelSelector>,
+}
+
+fn default_title() -> String {
+    "MXU".to_string()
+}
+
+/// 发送通知：`channels` 非空时通过外部通知渠道发送，否则发送系统通知
+pub(super) fn send_notification(
+    title: &str,
+    body: &str,
+    channels: &[String],
+) -> Result<(), String> {
+    info!(
+        "[MXU_NOTIFY] Sending notification: title={}, body={}",
+        title, body
+    );
+
+    let channels: Vec<String> = channels
+        .iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    if !channels.is_empty() {
+        let count = crate::commands::notification::send_to_channels(&channels, title, body)
+            .map_err(|e| format!("Failed to send notification: {}", e))?;
+        info!("[MXU_NOTIFY] Notification sent via {} channel(s)", count);
+        return Ok(());
+    }
+
+    notify_rust::Notification::new()
+        .summary(title)
+        .body(body)
+        .show()
+        .map_err(|e| format!("Failed to send notification: {}", e))?;
+    info!("[MXU_NOTIFY] Notification sent successfully");
+    Ok(())
+}
+
+/// 指定 channel 时通过外部通知渠道发送，否则发送系统通知
+pub struct NotifyAction;
+
+impl MxuAction for NotifyAction {
+    const NAME: &'static str = "MXU_NOTIFY_ACTION";
+    const TAG: &'static str = "MXU_NOTIFY";
+    type Param = NotifyParam;
+
+    fn run(ctx: &ActionContext, param: NotifyParam) -> Result<(), String> {
+        let NotifyParam {
+            title,
+            body,
+            channel,
+        } = param;
+
+        let channels: Vec<String> = match channel {
+            Some(ChannelSelector::One(c)) => vec![c],
+            Some(ChannelSelector::Many(items)) => items,
+            None => Vec::new(),
+        };
+        let template = Template::new(ctx);
+        send_notification(
+            &template.render(&title, Escape::Raw),
+            &template.render(&body, Escape::Raw),
+            &channels,
+        )
+    }
+}
@@ -0,0 +1,883 @@
+//! 外部插件动作
+//!
+//! 从 exe 目录和数据目录下的 `plugins/` 加载插件，每个插件是一个包含 `plugin.json` 的子目录：
+//!
+//! ```json
+//! { "name": "example", "type": "library", "path": "example" }
+//! { "name": "example", "type": "executable", "path": "example", "args": [], "timeout": 60 }
+//! ```
+//!
+//! `path` 相对插件目录，省略扩展名时按平台补全（`.dll` / `.so` / `.dylib` / `.exe`）。
+//! 插件自行声明动作名与参数 Schema（清单格式见 [`PluginManifest`]），
+//! MXU 在创建资源时与内置动作一同注册，与内置动作或先加载的插件重名的动作会被跳过。
+//!
+//! - 动态库：导出 `const char* mxu_plugin_manifest(void)` 返回清单 JSON，
+//!   以及 `int32_t mxu_plugin_run(const char* request)`，返回非 0 表示成功。需自行保证线程安全。
+//! - 可执行文件：每次调用启动一个进程，向 stdin 写入一行请求 JSON 后关闭 stdin，
+//!   stdout 最后一行非空内容为响应 JSON（`{"success": bool, "message": "..."}`），其余输出写入日志；
+//!   没有响应 JSON 时按退出码判断。清单通过 `{"type": "manifest"}` 请求获取。
+//!
+//! 执行请求为 `{"type": "run", "action", "param", "context": {task_id, node, instance, task, box}}`。
+//!
+//! 插件会在 MXU 进程内运行原生代码，因此需要在设置中逐个允许后才会加载。MXU 启动后首次同步该设置时
+//! 在后台线程加载一次（不持有任何锁），增删或允许插件需重启 MXU；加载完成前创建的资源不包含插件动作。
+
+use std::ffi::CStr;
+use std::io::Write;
+use std::os::raw::{c_char, c_void};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use libloading::Library;
+use log::{info, warn};
+use once_cell::sync::{Lazy, OnceCell};
+use serde::Deserialize;
+use serde_json::json;
+
+use super::launch::LAUNCH_POLL_INTERVAL;
+use super::{context_tasker, parse_param, sleep_unless_cancelled};
+use crate::commands::types::PluginInfo;
+use crate::maa_ffi::{from_cstr, to_cstring, MaaBool, MaaContext, MaaId, MaaRect};
+use crate::process_util::read_to_end_in_background;
+
+/// 插件配置文件名
+const PLUGIN_CONFIG_FILE: &str = "plugin.json";
+/// 支持的插件协议版本
+const PLUGIN_ABI_VERSION: u32 = 1;
+/// 获取可执行文件插件清单的超时
+const PLUGIN_MANIFEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `plugin.json`
+#[derive(Deserialize)]
+struct PluginConfig {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(rename = "type")]
+    kind: PluginKind,
+    path: String,
+    /// 可执行文件的额外启动参数
+    #[serde(default)]
+    args: Vec<String>,
+    /// 可执行文件单次执行的超时（秒），缺省表示不限
+    #[serde(default)]
+    timeout: Option<u64>,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum PluginKind {
+    Library,
+    Executable,
+}
+
+/// 插件清单：`{"abi": 1, "actions": [{"name", "description", "schema"}]}`
+#[derive(Deserialize, Debug)]
+struct PluginManifest {
+    /// 插件遵循的协议版本，缺省视为 1
+    #[serde(default)]
+    abi: Option<u32>,
+    actions: Vec<PluginActionDecl>,
+}
+
+#[derive(Deserialize, Debug)]
+struct PluginActionDecl {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    /// 参数的 JSON Schema
+    #[serde(default)]
+    schema: serde_json::Value,
+}
+
+/// 可执行文件插件的响应
+#[derive(Deserialize)]
+struct PluginResponse {
+    success: bool,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+type FnPluginManifest = unsafe extern "C" fn() -> *const c_char;
+type FnPluginRun = unsafe extern "C" fn(*const c_char) -> i32;
+
+enum PluginBackend {
+    Library {
+        run: FnPluginRun,
+        /// 保持动态库加载，run 指针才有效
+        _library: Library,
+    },
+    Executable {
+        program: PathBuf,
+        args: Vec<String>,
+        timeout: Option<Duration>,
+    },
+}
+
+struct Plugin {
+    name: String,
+    dir: PathBuf,
+    backend: PluginBackend,
+}
+
+/// 插件提供的一个动作
+pub struct PluginAction {
+    plugin: Arc<Plugin>,
+    pub name: String,
+    pub description: Option<String>,
+    pub schema: serde_json::Value,
+}
+
+/// 一次加载的结果
+struct LoadedPlugins {
+    /// 元素地址在程序运行期间保持不变，作为 trans_arg 传给回调
+    actions: Vec<PluginAction>,
+    /// 尝试加载的插件目录 -> 注册的动作名或错误
+    results: Vec<(PathBuf, Result<Vec<String>, String>)>,
+}
+
+/// 用户允许加载的插件目录（由前端在加载配置和修改设置时同步）
+static ALLOWED_PLUGINS: Lazy<Mutex<Vec<String>>> = Lazy::new(|| Mutex::new(Vec::new()));
+/// 是否已开始加载插件
+static LOAD_STARTED: AtomicBool = AtomicBool::new(false);
+/// 后台加载完成后的插件
+static LOADED_PLUGINS: OnceCell<LoadedPlugins> = OnceCell::new();
+
+/// 所有已加载的插件动作，加载完成前为空
+pub fn plugin_actions() -> &'static [PluginAction] {
+    LOADED_PLUGINS
+        .get()
+        .map(|loaded| loaded.actions.as_slice())
+        .unwrap_or_default()
+}
+
+/// 插件是否正在后台加载
+pub fn plugins_loading() -> bool {
+    LOAD_STARTED.load(Ordering::SeqCst) && LOADED_PLUGINS.get().is_none()
+}
+
+/// 设置允许加载的插件目录；首次调用时在后台线程加载允许的插件，之后的修改在重启后生效
+pub fn set_allowed_plugins(dirs: Vec<String>) {
+    *ALLOWED_PLUGINS.lock().unwrap_or_else(|e| e.into_inner()) = dirs.clone();
+    if LOAD_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    std::thread::spawn(move || {
+        let loaded = load_plugin_actions(&plugin_dirs(), &dirs);
+        info!(
+            "[MXU_PLUGIN] Plugin loading finished: {} action(s) from {} plugin(s)",
+            loaded.actions.len(),
+            loaded.results.iter().filter(|(_, r)| r.is_ok()).count()
+        );
+        let _ = LOADED_PLUGINS.set(loaded);
+    });
+}
+
+/// 插件目录中的所有插件及其加载状态
+pub fn list_plugins() -> Vec<PluginInfo> {
+    let allowed = ALLOWED_PLUGINS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    let loaded = LOADED_PLUGINS.get();
+    let loading = plugins_loading();
+
+    plugin_dirs()
+        .iter()
+        .flat_map(|root| find_plugin_dirs(root))
+        .map(|dir| {
+            let dir_str = dir.to_string_lossy().to_string();
+            let is_allowed = allowed.contains(&dir_str);
+            let config = read_config(&dir);
+            let result = loaded.and_then(|l| l.results.iter().find(|(d, _)| *d == dir));
+
+            let (status, actions, error) = match result {
+                Some((_, Ok(actions))) => ("loaded", actions.clone(), None),
+                Some((_, Err(e))) => ("failed", Vec::new(), Some(e.clone())),
+                None if loading && is_allowed => ("loading", Vec::new(), None),
+                None => ("not_loaded", Vec::new(), config.as_ref().err().cloned()),
+            };
+            // 允许列表与本次启动加载的插件不一致时需重启生效
+            let restart_required = loaded.is_some()
+                && match result {
+                    Some((_, Ok(_))) => !is_allowed,
+                    Some((_, Err(_))) => false,
+                    None => is_allowed,
+                };
+
+            PluginInfo {
+                name: config
+                    .as_ref()
+                    .ok()
+                    .and_then(|c| c.name.clone())
+                    .filter(|n| !n.trim().is_empty())
+                    .unwrap_or_else(|| dir_name(&dir)),
+                kind: config.ok().map(|c| match c.kind {
+                    PluginKind::Library => "library".to_string(),
+                    PluginKind::Executable => "executable".to_string(),
+                }),
+                dir: dir_str,
+                allowed: is_allowed,
+                status: status.to_string(),
+                actions,
+                error,
+                restart_required,
+            }
+        })
+        .collect()
+}
+
+/// 插件目录：exe 目录与数据目录下的 plugins（两者相同时只扫描一次）
+fn plugin_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    for base in [
+        crate::commands::utils::get_exe_directory(),
+        crate::commands::utils::get_app_data_dir(),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        let dir = base.join("plugins");
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// 解析插件入口路径，省略扩展名时按平台补全
+fn resolve_entry_path(dir: &Path, path: &str, kind: PluginKind) -> Result<PathBuf, String> {
+    let path = dir.join(path);
+    if path.is_file() {
+        return Ok(path);
+    }
+    if path.extension().is_none() {
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let candidates = match kind {
+            PluginKind::Library => vec![
+                format!("{}{}", file_name, std::env::consts::DLL_SUFFIX),
+                format!(
+                    "{}{}{}",
+                    std::env::consts::DLL_PREFIX,
+                    file_name,
+                    std::env::consts::DLL_SUFFIX
+                ),
+            ],
+            PluginKind::Executable => {
+                vec![format!("{}{}", file_name, std::env::consts::EXE_SUFFIX)]
+            }
+        };
+        if let Some(found) = candidates
+            .into_iter()
+            .map(|name| path.with_file_name(name))
+            .find(|p| p.is_file())
+        {
+            return Ok(found);
+        }
+    }
+    Err(format!("Plugin entry not found: {}", path.display()))
+}
+
+/// 插件目录下包含 `plugin.json` 的子目录（按名称排序）
+fn find_plugin_dirs(root: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.join(PLUGIN_CONFIG_FILE).is_file())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn dir_name(dir: &Path) -> String {
+    dir.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn read_config(dir: &Path) -> Result<PluginConfig, String> {
+    let config_path = dir.join(PLUGIN_CONFIG_FILE);
+    let content = std::fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read {}: {}", config_path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Invalid {}: {}", config_path.display(), e))
+}
+
+/// 扫描插件目录并加载允许的插件动作
+fn load_plugin_actions(roots: &[PathBuf], allowed: &[String]) -> LoadedPlugins {
+    let builtin: Vec<&str> = super::builtin_actions().iter().map(|e| e.name).collect();
+    let mut actions: Vec<PluginAction> = Vec::new();
+    let mut results = Vec::new();
+
+    for dir in roots.iter().flat_map(|root| find_plugin_dirs(root)) {
+        if !allowed.contains(&dir.to_string_lossy().to_string()) {
+            info!("[MXU_PLUGIN] Plugin {:?} is not enabled, skipped", dir);
+            continue;
+        }
+        let (plugin, manifest) = match load_plugin(&dir) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                warn!("[MXU_PLUGIN] Failed to load plugin {:?}: {}", dir, e);
+                results.push((dir, Err(e)));
+                continue;
+            }
+        };
+        let plugin = Arc::new(plugin);
+        info!(
+            "[MXU_PLUGIN] Loaded plugin '{}' from {:?} ({} action(s))",
+            plugin.name,
+            plugin.dir,
+            manifest.actions.len()
+        );
+        let mut registered = Vec::new();
+        for decl in manifest.actions {
+            let name = decl.name.trim().to_string();
+            if name.is_empty() {
+                warn!(
+                    "[MXU_PLUGIN] Plugin '{}' declares an action without name",
+                    plugin.name
+                );
+                continue;
+            }
+            if builtin.contains(&name.as_str()) || actions.iter().any(|a| a.name == name) {
+                warn!(
+                    "[MXU_PLUGIN] Action {} from plugin '{}' conflicts with an existing action, skipped",
+                    name, plugin.name
+                );
+                continue;
+            }
+            registered.push(name.clone());
+            actions.push(PluginAction {
+                plugin: plugin.clone(),
+                name,
+                description: decl.description,
+                schema: decl.schema,
+            });
+        }
+        results.push((dir, Ok(registered)));
+    }
+    LoadedPlugins { actions, results }
+}
+
+/// 加载单个插件并获取其清单
+fn load_plugin(dir: &Path) -> Result<(Plugin, PluginManifest), String> {
+    let config = read_config(dir)?;
+    let entry = resolve_entry_path(dir, &config.path, config.kind)?;
+    let name = config
+        .name
+        .filter(|n| !n.trim().is_empty())
+        .unwrap_or_else(|| dir_name(dir));
+
+    let (backend, manifest_json) = match config.kind {
+        PluginKind::Library => {
+            let library = unsafe { Library::new(&entry) }
+                .map_err(|e| format!("Failed to load {}: {}", entry.display(), e))?;
+            let (manifest, run) = unsafe {
+                let manifest = *library
+                    .get::<FnPluginManifest>(b"mxu_plugin_manifest\0")
+                    .map_err(|e| format!("Missing mxu_plugin_manifest: {}", e))?;
+                let run = *library
+                    .get::<FnPluginRun>(b"mxu_plugin_run\0")
+                    .map_err(|e| format!("Missing mxu_plugin_run: {}", e))?;
+                (manifest, run)
+            };
+            let manifest_ptr = unsafe { manifest() };
+            if manifest_ptr.is_null() {
+                return Err("mxu_plugin_manifest returned null".to_string());
+            }
+            let manifest_json = unsafe { CStr::from_ptr(manifest_ptr) }
+                .to_string_lossy()
+                .into_owned();
+            let backend = PluginBackend::Library {
+                run,
+                _library: library,
+            };
+            (backend, manifest_json)
+        }
+        PluginKind::Executable => {
+            let backend = PluginBackend::Executable {
+                program: entry,
+                args: config.args,
+                timeout: config.timeout.filter(|t| *t > 0).map(Duration::from_secs),
+            };
+            let plugin = Plugin {
+                name: name.clone(),
+                dir: dir.to_path_buf(),
+                backend,
+            };
+            let output = run_executable(
+                &plugin,
+                &json!({ "type": "manifest" }),
+                None,
+                Some(PLUGIN_MANIFEST_TIMEOUT),
+            )?;
+            let manifest_json = last_line(&output.stdout)
+                .ok_or("Plugin returned no manifest")?
+                .to_string();
+            let manifest = parse_manifest(&manifest_json)?;
+            return Ok((plugin, manifest));
+        }
+    };
+
+    let manifest = parse_manifest(&manifest_json)?;
+    Ok((
+        Plugin {
+            name,
+            dir: dir.to_path_buf(),
+            backend,
+        },
+        manifest,
+    ))
+}
+
+fn parse_manifest(content: &str) -> Result<PluginManifest, String> {
+    let manifest: PluginManifest =
+        serde_json::from_str(content).map_err(|e| format!("Invalid manifest: {}", e))?;
+    match manifest.abi {
+        Some(abi) if abi > PLUGIN_ABI_VERSION => Err(format!(
+            "Plugin requires protocol version {}, MXU supports {}",
+            abi, PLUGIN_ABI_VERSION
+        )),
+        _ => Ok(manifest),
+    }
+}
+
+/// 输出中最后一行非空内容
+fn last_line(output: &str) -> Option<&str> {
+    output.lines().map(str::trim).rfind(|l| !l.is_empty())
+}
+
+struct ExecutableOutput {
+    status: std::process::ExitStatus,
+    stdout: String,
+}
+
+/// 启动可执行文件插件，写入请求并等待其退出
+///
+/// `tasker` 有值时可被停止任务打断，超时或被打断时结束进程并返回错误
+fn run_executable(
+    plugin: &Plugin,
+    request: &serde_json::Value,
+    tasker: Option<usize>,
+    timeout: Option<Duration>,
+) -> Result<ExecutableOutput, String> {
+    let PluginBackend::Executable { program, args, .. } = &plugin.backend else {
+        return Err("Plugin is not an executable".to_string());
+    };
+
+    let mut cmd = std::process::Command::new(program);
+    cmd.args(args)
+        .current_dir(&plugin.dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("Failed to start {}: {}", program.display(), e))?;
+    let stdout = child.stdout.take().map(read_to_end_in_background);
+    let stderr = child.stderr.take().map(read_to_end_in_background);
+    if let Some(mut stdin) = child.stdin.take() {
+        // 写入失败（如插件不读取 stdin 直接退出）时仍按输出判断结果
+        if let Err(e) = writeln!(stdin, "{}", request) {
+            log::debug!("[MXU_PLUGIN] Failed to write request: {}", e);
+        }
+    }
+
+    let deadline = timeout.map(|t| Instant::now() + t);
+    let status = loop {
+        if let Some(status) = child
+            .try_wait()
+            .map_err(|e| format!("Failed to wait for plugin: {}", e))?
+        {
+            break status;
+        }
+        if deadline.is_some_and(|d| Instant::now() >= d) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!(
+                "Plugin timed out after {}s",
+                timeout.unwrap_or_default().as_secs()
+            ));
+        }
+        if !sleep_unless_cancelled(tasker, LAUNCH_POLL_INTERVAL) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err("Stop requested, killed plugin process".to_string());
+        }
+    };
+
+    let stdout = stdout.and_then(|h| h.join().ok()).unwrap_or_default();
+    let stderr = stderr.and_then(|h| h.join().ok()).unwrap_or_default();
+    for line in stderr.lines().filter(|l| !l.trim().is_empty()) {
+        info!("[MXU_PLUGIN] [{}] stderr: {}", plugin.name, line.trim_end());
+    }
+    Ok(ExecutableOutput { status, stdout })
+}
+
+impl PluginAction {
+    fn run(
+        &self,
+        context: *mut MaaContext,
+        param: serde_json::Value,
+        run_context: serde_json::Value,
+    ) -> Result<(), String> {
+        let request = json!({
+            "type": "run",
+            "action": self.name,
+            "param": param,
+            "context": run_context,
+        });
+
+        match &self.plugin.backend {
+            PluginBackend::Library { run, .. } => {
+                let request = to_cstring(&request.to_string());
+                if unsafe { run(request.as_ptr()) } != 0 {
+                    Ok(())
+                } else {
+                    Err("Plugin reported failure".to_string())
+                }
+            }
+            PluginBackend::Executable { timeout, .. } => {
+                let output =
+                    run_executable(&self.plugin, &request, context_tasker(context), *timeout)?;
+                let response = last_line(&output.stdout);
+                for line in output
+                    .stdout
+                    .lines()
+                    .map(str::trim_end)
+                    .filter(|l| !l.trim().is_empty() && Some(l.trim()) != response)
+                {
+                    info!("[MXU_PLUGIN] [{}] {}", self.plugin.name, line);
+                }
+                match response.and_then(|r| serde_json::from_str::<PluginResponse>(r).ok()) {
+                    Some(PluginResponse { success, message }) => {
+                        if let Some(message) = &message {
+                            info!("[MXU_PLUGIN] [{}] {}", self.plugin.name, message);
+                        }
+                        if success {
+                            Ok(())
+                        } else {
+                            Err(message.unwrap_or_else(|| "Plugin reported failure".to_string()))
+                        }
+                    }
+                    None if output.status.success() => Ok(()),
+                    None => Err(format!("Plugin exited with {}", output.status)),
+                }
+            }
+        }
+    }
+
+    /// 插件名称
+    pub fn plugin_name(&self) -> &str {
+        &self.plugin.name
+    }
+}
+
+/// 插件动作的 custom action 回调，trans_arg 指向 [`PluginAction`]
+pub(super) extern "C" fn plugin_action_callback(
+    context: *mut MaaContext,
+    task_id: MaaId,
+    current_task_name: *const c_char,
+    _custom_action_name: *const c_char,
+    custom_action_param: *const c_char,
+    _reco_id: MaaId,
+    box_rect: *const MaaRect,
+    trans_arg: *mut c_void,
+) -> MaaBool {
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        if trans_arg.is_null() {
+            log::error!("[MXU_PLUGIN] trans_arg is null");
+            return 0u8;
+        }
+        let action = unsafe { &*(trans_arg as *const PluginAction) };
+
+        let param_str = if custom_action_param.is_null() {
+            String::new()
+        } else {
+            unsafe { from_cstr(custom_action_param) }
+        };
+        info!(
+            "[MXU_PLUGIN] [{}] Received param: {}",
+            action.name, param_str
+        );
+
+        let param = match parse_param::<serde_json::Value>(&param_str) {
+            Ok(param) => param,
+            Err(e) => {
+                warn!("[MXU_PLUGIN] [{}] {}", action.name, e);
+                return 0u8;
+            }
+        };
+
+        let run = crate::run_context::task_context(task_id);
+        let rect = (!box_rect.is_null()).then(|| unsafe { *box_rect });
+        let run_context = json!({
+            "task_id": task_id,
+            "node": unsafe { from_cstr(current_task_name) },
+            "instance": run.instance_id,
+            "task": run.entry,
+            "box": rect.map(|r| [r.x, r.y, r.w, r.h]),
+        });
+
+        match action.run(context, param, run_context) {
+            Ok(()) => 1u8,
+            Err(e) => {
+                log::error!("[MXU_PLUGIN] [{}] {}", action.name, e);
+                0u8
+            }
+        }
+    }));
+
+    match result {
+        Ok(ret) => ret,
+        Err(e) => {
+            log::error!("[MXU_PLUGIN] Panic caught: {:?}", e);
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_manifest_accepts_current_abi() {
+        let manifest = parse_manifest(
+            r#"{"abi": 1, "actions": [{"name": "Foo", "description": "d", "schema": {"type": "object"}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.actions.len(), 1);
+        assert_eq!(manifest.actions[0].name, "Foo");
+        assert_eq!(manifest.actions[0].description.as_deref(), Some("d"));
+        assert_eq!(manifest.actions[0].schema, json!({"type": "object"}));
+
+        let manifest = parse_manifest(r#"{"actions": [{"name": "Bar"}]}"#).unwrap();
+        assert_eq!(manifest.abi, None);
+        assert!(manifest.actions[0].schema.is_null());
+    }
+
+    #[test]
+    fn parse_manifest_rejects_newer_abi_and_invalid_json() {
+        let err = parse_manifest(r#"{"abi": 2, "actions": []}"#).unwrap_err();
+        assert!(err.contains("protocol version 2"), "{}", err);
+        assert!(parse_manifest(r#"{"abi": 1}"#).is_err());
+        assert!(parse_manifest("not json").is_err());
+    }
+
+    #[test]
+    fn parse_plugin_config() {
+        let config: PluginConfig = serde_json::from_str(
+            r#"{"type": "executable", "path": "bin/tool", "args": ["-v"], "timeout": 5}"#,
+        )
+        .unwrap();
+        assert!(config.kind == PluginKind::Executable);
+        assert_eq!(config.path, "bin/tool");
+        assert_eq!(config.args, vec!["-v"]);
+        assert_eq!(config.timeout, Some(5));
+        assert!(config.name.is_none());
+
+        assert!(
+            serde_json::from_str::<PluginConfig>(r#"{"type": "script", "path": "x"}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn last_line_skips_trailing_blank_lines() {
+        assert_eq!(last_line("a\n b \n\n  \n"), Some("b"));
+        assert_eq!(last_line(" \n"), None);
+    }
+
+    #[cfg(unix)]
+    mod executable {
+        use super::*;
+        use std::os::unix::fs::PermissionsExt;
+        use std::sync::atomic::AtomicU32;
+
+        /// 插件根目录（相当于 `plugins/`），drop 时删除
+        struct PluginRoot(PathBuf);
+
+        impl PluginRoot {
+            fn new() -> Self {
+                static NEXT: AtomicU32 = AtomicU32::new(0);
+                let dir = std::env::temp_dir().join(format!(
+                    "mxu-plugin-test-{}-{}",
+                    std::process::id(),
+                    NEXT.fetch_add(1, Ordering::Relaxed)
+                ));
+                std::fs::create_dir_all(&dir).unwrap();
+                Self(dir)
+            }
+
+            /// 创建可执行文件插件：清单请求时输出 `manifest`，执行请求时运行 `run` 脚本
+            fn plugin(
+                &self,
+                name: &str,
+                config: serde_json::Value,
+                manifest: &str,
+                run: &str,
+            ) -> PathBuf {
+                let dir = self.0.join(name);
+                std::fs::create_dir_all(&dir).unwrap();
+                std::fs::write(dir.join(PLUGIN_CONFIG_FILE), config.to_string()).unwrap();
+                let script = format!(
+                    "#!/bin/sh\nread -r request\ncase \"$request\" in\n\
+                     *'\"manifest\"'*) echo '{}' ;;\n*) printf '%s\\n' \"$request\" > request.json\n{}\n;;\nesac\n",
+                    manifest, run
+                );
+                let path = dir.join("plugin.sh");
+                std::fs::write(&path, script).unwrap();
+                std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+                dir
+            }
+        }
+
+        impl Drop for PluginRoot {
+            fn drop(&mut self) {
+                let _ = std::fs::remove_dir_all(&self.0);
+            }
+        }
+
+        const MANIFEST: &str = r#"{"abi": 1, "actions": [{"name": "TestEcho"}]}"#;
+
+        fn config(timeout: Option<u64>) -> serde_json::Value {
+            json!({"name": "echo", "type": "executable", "path": "plugin.sh", "timeout": timeout})
+        }
+
+        fn load_action(root: &PluginRoot, dir: &Path) -> LoadedPlugins {
+            load_plugin_actions(&[root.0.clone()], &[dir.to_string_lossy().to_string()])
+        }
+
+        fn run(action: &PluginAction) -> Result<(), String> {
+            action.run(
+                std::ptr::null_mut(),
+                json!({"x": 1}),
+                json!({"task_id": 7, "node": "Node"}),
+            )
+        }
+
+        #[test]
+        fn manifest_request_registers_actions() {
+            let root = PluginRoot::new();
+            let dir = root.plugin("echo", config(None), MANIFEST, "echo ok");
+            let loaded = load_action(&root, &dir);
+            assert_eq!(loaded.actions.len(), 1);
+            assert_eq!(loaded.actions[0].name, "TestEcho");
+            assert_eq!(loaded.actions[0].plugin_name(), "echo");
+            assert!(
+                matches!(&loaded.results[..], [(d, Ok(names))] if *d == dir && names == &["TestEcho"])
+            );
+        }
+
+        #[test]
+        fn plugins_not_allowed_are_not_started() {
+            let root = PluginRoot::new();
+            let dir = root.plugin("echo", config(None), MANIFEST, "echo ok");
+            std::fs::write(dir.join("plugin.sh"), "#!/bin/sh\ntouch started\n").unwrap();
+            let loaded = load_plugin_actions(&[root.0.clone()], &[]);
+            assert!(loaded.actions.is_empty() && loaded.results.is_empty());
+            assert!(!dir.join("started").exists());
+        }
+
+        #[test]
+        fn conflicting_and_invalid_manifests_are_reported() {
+            let root = PluginRoot::new();
+            let a = root.plugin("a", config(None), MANIFEST, "");
+            let b = root.plugin(
+                "b",
+                config(None),
+                r#"{"actions": [{"name": "TestEcho"}, {"name": "MXU_SLEEP_ACTION"}, {"name": "Other"}]}"#,
+                "",
+            );
+            let c = root.plugin("c", config(None), "oops", "");
+            let allowed: Vec<String> = [&a, &b, &c]
+                .iter()
+                .map(|d| d.to_string_lossy().to_string())
+                .collect();
+            let loaded = load_plugin_actions(&[root.0.clone()], &allowed);
+            let names: Vec<&str> = loaded.actions.iter().map(|a| a.name.as_str()).collect();
+            assert_eq!(names, vec!["TestEcho", "Other"]);
+            assert!(
+                matches!(&loaded.results[2], (d, Err(e)) if *d == c && e.contains("Invalid manifest"))
+            );
+        }
+
+        #[test]
+        fn run_sends_request_and_reads_response() {
+            let root = PluginRoot::new();
+            let dir = root.plugin(
+                "echo",
+                config(None),
+                MANIFEST,
+                r#"echo "some log"; echo '{"success": true, "message": "done"}'"#,
+            );
+            let loaded = load_action(&root, &dir);
+            run(&loaded.actions[0]).unwrap();
+
+            let request: serde_json::Value =
+                serde_json::from_str(&std::fs::read_to_string(dir.join("request.json")).unwrap())
+                    .unwrap();
+            assert_eq!(
+                request,
+                json!({
+                    "type": "run",
+                    "action": "TestEcho",
+                    "param": {"x": 1},
+                    "context": {"task_id": 7, "node": "Node"},
+                })
+            );
+        }
+
+        #[test]
+        fn run_reports_failure_message() {
+            let root = PluginRoot::new();
+            let dir = root.plugin(
+                "echo",
+                config(None),
+                MANIFEST,
+                r#"echo '{"success": false, "message": "bad input"}'"#,
+            );
+            let loaded = load_action(&root, &dir);
+            assert_eq!(run(&loaded.actions[0]).unwrap_err(), "bad input");
+        }
+
+        #[test]
+        fn run_falls_back_to_exit_code() {
+            let root = PluginRoot::new();
+            let ok = root.plugin("ok", config(None), MANIFEST, "echo plain output");
+            let loaded = load_action(&root, &ok);
+            run(&loaded.actions[0]).unwrap();
+
+            let root = PluginRoot::new();
+            let failing = root.plugin("fail", config(None), MANIFEST, "exit 3");
+            let loaded = load_action(&root, &failing);
+            let err = run(&loaded.actions[0]).unwrap_err();
+            assert!(err.contains("exited with"), "{}", err);
+        }
+
+        #[test]
+        fn run_kills_plugin_after_timeout() {
+            let root = PluginRoot::new();
+            let dir = root.plugin("slow", config(Some(1)), MANIFEST, "sleep 30");
+            let loaded = load_action(&root, &dir);
+            let started = Instant::now();
+            let err = run(&loaded.actions[0]).unwrap_err();
+            assert!(err.contains("timed out"), "{}", err);
+            assert!(started.elapsed() < Duration::from_secs(10));
+        }
+    }
+}
@@ -0,0 +1,418 @@
+//! MXU_POWER：关机 / 重启 / 息屏 / 睡眠 / 休眠 / 注销，支持倒计时与空闲守卫
+
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::{
+    sleep_unless_cancelled, wait_while_paused, ActionContext, MxuAction, COUNTDOWN_INTERVAL,
+};
+use crate::commands::notification::notification_messages;
+use crate::commands::types::NotificationMessages;
+use crate::maa_ffi::emit_cancellable_countdown;
+use crate::power::{PowerAction as PowerOp, SystemCommandRunner};
+
+/// 「仅在没有其它实例运行时执行」守卫的轮询间隔
+const POWER_IDLE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Deserialize, JsonSchema)]
+pub struct PowerParam {
+    /// `shutdown`（默认）/ `restart` / `screenoff` / `sleep` / `hibernate` / `logoff`
+    #[serde(default = "default_power_action")]
+    power_action: String,
+    /// 执行前的倒计时（秒），期间发送桌面通知和倒计时事件，
+    /// 可在工具栏的倒计时上取消（`cancel_power_action`，跳过并返回成功）或停止任务取消（返回失败）
+    #[serde(default)]
+    countdown: u64,
+    /// 仅在没有其它实例运行任务时执行，否则跳过
+    #[serde(default)]
+    only_if_idle: bool,
+    /// `only_if_idle` 时最多等待其它实例结束的时间（秒），默认不等待
+    #[serde(default)]
+    idle_wait: u64,
+}
+
+fn default_power_action() -> String {
+    "shutdown".to_string()
+}
+
+/// MXU_POWER 发送的通知
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PowerNotice {
+    /// 倒计时开始
+    Countdown,
+    /// 用户取消
+    Cancelled,
+    /// 其它实例仍在运行，已跳过
+    Skipped,
+}
+
+impl PowerNotice {
+    /// 按前端下发的本地化文案生成通知内容
+    fn render(&self, messages: &NotificationMessages, action: PowerOp, countdown: u64) -> String {
+        let template = match self {
+            Self::Countdown => &messages.power_countdown,
+            Self::Cancelled => &messages.power_cancelled,
+            Self::Skipped => &messages.power_skipped,
+        };
+        let name = messages
+            .power_actions
+            .get(action.key())
+            .map(String::as_str)
+            .unwrap_or(action.key());
+        template
+            .replace("{action}", name)
+            .replace("{seconds}", &countdown.to_string())
+    }
+}
+
+/// MXU_POWER 与系统交互的接口，测试时替换为不实际执行的实现
+trait PowerHost {
+    /// 发送桌面通知
+    fn notify(&self, notice: PowerNotice, action: PowerOp, countdown: u64);
+    /// 发送倒计时事件，`cancel_id` 为待执行操作 ID
+    fn countdown(&self, ctx: &ActionContext, remaining_secs: u64, total_secs: u64, cancel_id: u64);
+    /// 除 `tasker` 外仍在运行任务的实例
+    fn other_running_instances(&self, tasker: Option<usize>) -> Vec<String>;
+    /// 执行电源操作
+    fn execute(&self, action: PowerOp) -> Result<(), String>;
+}
+
+/// 实际发送通知、事件并执行系统命令
+struct SystemPowerHost;
+
+impl PowerHost for SystemPowerHost {
+    /// 失败时仅记录日志
+    fn notify(&self, notice: PowerNotice, action: PowerOp, countdown: u64) {
+        let messages = notification_messages();
+        if let Err(e) = notify_rust::Notification::new()
+            .summary(&messages.title)
+            .body(&notice.render(&messages, action, countdown))
+            .show()
+        {
+            warn!("[MXU_POWER] Failed to show notification: {}", e);
+        }
+    }
+
+    fn countdown(&self, ctx: &ActionContext, remaining_secs: u64, total_secs: u64, cancel_id: u64) {
+        emit_cancellable_countdown(
+            ctx.task_id,
+            PowerAction::TAG,
+            &ctx.node,
+            remaining_secs,
+            total_secs,
+            cancel_id,
+        );
+    }
+
+    fn other_running_instances(&self, tasker: Option<usize>) -> Vec<String> {
+        crate::power::other_running_instances(tasker)
+    }
+
+    fn execute(&self, action: PowerOp) -> Result<(), String> {
+        crate::power::execute_power_action(action, &SystemCommandRunner)
+    }
+}
+
+/// 等待其它实例结束，最多等待 `max_wait`，返回仍在运行的实例（被停止时返回 None）
+fn wait_for_other_instances(
+    host: &dyn PowerHost,
+    tasker: Option<usize>,
+    max_wait: Duration,
+) -> Option<Vec<String>> {
+    let deadline = Instant::now() + max_wait;
+    loop {
+        let running = host.other_running_instances(tasker);
+        let now = Instant::now();
+        if running.is_empty() || now >= deadline {
+            return Some(running);
+        }
+        info!(
+            "[MXU_POWER] Waiting for other instances to finish: {:?}",
+            running
+        );
+        if !sleep_unless_cancelled(tasker, (deadline - now).min(POWER_IDLE_POLL_INTERVAL)) {
+            return None;
+        }
+    }
+}
+
+/// 执行电源操作；被守卫跳过或被用户取消时返回成功
+pub struct PowerAction;
+
+impl MxuAction for PowerAction {
+    const NAME: &'static str = "MXU_POWER_ACTION";
+    const TAG: &'static str = "MXU_POWER";
+    type Param = PowerParam;
+
+    fn run(ctx: &ActionContext, param: PowerParam) -> Result<(), String> {
+        run_power(ctx, param, &SystemPowerHost)
+    }
+}
+
+fn run_power(ctx: &ActionContext, param: PowerParam, host: &dyn PowerHost) -> Result<(), String> {
+    let action = PowerOp::parse(&param.power_action)
+        .ok_or_else(|| format!("Unknown power action: {}", param.power_action))?;
+    let countdown = param.countdown;
+    let tasker = ctx.tasker();
+
+    if param.only_if_idle {
+        let running = wait_for_other_instances(host, tasker, Duration::from_secs(param.idle_wait))
+            .ok_or("Cancelled by stop request")?;
+        if !running.is_empty() {
+            info!(
+                "[MXU_POWER] Skipping {:?}, other instances are still running: {:?}",
+                action, running
+            );
+            host.notify(PowerNotice::Skipped, action, countdown);
+            return Ok(());
+        }
+    }
+
+    if countdown > 0 {
+        let pending_id = crate::power::begin_pending();
+        host.notify(PowerNotice::Countdown, action, countdown);
+
+        let mut deadline = Instant::now() + Duration::from_secs(countdown);
+        loop {
+            // 任务暂停期间停止倒计时，恢复后顺延截止时间
+            match wait_while_paused(tasker) {
+                Some(paused) => deadline += paused,
+                None => {
+                    crate::power::end_pending(pending_id);
+                    host.countdown(ctx, 0, countdown, pending_id);
+                    return Err(format!("{:?} cancelled by stop request", action));
+                }
+            }
+            if crate::power::is_pending_cancelled(pending_id) {
+                crate::power::end_pending(pending_id);
+                host.countdown(ctx, 0, countdown, pending_id);
+                info!("[MXU_POWER] {:?} cancelled by user", action);
+                host.notify(PowerNotice::Cancelled, action, countdown);
+                return Ok(());
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                host.countdown(ctx, 0, countdown, pending_id);
+                break;
+            }
+            let remaining = deadline - now;
+            host.countdown(
+                ctx,
+                remaining.as_secs_f64().ceil() as u64,
+                countdown,
+                pending_id,
+            );
+            if !sleep_unless_cancelled(tasker, remaining.min(COUNTDOWN_INTERVAL)) {
+                crate::power::end_pending(pending_id);
+                host.countdown(ctx, 0, countdown, pending_id);
+                return Err(format!("{:?} cancelled by stop request", action));
+            }
+        }
+        crate::power::end_pending(pending_id);
+
+        // 倒计时期间可能有其它实例开始运行
+        if param.only_if_idle {
+            let running = host.other_running_instances(tasker);
+            if !running.is_empty() {
+                info!(
+                    "[MXU_POWER] Skipping {:?}, other instances started during countdown: {:?}",
+                    action, running
+                );
+                return Ok(());
+            }
+        }
+    }
+
+    info!("[MXU_POWER] Executing power action: {:?}", action);
+
+    host.execute(action)
+        .map_err(|e| format!("{:?} failed: {}", action, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::power::tests::RecordingRunner;
+    use std::cell::RefCell;
+
+    /// 记录通知、倒计时与命令的测试宿主
+    #[derive(Default)]
+    struct FakeHost {
+        notifications: RefCell<Vec<(PowerNotice, PowerOp)>>,
+        /// (剩余秒数, 待执行操作 ID)
+        countdowns: RefCell<Vec<(u64, u64)>>,
+        /// 每次查询依次返回的运行中实例，用完后返回空
+        running: RefCell<Vec<Vec<String>>>,
+        /// 收到第一次倒计时事件时取消该操作
+        cancel_on_countdown: bool,
+        runner: RecordingRunner,
+    }
+
+    impl PowerHost for FakeHost {
+        fn notify(&self, notice: PowerNotice, action: PowerOp, _countdown: u64) {
+            self.notifications.borrow_mut().push((notice, action));
+        }
+
+        fn countdown(&self, _ctx: &ActionContext, remaining_secs: u64, _total: u64, id: u64) {
+            self.countdowns.borrow_mut().push((remaining_secs, id));
+            if self.cancel_on_countdown && remaining_secs > 0 {
+                assert_eq!(crate::power::cancel_pending(Some(id)), 1);
+            }
+        }
+
+        fn other_running_instances(&self, _tasker: Option<usize>) -> Vec<String> {
+            let mut running = self.running.borrow_mut();
+            if running.is_empty() {
+                Vec::new()
+            } else {
+                running.remove(0)
+            }
+        }
+
+        fn execute(&self, action: PowerOp) -> Result<(), String> {
+            crate::power::execute_power_action(action, &self.runner)
+        }
+    }
+
+    fn ctx() -> ActionContext {
+        ActionContext {
+            context: std::ptr::null_mut(),
+            task_id: 1,
+            node: "PowerTest".to_string(),
+            box_rect: None,
+        }
+    }
+
+    fn run(host: &FakeHost, param: serde_json::Value) -> Result<(), String> {
+        run_power(&ctx(), serde_json::from_value(param).unwrap(), host)
+    }
+
+    #[test]
+    fn unknown_action_fails() {
+        let host = FakeHost::default();
+        let err = run(&host, serde_json::json!({ "power_action": "explode" })).unwrap_err();
+        assert_eq!(err, "Unknown power action: explode");
+        assert!(host.runner.calls.borrow().is_empty());
+    }
+
+    #[test]
+    fn countdown_then_execute() {
+        let host = FakeHost::default();
+        run(
+            &host,
+            serde_json::json!({ "power_action": "restart", "countdown": 1 }),
+        )
+        .unwrap();
+
+        let countdowns = host.countdowns.borrow();
+        let id = countdowns[0].1;
+        assert_eq!(countdowns.first(), Some(&(1, id)));
+        assert_eq!(countdowns.last(), Some(&(0, id)));
+        assert!(countdowns.iter().all(|(_, cancel_id)| *cancel_id == id));
+        assert_eq!(
+            *host.notifications.borrow(),
+            [(PowerNotice::Countdown, PowerOp::Restart)]
+        );
+        #[cfg(target_os = "linux")]
+        assert_eq!(*host.runner.calls.borrow(), ["systemctl reboot"]);
+        assert!(!crate::power::is_pending_cancelled(id));
+    }
+
+    #[test]
+    fn cancel_during_countdown_skips() {
+        let host = FakeHost {
+            cancel_on_countdown: true,
+            ..Default::default()
+        };
+        run(
+            &host,
+            serde_json::json!({ "power_action": "shutdown", "countdown": 30 }),
+        )
+        .unwrap();
+
+        assert!(host.runner.calls.borrow().is_empty());
+        let countdowns = host.countdowns.borrow();
+        assert_eq!(countdowns.len(), 2);
+        assert_eq!(countdowns[1].0, 0);
+        assert_eq!(
+            *host.notifications.borrow(),
+            [
+                (PowerNotice::Countdown, PowerOp::Shutdown),
+                (PowerNotice::Cancelled, PowerOp::Shutdown)
+            ]
+        );
+    }
+
+    #[test]
+    fn other_instance_guard_skips() {
+        let host = FakeHost {
+            running: RefCell::new(vec![vec!["other".to_string()]]),
+            ..Default::default()
+        };
+        run(
+            &host,
+            serde_json::json!({ "power_action": "sleep", "only_if_idle": true }),
+        )
+        .unwrap();
+
+        assert!(host.runner.calls.borrow().is_empty());
+        assert_eq!(
+            *host.notifications.borrow(),
+            [(PowerNotice::Skipped, PowerOp::Sleep)]
+        );
+    }
+
+    #[test]
+    fn other_instance_guard_rechecks_after_countdown() {
+        let host = FakeHost {
+            running: RefCell::new(vec![Vec::new(), vec!["other".to_string()]]),
+            ..Default::default()
+        };
+        run(
+            &host,
+            serde_json::json!({ "power_action": "hibernate", "countdown": 1, "only_if_idle": true }),
+        )
+        .unwrap();
+
+        assert!(host.runner.calls.borrow().is_empty());
+        assert_eq!(host.countdowns.borrow().last().map(|c| c.0), Some(0));
+    }
+
+    #[test]
+    fn idle_guard_passes_without_other_instances() {
+        let host = FakeHost::default();
+        run(
+            &host,
+            serde_json::json!({ "power_action": "shutdown", "only_if_idle": true }),
+        )
+        .unwrap();
+        assert!(host.notifications.borrow().is_empty());
+        #[cfg(target_os = "linux")]
+        assert_eq!(*host.runner.calls.borrow(), ["systemctl poweroff"]);
+    }
+
+    #[test]
+    fn notices_use_message_table() {
+        let mut messages = NotificationMessages {
+            power_countdown: "{seconds}|{action}".to_string(),
+            ..Default::default()
+        };
+        messages
+            .power_actions
+            .insert("restart".to_string(), "R".to_string());
+        messages.power_actions.remove("sleep");
+
+        assert_eq!(
+            PowerNotice::Countdown.render(&messages, PowerOp::Restart, 30),
+            "30|R"
+        );
+        // 文案表缺少操作名称时使用键
+        assert_eq!(
+            PowerNotice::Cancelled.render(&messages, PowerOp::Sleep, 0),
+            messages.power_cancelled.replace("{action}", "sleep")
+        );
+    }
+}
@@ -0,0 +1,461 @@
+//! MXU_SCREENSHOT：保存当前控制器画面，支持裁剪与旧图清理
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use log::{info, warn};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::file::ensure_allowed;
+use super::template::{Escape, Template};
+use super::{context_tasker, non_empty, ActionContext, MxuAction};
+use crate::commands::file_ops::file_action_roots;
+use crate::maa_ffi::{callback_fns, MaaContext, MaaTasker, MAA_STATUS_SUCCEEDED};
+
+/// 默认截图目录（相对数据目录）
+const SCREENSHOT_DEFAULT_DIR: &str = "screenshots";
+/// 默认文件名模板（相对截图目录）
+const SCREENSHOT_DEFAULT_FILENAME: &str = "{date}/{instance}_{node}_{time}.png";
+/// 默认目录下的默认保留天数
+const SCREENSHOT_DEFAULT_KEEP_DAYS: u64 = 30;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ScreenshotParam {
+    /// 保存目录，相对路径基于数据目录，默认 `screenshots`，可使用占位符（含占位符的目录层级
+    /// 与文件名一起参与清理时的匹配）
+    #[serde(default)]
+    dir: Option<String>,
+    /// 文件名模板，默认 `{date}/{instance}_{node}_{time}.png`，
+    /// 可使用占位符（见 [`super::template`]），其中 `{time}` 为 `HH-MM-SS`
+    #[serde(default)]
+    filename: Option<String>,
+    /// `png` / `jpeg`，默认按扩展名推断
+    #[serde(default)]
+    format: Option<String>,
+    /// JPEG 质量（1-100），默认 90
+    #[serde(default)]
+    quality: Option<u64>,
+    /// 是否裁剪到识别结果的 box
+    #[serde(default)]
+    crop: bool,
+    /// 是否重新截图，默认使用识别时的画面
+    #[serde(default)]
+    refresh: bool,
+    /// 保留天数，0 表示不限；未设置 `dir` 时默认 30，自定义 `dir` 时默认 0（不清理）。
+    /// 只清理路径与 `dir` + `filename` 模板匹配的图片，不影响目录中的其它文件
+    #[serde(default)]
+    keep_days: Option<u64>,
+    /// 与模板匹配的截图最多保留的数量，默认 0 表示不限
+    #[serde(default)]
+    max_files: u64,
+}
+
+/// 从 context 所属 tasker 的控制器获取当前画面（PNG 编码）
+/// `refresh` 为 true 时先重新截图，否则使用识别时缓存的画面
+pub(super) fn capture_controller_image(
+    context: *mut MaaContext,
+    refresh: bool,
+) -> Result<Vec<u8>, String> {
+    let tasker = context_tasker(context).ok_or("Cannot resolve tasker from context")?;
+
+    // 在工作线程上运行，使用缓存的函数指针而不锁定 MAA_LIBRARY
+    let fns = callback_fns()?;
+    let get_controller = fns
+        .tasker_get_controller
+        .ok_or("MaaTaskerGetController not available, please update MaaFramework")?;
+
+    unsafe {
+        let controller = get_controller(tasker as *mut MaaTasker);
+        if controller.is_null() {
+            return Err("Tasker has no controller".to_string());
+        }
+
+        if refresh {
+            let screencap_id = (fns.controller_post_screencap)(controller);
+            if (fns.controller_wait)(controller, screencap_id) != MAA_STATUS_SUCCEEDED {
+                return Err("Screencap failed".to_string());
+            }
+        }
+
+        let buffer = (fns.image_buffer_create)();
+        if buffer.is_null() {
+            return Err("Failed to create image buffer".to_string());
+        }
+        let result = if (fns.controller_cached_image)(controller, buffer) == 0 {
+            Err("Failed to get cached image".to_string())
+        } else {
+            let data = (fns.image_buffer_get_encoded)(buffer);
+            let size = (fns.image_buffer_get_encoded_size)(buffer);
+            if data.is_null() || size == 0 {
+                Err("No image data available".to_string())
+            } else {
+                Ok(std::slice::from_raw_parts(data, size as usize).to_vec())
+            }
+        };
+        (fns.image_buffer_destroy)(buffer);
+        result
+    }
+}
+
+/// 拆分目录模板为不含占位符的前缀（清理的根目录）与其余含占位符的层级；
+/// 只由 `{exe_dir}` / `{data_dir}` 组成的层级视为固定前缀
+fn split_dir_template(dir: &str) -> (String, String) {
+    let mut prefix = Vec::new();
+    let mut components = dir.split(['/', '\\']).peekable();
+    while let Some(component) = components.peek() {
+        if component.contains('{') && !matches!(*component, "{exe_dir}" | "{data_dir}") {
+            break;
+        }
+        prefix.push(*component);
+        components.next();
+    }
+    let rest = components.collect::<Vec<_>>().join("/");
+    (prefix.join("/"), rest)
+}
+
+/// 将相对路径模板转换为 glob 模式：占位符匹配单层路径中的任意字符，其余部分按字面匹配；
+/// 去掉扩展名，因为保存时可能按格式替换扩展名
+fn template_pattern(template: &str) -> Result<glob::Pattern, String> {
+    let mut pattern = String::new();
+    let mut rest = template.replace('\\', "/");
+    while let Some(start) = rest.find('{') {
+        pattern.push_str(&glob::Pattern::escape(&rest[..start]));
+        match rest[start..].find('}') {
+            Some(end) => {
+                pattern.push('*');
+                rest = rest[start + end + 1..].to_string();
+            }
+            None => {
+                pattern.push_str(&glob::Pattern::escape(&rest[start..]));
+                rest.clear();
+            }
+        }
+    }
+    pattern.push_str(&glob::Pattern::escape(&rest));
+    let pattern = Path::new(&pattern).with_extension("");
+    glob::Pattern::new(&pattern.to_string_lossy())
+        .map_err(|e| format!("Invalid screenshot path pattern '{}': {}", template, e))
+}
+
+/// 清理 `root` 下路径与模板匹配的旧截图，并删除因此变空的子目录
+fn prune_screenshots(root: &Path, template: &str, keep_days: u64, max_files: usize) {
+    let pattern = match template_pattern(template) {
+        Ok(pattern) => pattern,
+        Err(e) => {
+            warn!("[MXU_SCREENSHOT] {}", e);
+            return;
+        }
+    };
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        ..Default::default()
+    };
+
+    // 只遍历到模板的层级深度
+    fn collect(dir: &Path, depth: usize, files: &mut Vec<(PathBuf, std::time::SystemTime)>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if depth > 1 {
+                    collect(&path, depth - 1, files);
+                }
+                continue;
+            }
+            let is_image = depth == 1
+                && file_type.is_file()
+                && path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .map(|e| matches!(e.to_ascii_lowercase().as_str(), "png" | "jpg" | "jpeg"))
+                    .unwrap_or(false);
+            if is_image {
+                let modified = entry
+                    .metadata()
+                    .and_then(|m| m.modified())
+                    .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+                files.push((path, modified));
+            }
+        }
+    }
+
+    let depth = template.split(['/', '\\']).count();
+    let mut files = Vec::new();
+    collect(root, depth, &mut files);
+    files.retain(|(path, _)| {
+        path.strip_prefix(root)
+            .map(|rel| pattern.matches_path_with(&rel.with_extension(""), options))
+            .unwrap_or(false)
+    });
+    // 最新的在前
+    files.sort_by_key(|f| std::cmp::Reverse(f.1));
+
+    let now = std::time::SystemTime::now();
+    let max_age = Duration::from_secs(keep_days.saturating_mul(24 * 60 * 60));
+    let mut removed = 0usize;
+    for (idx, (path, modified)) in files.iter().enumerate() {
+        let expired = keep_days > 0 && now.duration_since(*modified).unwrap_or_default() > max_age;
+        let over_count = max_files > 0 && idx >= max_files;
+        if (expired || over_count) && std::fs::remove_file(path).is_ok() {
+            removed += 1;
+            // 仅在目录为空时成功
+            for parent in path.ancestors().skip(1) {
+                if parent == root || std::fs::remove_dir(parent).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+    if removed > 0 {
+        info!("[MXU_SCREENSHOT] Pruned {} old screenshot(s)", removed);
+    }
+}
+
+/// 保存截图，保存后按保留策略清理截图目录
+pub struct ScreenshotAction;
+
+impl MxuAction for ScreenshotAction {
+    const NAME: &'static str = "MXU_SCREENSHOT_ACTION";
+    const TAG: &'static str = "MXU_SCREENSHOT";
+    type Param = ScreenshotParam;
+
+    fn run(ctx: &ActionContext, param: ScreenshotParam) -> Result<(), String> {
+        // `{time}` 使用适合文件名的格式
+        let template = Template::new(ctx);
+        let time = template.now().format("%H-%M-%S").to_string();
+        let template = template.with("time", time);
+
+        let absolute = |path: PathBuf| -> Result<PathBuf, String> {
+            if path.is_absolute() {
+                Ok(path)
+            } else {
+                Ok(crate::commands::utils::get_app_data_dir()?.join(path))
+            }
+        };
+
+        // 保存目录
+        let dir_template = non_empty(param.dir);
+        let custom_dir = dir_template.is_some();
+        let dir_template = dir_template.unwrap_or_else(|| SCREENSHOT_DEFAULT_DIR.to_string());
+        let dir = absolute(PathBuf::from(template.render(&dir_template, Escape::Raw)))?;
+
+        // 渲染文件名模板
+        let filename_template =
+            non_empty(param.filename).unwrap_or_else(|| SCREENSHOT_DEFAULT_FILENAME.to_string());
+        let filename = template.render(&filename_template, Escape::FileName);
+        let mut path = dir.join(&filename);
+
+        // 确定图片格式
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+        let format = match non_empty(param.format)
+            .map(|s| s.to_ascii_lowercase())
+            .or(extension.clone())
+            .as_deref()
+        {
+            None | Some("png") => image::ImageFormat::Png,
+            Some("jpg") | Some("jpeg") => image::ImageFormat::Jpeg,
+            Some(other) => return Err(format!("Unsupported format: {}", other)),
+        };
+        let expected_ext = if format == image::ImageFormat::Png {
+            "png"
+        } else {
+            "jpg"
+        };
+        if !matches!(
+            extension.as_deref(),
+            Some("png") | Some("jpg") | Some("jpeg")
+        ) {
+            path.set_extension(expected_ext);
+        }
+
+        let encoded = capture_controller_image(ctx.context, param.refresh)?;
+        let mut img = image::load_from_memory(&encoded)
+            .map_err(|e| format!("Failed to decode image: {}", e))?;
+
+        // 裁剪到识别结果区域（与图片边界取交集）
+        if param.crop {
+            if let Some(rect) = ctx.box_rect {
+                let x = rect.x.max(0) as u32;
+                let y = rect.y.max(0) as u32;
+                let w = (rect.w.max(0) as u32).min(img.width().saturating_sub(x));
+                let h = (rect.h.max(0) as u32).min(img.height().saturating_sub(y));
+                if w > 0 && h > 0 {
+                    img = img.crop_imm(x, y, w, h);
+                } else {
+                    warn!("[MXU_SCREENSHOT] Empty box, saving full frame");
+                }
+            }
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+        }
+
+        let save_result = if format == image::ImageFormat::Jpeg {
+            let quality = param.quality.unwrap_or(90).clamp(1, 100) as u8;
+            std::fs::File::create(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|file| {
+                    let mut writer = std::io::BufWriter::new(file);
+                    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut writer, quality)
+                        .encode_image(&img.to_rgb8())
+                        .map_err(|e| e.to_string())
+                })
+        } else {
+            img.save_with_format(&path, format)
+                .map_err(|e| e.to_string())
+        };
+        save_result.map_err(|e| format!("Failed to save {:?}: {}", path, e))?;
+        info!("[MXU_SCREENSHOT] Screenshot saved: {:?}", path);
+
+        // 自定义目录可能是用户已有的文件夹，需显式设置 keep_days 才清理
+        let keep_days = param.keep_days.unwrap_or(if custom_dir {
+            0
+        } else {
+            SCREENSHOT_DEFAULT_KEEP_DAYS
+        });
+        let max_files = param.max_files as usize;
+        if keep_days > 0 || max_files > 0 {
+            let (prefix, rest) = split_dir_template(&dir_template);
+            let root = absolute(PathBuf::from(template.render(&prefix, Escape::Raw)))?;
+            let pattern = if rest.is_empty() {
+                filename_template
+            } else {
+                format!("{}/{}", rest, filename_template)
+            };
+            match ensure_allowed(&root, &file_action_roots()?) {
+                Ok(_) => prune_screenshots(&root, &pattern, keep_days, max_files),
+                Err(e) => warn!("[MXU_SCREENSHOT] Skipping cleanup: {}", e),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::SystemTime;
+
+    /// 测试用临时目录，drop 时删除
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            static NEXT: AtomicU32 = AtomicU32::new(0);
+            let dir = std::env::temp_dir().join(format!(
+                "mxu-screenshot-test-{}-{}",
+                std::process::id(),
+                NEXT.fetch_add(1, Ordering::Relaxed)
+            ));
+            std::fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        /// 创建文件并设置修改时间为 `age_days` 天前
+        fn file(&self, rel: &str, age_days: u64) -> PathBuf {
+            let path = self.0.join(rel);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            let file = std::fs::File::create(&path).unwrap();
+            let modified = SystemTime::now() - Duration::from_secs(age_days * 24 * 60 * 60);
+            file.set_modified(modified).unwrap();
+            path
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn split_dir_template_keeps_fixed_prefix() {
+        assert_eq!(
+            split_dir_template("screenshots"),
+            ("screenshots".to_string(), String::new())
+        );
+        assert_eq!(
+            split_dir_template("shots/{instance}/{date}"),
+            ("shots".to_string(), "{instance}/{date}".to_string())
+        );
+        assert_eq!(
+            split_dir_template("{data_dir}\\shots\\{date}"),
+            ("{data_dir}/shots".to_string(), "{date}".to_string())
+        );
+        assert_eq!(
+            split_dir_template("/abs/dir"),
+            ("/abs/dir".to_string(), String::new())
+        );
+        assert_eq!(
+            split_dir_template("{date}"),
+            (String::new(), "{date}".to_string())
+        );
+    }
+
+    #[test]
+    fn template_pattern_matches_rendered_names() {
+        let pattern = template_pattern(SCREENSHOT_DEFAULT_FILENAME).unwrap();
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+        let matches = |path: &str| pattern.matches_path_with(Path::new(path), options);
+        assert!(matches("2026-10-18/inst_node_10-00-00"));
+        assert!(!matches("2026-10-18/photo"));
+        assert!(!matches("inst_node_10-00-00"));
+        assert!(!matches("a/b/inst_node_10-00-00"));
+
+        let literal = template_pattern("[x] {date}.jpg").unwrap();
+        assert!(literal.matches("[x] 2026-10-18"));
+        assert!(!literal.matches("x 2026-10-18"));
+    }
+
+    #[test]
+    fn prune_only_touches_files_matching_the_template() {
+        let tmp = TempDir::new();
+        let old_shot = tmp.file("2026-01-01/inst_node_10-00-00.png", 60);
+        let new_shot = tmp.file("2026-10-18/inst_node_10-00-00.png", 0);
+        let photo = tmp.file("holiday/photo.jpg", 60);
+        let top_level = tmp.file("old.png", 60);
+        let nested = tmp.file("a/b/inst_node_10-00-00.png", 60);
+        std::fs::create_dir_all(tmp.0.join("empty")).unwrap();
+
+        prune_screenshots(&tmp.0, SCREENSHOT_DEFAULT_FILENAME, 30, 0);
+
+        assert!(!old_shot.exists());
+        assert!(!tmp.0.join("2026-01-01").exists());
+        assert!(new_shot.exists());
+        assert!(photo.exists());
+        assert!(top_level.exists());
+        assert!(nested.exists());
+        assert!(tmp.0.join("empty").exists());
+    }
+
+    #[test]
+    fn prune_keeps_newest_by_count() {
+        let tmp = TempDir::new();
+        let newest = tmp.file("shot_1.png", 1);
+        let middle = tmp.file("shot_2.jpg", 2);
+        let oldest = tmp.file("shot_3.png", 3);
+        let other = tmp.file("photo.png", 10);
+
+        prune_screenshots(&tmp.0, "shot_{time}.png", 0, 2);
+
+        assert!(newest.exists() && middle.exists());
+        assert!(!oldest.exists());
+        assert!(other.exists());
+    }
+}
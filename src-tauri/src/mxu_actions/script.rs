@@ -0,0 +1,432 @@
+//! MXU_SCRIPT：执行内嵌 Rhai 脚本，处理日期判断、字符串拼接等简单逻辑
+//!
+//! 脚本运行在沙箱中：不能访问文件系统和 `import` 模块，受操作数与运行时间限制。
+//! 可用的绑定：
+//!
+//! - `ctx`：`#{task_id, node, instance, task, box}`，`box` 为 `[x, y, w, h]` 或 `()`
+//! - `args`：参数中的 `args`，缺省为空 map
+//! - `now()` 返回 `#{year, month, day, hour, minute, second, weekday, timestamp}`（weekday 1-7 对应周一至周日），
+//!   `format_time(fmt)` 按 strftime 格式化当前本地时间
+//! - `sleep(ms)`：等待，可被停止任务打断
+//! - `get_var(name[, scope])` / `set_var(name, value[, scope])`：读写持久化变量，scope 为 `instance`（默认）或 `global`
+//! - `http_get(url)` / `http_post(url, body)`：返回 `#{status, body}`，body 为 map 时按 JSON 发送
+//! - `notify(title, body[, channel])`：发送通知，channel 同 MXU_NOTIFY
+//! - `screenshot()`：重新截图并返回 `#{width, height}`，`pixel(x, y)` 读取最近一次截图的 `[r, g, b]`
+//! - `click(x, y)`：通过当前控制器点击
+//! - `log(msg)` / `print(msg)`：写入日志
+//!
+//! 脚本返回 `false` 或抛出异常（`throw "..."`）时节点失败，其余返回值视为成功。
+
+use std::cell::RefCell;
+use std::path::{Component, Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use chrono::{Datelike, Local, Timelike};
+use log::info;
+use rhai::{Dynamic, Engine, EvalAltResult, Map, Scope};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::notify::send_notification;
+use super::screenshot::capture_controller_image;
+use super::{
+    context_tasker, is_cancelled, non_empty, sleep_unless_cancelled, with_tasker_instance,
+    ActionContext, MxuAction,
+};
+use crate::commands::variables::{get_value, update_value};
+use crate::maa_ffi::{callback_fns, MaaContext, MaaTasker, MAA_STATUS_SUCCEEDED};
+
+/// 默认运行时间上限（秒）
+const SCRIPT_DEFAULT_TIMEOUT: u64 = 30;
+/// 默认操作数上限
+const SCRIPT_DEFAULT_MAX_OPERATIONS: u64 = 10_000_000;
+/// 每隔多少次操作检查一次超时与停止请求
+const SCRIPT_PROGRESS_CHECK_INTERVAL: u64 = 1024;
+/// 单次 HTTP 请求的超时上限
+const SCRIPT_HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+/// 脚本中字符串的最大长度
+const SCRIPT_MAX_STRING_SIZE: usize = 1 << 20;
+/// 脚本中数组 / map 的最大元素数
+const SCRIPT_MAX_COLLECTION_SIZE: usize = 100_000;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ScriptParam {
+    /// 内联脚本，与 `file` 二选一
+    #[serde(default)]
+    script: Option<String>,
+    /// 脚本文件，相对当前实例已加载的资源目录（后加载的优先）
+    #[serde(default)]
+    file: Option<String>,
+    /// 传给脚本的参数，脚本中通过 `args` 访问
+    #[serde(default)]
+    args: Option<serde_json::Value>,
+    /// 运行时间上限（秒），默认 30
+    #[serde(default)]
+    timeout: Option<u64>,
+    /// 操作数上限，默认 10000000
+    #[serde(default)]
+    max_operations: Option<u64>,
+}
+
+/// 脚本运行中止的原因，作为 `on_progress` 的终止标记
+const TERMINATED_TIMEOUT: &str = "timeout";
+const TERMINATED_CANCELLED: &str = "cancelled";
+
+fn script_error(e: impl std::fmt::Display) -> Box<EvalAltResult> {
+    e.to_string().into()
+}
+
+/// 在当前实例的资源目录中查找脚本文件，拒绝绝对路径与 `..`
+fn resolve_script_file(tasker: Option<usize>, file: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(file);
+    if relative
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(format!(
+            "Script file must be a relative path inside the resource directory: {}",
+            file
+        ));
+    }
+    let tasker = tasker.ok_or("Cannot resolve tasker from context")?;
+    let resource_paths = with_tasker_instance(tasker, |instance| instance.resource_paths.clone())?;
+    resource_paths
+        .iter()
+        .rev()
+        .map(|dir| Path::new(dir).join(relative))
+        .find(|path| path.is_file())
+        .ok_or_else(|| format!("Script file not found in resource directories: {}", file))
+}
+
+/// 变量作用域：`global` 返回 None，其余返回当前任务所属实例
+fn variable_scope(task_id: i64, scope: &str) -> Result<Option<String>, String> {
+    match scope.trim().to_ascii_lowercase().as_str() {
+        "global" => Ok(None),
+        "" | "instance" => {
+            let instance_id = crate::run_context::task_context(task_id).instance_id;
+            if instance_id.is_empty() {
+                Err("Cannot resolve instance of current task".to_string())
+            } else {
+                Ok(Some(instance_id))
+            }
+        }
+        other => Err(format!("Invalid variable scope: {}", other)),
+    }
+}
+
+pub(super) fn get_variable(
+    task_id: i64,
+    name: &str,
+    scope: &str,
+) -> Result<Dynamic, Box<EvalAltResult>> {
+    let instance_id = variable_scope(task_id, scope)?;
+    match get_value(instance_id.as_deref(), name)? {
+        Some(value) => rhai::serde::to_dynamic(value),
+        None => Ok(Dynamic::UNIT),
+    }
+}
+
+fn set_variable(
+    task_id: i64,
+    name: &str,
+    value: Dynamic,
+    scope: &str,
+) -> Result<(), Box<EvalAltResult>> {
+    let instance_id = variable_scope(task_id, scope)?;
+    let value: serde_json::Value = rhai::serde::from_dynamic(&value)?;
+    info!(
+        "[MXU_SCRIPT] set_var {} (instance={:?}) = {}",
+        name, instance_id, value
+    );
+    update_value(instance_id.as_deref(), name, None, None, |var| {
+        var.value = value;
+        Ok(())
+    })?;
+    Ok(())
+}
+
+/// 发送 HTTP 请求，超时不超过脚本剩余时间
+fn http_request(
+    deadline: Instant,
+    method: reqwest::Method,
+    url: &str,
+    body: Option<Dynamic>,
+) -> Result<Map, Box<EvalAltResult>> {
+    let timeout = deadline
+        .saturating_duration_since(Instant::now())
+        .min(SCRIPT_HTTP_TIMEOUT);
+    if timeout.is_zero() {
+        return Err(script_error("Script time limit exceeded"));
+    }
+    let client = reqwest::blocking::Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| script_error(format!("Failed to build HTTP client: {}", e)))?;
+
+    info!("[MXU_SCRIPT] {} {}", method, url);
+    let mut request = client.request(method, url);
+    if let Some(body) = body {
+        request = if body.is_string() {
+            request.body(body.into_string().unwrap_or_default())
+        } else {
+            let json: serde_json::Value = rhai::serde::from_dynamic(&body)?;
+            request
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(json.to_string())
+        };
+    }
+    let response = request
+        .send()
+        .map_err(|e| script_error(format!("Request failed: {}", e)))?;
+
+    let mut result = Map::new();
+    result.insert("status".into(), (response.status().as_u16() as i64).into());
+    result.insert("body".into(), response.text().unwrap_or_default().into());
+    Ok(result)
+}
+
+/// 通过 context 所属 tasker 的控制器点击并等待完成
+fn controller_click(context: *mut MaaContext, x: i32, y: i32) -> Result<(), String> {
+    let tasker = context_tasker(context).ok_or("Cannot resolve tasker from context")?;
+    // 在工作线程上运行，使用缓存的函数指针而不锁定 MAA_LIBRARY
+    let fns = callback_fns()?;
+    let get_controller = fns
+        .tasker_get_controller
+        .ok_or("MaaTaskerGetController not available, please update MaaFramework")?;
+
+    unsafe {
+        let controller = get_controller(tasker as *mut MaaTasker);
+        if controller.is_null() {
+            return Err("Tasker has no controller".to_string());
+        }
+        let click_id = (fns.controller_post_click)(controller, x, y);
+        if (fns.controller_wait)(controller, click_id) != MAA_STATUS_SUCCEEDED {
+            return Err(format!("Click ({}, {}) failed", x, y));
+        }
+    }
+    Ok(())
+}
+
+/// 创建带资源限制并注册了 MXU 绑定的脚本引擎
+fn build_engine(ctx: &ActionContext, deadline: Instant, max_operations: u64) -> Engine {
+    let mut engine = Engine::new();
+    let tasker = ctx.tasker();
+    let task_id = ctx.task_id;
+    let context = ctx.context;
+
+    // 沙箱与资源限制
+    engine.set_module_resolver(rhai::module_resolvers::DummyModuleResolver::new());
+    engine.disable_symbol("eval");
+    engine.set_max_operations(max_operations);
+    engine.set_max_string_size(SCRIPT_MAX_STRING_SIZE);
+    engine.set_max_array_size(SCRIPT_MAX_COLLECTION_SIZE);
+    engine.set_max_map_size(SCRIPT_MAX_COLLECTION_SIZE);
+    engine.set_max_call_levels(64);
+    engine.set_max_expr_depths(64, 32);
+    engine.on_progress(move |ops| {
+        if ops % SCRIPT_PROGRESS_CHECK_INTERVAL != 0 {
+            return None;
+        }
+        if Instant::now() >= deadline {
+            Some(TERMINATED_TIMEOUT.into())
+        } else if is_cancelled(tasker) {
+            Some(TERMINATED_CANCELLED.into())
+        } else {
+            None
+        }
+    });
+
+    // 日志
+    engine.on_print(|s| info!("[MXU_SCRIPT] {}", s));
+    engine.on_debug(|s, _, pos| info!("[MXU_SCRIPT] {} @ {}", s, pos));
+    engine.register_fn("log", |s: &str| info!("[MXU_SCRIPT] {}", s));
+
+    // 时间
+    engine.register_fn("now", || {
+        let now = Local::now();
+        let mut map = Map::new();
+        map.insert("year".into(), (now.year() as i64).into());
+        map.insert("month".into(), (now.month() as i64).into());
+        map.insert("day".into(), (now.day() as i64).into());
+        map.insert("hour".into(), (now.hour() as i64).into());
+        map.insert("minute".into(), (now.minute() as i64).into());
+        map.insert("second".into(), (now.second() as i64).into());
+        map.insert(
+            "weekday".into(),
+            (now.weekday().number_from_monday() as i64).into(),
+        );
+        map.insert("timestamp".into(), now.timestamp().into());
+        map
+    });
+    engine.register_fn("format_time", |fmt: &str| {
+        use std::fmt::Write;
+        let mut output = String::new();
+        write!(output, "{}", Local::now().format(fmt))
+            .map_err(|_| script_error(format!("Invalid time format: {}", fmt)))?;
+        Ok::<_, Box<EvalAltResult>>(output)
+    });
+    engine.register_fn("sleep", move |ms: i64| {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let duration = Duration::from_millis(ms.max(0) as u64);
+        if duration > remaining {
+            return Err(script_error("Script time limit exceeded"));
+        }
+        if !sleep_unless_cancelled(tasker, duration) {
+            return Err(script_error("Stop requested"));
+        }
+        Ok(())
+    });
+
+    // 持久化变量
+    engine.register_fn("get_var", move |name: &str| get_variable(task_id, name, ""));
+    engine.register_fn("get_var", move |name: &str, scope: &str| {
+        get_variable(task_id, name, scope)
+    });
+    engine.register_fn("set_var", move |name: &str, value: Dynamic| {
+        set_variable(task_id, name, value, "")
+    });
+    engine.register_fn("set_var", move |name: &str, value: Dynamic, scope: &str| {
+        set_variable(task_id, name, value, scope)
+    });
+
+    // HTTP
+    engine.register_fn("http_get", move |url: &str| {
+        http_request(deadline, reqwest::Method::GET, url, None)
+    });
+    engine.register_fn("http_post", move |url: &str, body: Dynamic| {
+        http_request(deadline, reqwest::Method::POST, url, Some(body))
+    });
+
+    // 通知
+    engine.register_fn("notify", |title: &str, body: &str| {
+        send_notification(title, body, &[]).map_err(script_error)
+    });
+    engine.register_fn("notify", |title: &str, body: &str, channel: Dynamic| {
+        let channels: Vec<String> = if channel.is_array() {
+            rhai::serde::from_dynamic(&channel)?
+        } else {
+            vec![channel.to_string()]
+        };
+        send_notification(title, body, &channels).map_err(script_error)
+    });
+
+    // 控制器
+    let last_image: Rc<RefCell<Option<image::RgbImage>>> = Rc::new(RefCell::new(None));
+    let image_for_screenshot = last_image.clone();
+    engine.register_fn("screenshot", move || {
+        let encoded = capture_controller_image(context, true).map_err(script_error)?;
+        let img = image::load_from_memory(&encoded)
+            .map_err(|e| script_error(format!("Failed to decode image: {}", e)))?
+            .to_rgb8();
+        let mut map = Map::new();
+        map.insert("width".into(), (img.width() as i64).into());
+        map.insert("height".into(), (img.height() as i64).into());
+        *image_for_screenshot.borrow_mut() = Some(img);
+        Ok::<_, Box<EvalAltResult>>(map)
+    });
+    engine.register_fn("pixel", move |x: i64, y: i64| {
+        let image = last_image.borrow();
+        let img = image
+            .as_ref()
+            .ok_or_else(|| script_error("Call screenshot() before pixel()"))?;
+        if x < 0 || y < 0 || x >= img.width() as i64 || y >= img.height() as i64 {
+            return Err(script_error(format!("Pixel ({}, {}) out of range", x, y)));
+        }
+        let [r, g, b] = img.get_pixel(x as u32, y as u32).0;
+        Ok(vec![
+            Dynamic::from(r as i64),
+            Dynamic::from(g as i64),
+            Dynamic::from(b as i64),
+        ])
+    });
+    engine.register_fn("click", move |x: i64, y: i64| {
+        info!("[MXU_SCRIPT] click ({}, {})", x, y);
+        controller_click(context, x as i32, y as i32).map_err(script_error)
+    });
+
+    engine
+}
+
+/// 执行脚本，返回 `false` 或抛出异常时失败
+pub struct ScriptAction;
+
+impl MxuAction for ScriptAction {
+    const NAME: &'static str = "MXU_SCRIPT_ACTION";
+    const TAG: &'static str = "MXU_SCRIPT";
+    type Param = ScriptParam;
+
+    fn run(ctx: &ActionContext, param: ScriptParam) -> Result<(), String> {
+        let (source, origin) = match (non_empty(param.script), non_empty(param.file)) {
+            (Some(script), None) => (script, "inline".to_string()),
+            (None, Some(file)) => {
+                let path = resolve_script_file(ctx.tasker(), &file)?;
+                let source = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+                (source, path.display().to_string())
+            }
+            (Some(_), Some(_)) => {
+                return Err("Only one of 'script' and 'file' can be specified".to_string())
+            }
+            (None, None) => return Err("Missing 'script' or 'file' parameter".to_string()),
+        };
+
+        let timeout = param
+            .timeout
+            .filter(|t| *t > 0)
+            .unwrap_or(SCRIPT_DEFAULT_TIMEOUT);
+        let deadline = Instant::now() + Duration::from_secs(timeout);
+        let engine = build_engine(
+            ctx,
+            deadline,
+            param
+                .max_operations
+                .filter(|n| *n > 0)
+                .unwrap_or(SCRIPT_DEFAULT_MAX_OPERATIONS),
+        );
+
+        let run = crate::run_context::task_context(ctx.task_id);
+        let context = serde_json::json!({
+            "task_id": ctx.task_id,
+            "node": ctx.node,
+            "instance": run.instance_id,
+            "task": run.entry,
+            "box": ctx.box_rect.map(|r| [r.x, r.y, r.w, r.h]),
+        });
+        let args = param
+            .args
+            .unwrap_or_else(|| serde_json::Value::Object(Default::default()));
+
+        let mut scope = Scope::new();
+        scope.push_constant_dynamic(
+            "ctx",
+            rhai::serde::to_dynamic(context).map_err(|e| e.to_string())?,
+        );
+        scope.push_constant_dynamic(
+            "args",
+            rhai::serde::to_dynamic(args).map_err(|e| e.to_string())?,
+        );
+
+        info!("[MXU_SCRIPT] Running script ({})", origin);
+        let result = engine
+            .eval_with_scope::<Dynamic>(&mut scope, &source)
+            .map_err(|e| match *e {
+                EvalAltResult::ErrorTerminated(ref token, _)
+                    if token.to_string() == TERMINATED_TIMEOUT =>
+                {
+                    format!("Script timed out after {}s", timeout)
+                }
+                EvalAltResult::ErrorTerminated(..) => "Stop requested".to_string(),
+                EvalAltResult::ErrorTooManyOperations(_) => {
+                    "Script exceeded the operation limit".to_string()
+                }
+                other => format!("Script error: {}", other),
+            })?;
+
+        info!("[MXU_SCRIPT] Script returned: {}", result);
+        if result.as_bool() == Ok(false) {
+            return Err("Script returned false".to_string());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+//! MXU_SLEEP：倒计时等待指定秒数
+
+use std::time::Duration;
+
+use log::info;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::{ActionContext, MxuAction};
+
+/// 默认等待时间（秒）
+const SLEEP_DEFAULT_SECS: u64 = 5;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct SleepParam {
+    /// 等待秒数，默认 5；参数无法解析时同样等待 5 秒
+    #[serde(default = "default_sleep_time")]
+    sleep_time: u64,
+}
+
+fn default_sleep_time() -> u64 {
+    SLEEP_DEFAULT_SECS
+}
+
+/// 等待期间发送倒计时事件，停止任务时提前结束并返回失败
+pub struct SleepAction;
+
+impl MxuAction for SleepAction {
+    const NAME: &'static str = "MXU_SLEEP_ACTION";
+    const TAG: &'static str = "MXU_SLEEP";
+    type Param = SleepParam;
+
+    fn run(ctx: &ActionContext, param: SleepParam) -> Result<(), String> {
+        info!("[MXU_SLEEP] Sleeping for {} seconds...", param.sleep_time);
+
+        // 执行睡眠（可被停止任务打断）
+        if !ctx.wait(Self::TAG, Duration::from_secs(param.sleep_time)) {
+            return Err("Sleep cancelled by stop request".to_string());
+        }
+
+        info!("[MXU_SLEEP] Sleep completed");
+        Ok(())
+    }
+
+    /// 与旧版一致：参数无法解析时按默认时间等待
+    fn fallback_param() -> Option<SleepParam> {
+        Some(SleepParam {
+            sleep_time: SLEEP_DEFAULT_SECS,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse_action_param;
+    use super::*;
+
+    #[test]
+    fn falls_back_to_default_on_bad_param() {
+        let param = parse_action_param::<SleepAction>(r#"{"sleep_time":2}"#).unwrap();
+        assert_eq!(param.sleep_time, 2);
+        for bad in [
+            "{sleep_time:2",
+            r#"{"sleep_time":"2"}"#,
+            r#"{"sleep_time":-1}"#,
+        ] {
+            let param = parse_action_param::<SleepAction>(bad).unwrap();
+            assert_eq!(param.sleep_time, SLEEP_DEFAULT_SECS, "{}", bad);
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! MXU_LOG：向任务日志写入一条消息，可附带当前画面截图
+
+use log::warn;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::screenshot::capture_controller_image;
+use super::template::{Escape, Template};
+use super::{non_empty, ActionContext, MxuAction};
+use crate::agent_log::{AgentLogLevel, AgentLogRecord};
+use crate::commands::types::RunLogEntry;
+use crate::commands::utils::get_app_data_dir;
+
+/// 截图保存位置（相对数据目录）
+const LOG_SCREENSHOT_FILENAME: &str = "screenshots/log/{date}/{instance}_{node}_{time}.png";
+
+#[derive(Deserialize, JsonSchema)]
+pub struct LogParam {
+    /// `trace` / `debug` / `info`（默认）/ `warn` / `error`，
+    /// trace 与 debug 只写入后端日志，不在任务日志中显示
+    #[serde(default)]
+    level: Option<String>,
+    /// 日志内容，可使用占位符（见 [`super::template`]）
+    message: String,
+    /// 是否附带当前画面截图（识别时的画面），保存在数据目录的 `screenshots/log`
+    #[serde(default)]
+    screenshot: bool,
+}
+
+/// 保存当前画面，返回图片路径
+fn save_screenshot(ctx: &ActionContext, template: Template) -> Result<String, String> {
+    let encoded = capture_controller_image(ctx.context, false)?;
+    let time = template.now().format("%H-%M-%S").to_string();
+    let filename = template
+        .with("time", time)
+        .render(LOG_SCREENSHOT_FILENAME, Escape::FileName);
+    let path = get_app_data_dir()?.join(filename);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+    }
+    std::fs::write(&path, encoded).map_err(|e| format!("Failed to save {:?}: {}", path, e))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// 将消息写入后端日志、前端任务日志（`maa-action-log` 事件）以及本轮运行历史
+pub struct LogAction;
+
+impl MxuAction for LogAction {
+    const NAME: &'static str = "MXU_LOG_ACTION";
+    const TAG: &'static str = "MXU_LOG";
+    type Param = LogParam;
+
+    fn run(ctx: &ActionContext, param: LogParam) -> Result<(), String> {
+        let level = match non_empty(param.level) {
+            Some(level) => {
+                AgentLogLevel::parse(&level).ok_or_else(|| format!("Invalid level '{}'", level))?
+            }
+            None => AgentLogLevel::Info,
+        };
+        let template = Template::new(ctx);
+        let message = template.render(&param.message, Escape::Raw);
+
+        // 截图失败不影响日志本身
+        let image = if param.screenshot {
+            save_screenshot(ctx, template)
+                .inspect_err(|e| warn!("[MXU_LOG] Screenshot failed: {}", e))
+                .ok()
+        } else {
+            None
+        };
+
+        let record = AgentLogRecord {
+            level,
+            message,
+            node: non_empty(Some(ctx.node.clone())),
+            fields: serde_json::Map::new(),
+            image,
+        };
+        log::log!(level.as_log_level(), "[MXU_LOG] {}", record.to_log_text());
+
+        let instance_id = crate::run_context::task_context(ctx.task_id).instance_id;
+        if !instance_id.is_empty() {
+            crate::maa_ffi::emit_action_log(&instance_id, ctx.task_id, &record);
+        }
+        crate::run_context::record_run_log(
+            ctx.task_id,
+            RunLogEntry {
+                time: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                level: level.as_str().to_string(),
+                node: ctx.node.clone(),
+                message: record.message,
+                image: record.image,
+            },
+        );
+        Ok(())
+    }
+}
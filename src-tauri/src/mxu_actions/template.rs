@@ -0,0 +1,362 @@
+//! 动作参数模板
+//!
+//! MXU 动作的字符串参数可使用 `{name}` / `{name:arg}` 形式的占位符：
+//!
+//! - `{instance}`、`{task}`、`{task_id}`、`{node}`、`{last_error}`：当前任务上下文
+//! - `{date}` / `{date:<strftime>}`：当前日期，默认 `%Y-%m-%d`
+//! - `{time}` / `{time:<strftime>}`：当前时间，默认 `%Y-%m-%d %H:%M:%S`
+//! - `{timestamp}`：毫秒时间戳
+//! - `{env:VAR}`：环境变量，未设置时为空
+//! - `{var:name}`：持久化变量，优先取实例变量，其次取全局变量，不存在时为空
+//! - `{exe_dir}`、`{data_dir}`：MXU 程序目录与数据目录
+//!
+//! 替换值按目标位置转义（见 [`Escape`]），无法识别的占位符原样保留，因此 JSON 文本中的花括号不受影响。
+
+use chrono::{DateTime, Local};
+use serde_json::Value;
+
+use super::ActionContext;
+use crate::maa_ffi::MaaId;
+
+/// 替换值的转义方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Escape {
+    /// 原样替换
+    Raw,
+    /// URL 编码
+    Url,
+    /// 按 shell 规则加引号，替换后仍为单个参数
+    Shell,
+    /// 转义为 JSON 字符串内容（不含两侧引号）
+    Json,
+    /// 替换不能用于文件名的字符
+    FileName,
+}
+
+impl Escape {
+    pub fn apply(self, value: &str) -> String {
+        match self {
+            Self::Raw => value.to_string(),
+            Self::Url => urlencoding::encode(value).into_owned(),
+            Self::Shell => shell_words::quote(value).into_owned(),
+            Self::Json => {
+                let quoted = Value::String(value.to_string()).to_string();
+                quoted[1..quoted.len() - 1].to_string()
+            }
+            Self::FileName => sanitize_file_component(value),
+        }
+    }
+}
+
+/// 将值中不能用于文件名的字符替换为 `_`
+fn sanitize_file_component(value: &str) -> String {
+    let sanitized: String = value
+        .chars()
+        .map(|c| match c {
+            '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let trimmed = sanitized.trim().trim_matches('.');
+    if trimmed.is_empty() {
+        "_".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// 模板渲染上下文，创建时固定当前时间，同一参数中的多个时间占位符保持一致
+pub struct Template {
+    task_id: MaaId,
+    instance: String,
+    task: String,
+    node: String,
+    last_error: String,
+    now: DateTime<Local>,
+    /// 动作自定义的占位符，优先于内置占位符
+    overrides: Vec<(&'static str, String)>,
+}
+
+impl Template {
+    pub fn new(ctx: &ActionContext) -> Self {
+        let run = crate::run_context::task_context(ctx.task_id);
+        Self {
+            task_id: ctx.task_id,
+            instance: run.instance_id,
+            task: run.entry,
+            node: ctx.node.clone(),
+            last_error: run.last_error,
+            now: Local::now(),
+            overrides: Vec::new(),
+        }
+    }
+
+    /// 渲染使用的当前时间
+    pub fn now(&self) -> DateTime<Local> {
+        self.now
+    }
+
+    /// 添加或覆盖无参数的占位符
+    pub fn with(mut self, name: &'static str, value: String) -> Self {
+        self.overrides.push((name, value));
+        self
+    }
+
+    /// 当前时间按 strftime 格式化，格式无效时返回 None
+    fn format_now(&self, fmt: &str) -> Option<String> {
+        use std::fmt::Write;
+        let mut output = String::new();
+        write!(output, "{}", self.now.format(fmt)).ok()?;
+        Some(output)
+    }
+
+    /// 持久化变量的文本形式，字符串不带引号
+    fn variable(&self, name: &str) -> Option<String> {
+        use crate::commands::variables::get_value;
+
+        let instance = (!self.instance.is_empty()).then_some(self.instance.as_str());
+        let value = instance
+            .and_then(|id| get_value(Some(id), name).ok().flatten())
+            .or_else(|| get_value(None, name).ok().flatten())?;
+        Some(match value {
+            Value::String(s) => s,
+            Value::Null => String::new(),
+            other => other.to_string(),
+        })
+    }
+
+    /// 解析单个占位符，无法识别时返回 None
+    fn resolve(&self, name: &str, arg: Option<&str>) -> Option<String> {
+        if arg.is_none() {
+            if let Some((_, value)) = self.overrides.iter().rev().find(|(n, _)| *n == name) {
+                return Some(value.clone());
+            }
+        }
+        match (name, arg) {
+            ("instance", None) => Some(self.instance.clone()),
+            ("task", None) => Some(self.task.clone()),
+            ("task_id", None) => Some(self.task_id.to_string()),
+            ("node", None) => Some(self.node.clone()),
+            ("last_error", None) => Some(self.last_error.clone()),
+            ("date", fmt) => self.format_now(fmt.unwrap_or("%Y-%m-%d")),
+            ("time", fmt) => self.format_now(fmt.unwrap_or("%Y-%m-%d %H:%M:%S")),
+            ("timestamp", None) => Some(self.now.timestamp_millis().to_string()),
+            ("env", Some(var)) => Some(std::env::var(var).unwrap_or_default()),
+            ("var", Some(var)) => Some(self.variable(var).unwrap_or_default()),
+            ("exe_dir", None) => crate::commands::utils::get_exe_directory()
+                .ok()
+                .map(|p| p.to_string_lossy().into_owned()),
+            ("data_dir", None) => crate::commands::utils::get_app_data_dir()
+                .ok()
+                .map(|p| p.to_string_lossy().into_owned()),
+            _ => None,
+        }
+    }
+
+    /// 替换字符串中的占位符，替换值按 `escape` 转义
+    pub fn render(&self, template: &str, escape: Escape) -> String {
+        let mut output = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            output.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let replaced = after.find('}').and_then(|end| {
+                let inner = &after[..end];
+                let (name, arg) = match inner.split_once(':') {
+                    Some((name, arg)) => (name, Some(arg)),
+                    None => (inner, None),
+                };
+                let valid_name =
+                    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+                valid_name
+                    .then(|| self.resolve(name, arg))
+                    .flatten()
+                    .map(|value| (escape.apply(&value), end))
+            });
+            match replaced {
+                Some((value, end)) => {
+                    output.push_str(&value);
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    output.push('{');
+                    rest = after;
+                }
+            }
+        }
+        output.push_str(rest);
+        output
+    }
+
+    /// 递归替换 JSON 中所有字符串值（包括键）里的占位符
+    pub fn render_json(&self, value: &Value) -> Value {
+        match value {
+            Value::String(s) => Value::String(self.render(s, Escape::Raw)),
+            Value::Array(items) => {
+                Value::Array(items.iter().map(|v| self.render_json(v)).collect())
+            }
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(k, v)| (self.render(k, Escape::Raw), self.render_json(v)))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn template() -> Template {
+        Template {
+            task_id: 42,
+            instance: "inst 1".to_string(),
+            task: "Daily".to_string(),
+            node: "StartNode".to_string(),
+            last_error: String::new(),
+            now: Local.with_ymd_and_hms(2026, 10, 18, 9, 5, 7).unwrap(),
+            overrides: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn context_and_time_placeholders() {
+        let t = template();
+        assert_eq!(
+            t.render("{instance}/{task}/{task_id}/{node}", Escape::Raw),
+            "inst 1/Daily/42/StartNode"
+        );
+        assert_eq!(t.render("{date}", Escape::Raw), "2026-10-18");
+        assert_eq!(t.render("{time}", Escape::Raw), "2026-10-18 09:05:07");
+        assert_eq!(
+            t.render("{timestamp}", Escape::Raw),
+            t.now().timestamp_millis().to_string()
+        );
+    }
+
+    #[test]
+    fn argument_may_contain_colons() {
+        let t = template();
+        assert_eq!(t.render("at {time:%H:%M}", Escape::Raw), "at 09:05");
+        assert_eq!(
+            t.render("{date:%Y%m%d}-{time:%H:%M:%S}", Escape::Raw),
+            "20261018-09:05:07"
+        );
+    }
+
+    #[test]
+    fn invalid_time_format_is_kept() {
+        let t = template();
+        assert_eq!(t.render("{time:%Q}", Escape::Raw), "{time:%Q}");
+    }
+
+    #[test]
+    fn unknown_placeholders_are_kept() {
+        let t = template();
+        assert_eq!(
+            t.render("{unknown} {instance:x}", Escape::Raw),
+            "{unknown} {instance:x}"
+        );
+        assert_eq!(t.render("{} {bad name} {", Escape::Raw), "{} {bad name} {");
+        assert_eq!(t.render("{{instance}}", Escape::Raw), "{inst 1}");
+        assert_eq!(t.render("{env:MXU_TEMPLATE_TEST_UNSET}|", Escape::Raw), "|");
+    }
+
+    #[test]
+    fn json_braces_pass_through() {
+        let t = template();
+        assert_eq!(
+            t.render(
+                r#"{"name": "{instance}", "nested": {"n": 1}}"#,
+                Escape::Json
+            ),
+            r#"{"name": "inst 1", "nested": {"n": 1}}"#
+        );
+    }
+
+    #[test]
+    fn overrides_take_precedence_without_argument() {
+        let t = template()
+            .with("task", "first".to_string())
+            .with("task", "second".to_string())
+            .with("file", "a.png".to_string());
+        assert_eq!(t.render("{task} {file}", Escape::Raw), "second a.png");
+        assert_eq!(t.render("{file:x}", Escape::Raw), "{file:x}");
+    }
+
+    #[test]
+    fn escape_only_applies_to_values() {
+        let t = template().with("value", "a b&c".to_string());
+        assert_eq!(
+            t.render("https://x/?q={value}&p=1", Escape::Url),
+            "https://x/?q=a%20b%26c&p=1"
+        );
+        assert_eq!(t.render("echo {instance}", Escape::Shell), "echo 'inst 1'");
+    }
+
+    #[test]
+    fn url_escape() {
+        assert_eq!(Escape::Url.apply("a b/c?d=ü"), "a%20b%2Fc%3Fd%3D%C3%BC");
+        assert_eq!(Escape::Url.apply("safe-._~"), "safe-._~");
+    }
+
+    #[test]
+    fn shell_escape() {
+        assert_eq!(Escape::Shell.apply("plain"), "plain");
+        assert_eq!(Escape::Shell.apply("two words"), "'two words'");
+        assert_eq!(Escape::Shell.apply("it's"), r"'it'\''s'");
+        assert_eq!(Escape::Shell.apply("$HOME;rm"), "'$HOME;rm'");
+        let value = "a 'b' \"c\" $d";
+        assert_eq!(
+            shell_words::split(&Escape::Shell.apply(value)).unwrap(),
+            vec![value]
+        );
+    }
+
+    #[test]
+    fn json_escape() {
+        assert_eq!(Escape::Json.apply("plain"), "plain");
+        assert_eq!(Escape::Json.apply("a\"b\\c\nd\te"), r#"a\"b\\c\nd\te"#);
+        let rendered = format!("\"{}\"", Escape::Json.apply("x\"\u{1}y"));
+        assert_eq!(
+            serde_json::from_str::<String>(&rendered).unwrap(),
+            "x\"\u{1}y"
+        );
+    }
+
+    #[test]
+    fn file_name_escape() {
+        assert_eq!(
+            Escape::FileName.apply("a/b\\c:d*e?f\"g<h>i|j"),
+            "a_b_c_d_e_f_g_h_i_j"
+        );
+        assert_eq!(Escape::FileName.apply("line\nbreak"), "line_break");
+        assert_eq!(Escape::FileName.apply(" .name. "), "name");
+        assert_eq!(Escape::FileName.apply("..."), "_");
+        assert_eq!(Escape::FileName.apply(""), "_");
+        assert_eq!(Escape::Raw.apply("a/b"), "a/b");
+    }
+
+    #[test]
+    fn render_json_renders_keys_and_nested_strings() {
+        let t = template();
+        let value = json!({
+            "{node}": ["{instance}", 1, {"t": "{time:%H:%M}"}],
+            "keep": "{unknown}",
+            "n": null
+        });
+        assert_eq!(
+            t.render_json(&value),
+            json!({
+                "StartNode": ["inst 1", 1, {"t": "09:05"}],
+                "keep": "{unknown}",
+                "n": null
+            })
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! MXU_VARIABLE：读写持久化变量的动作，以及比较变量值的识别
+
+use log::info;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::{non_empty, ActionContext, MxuAction, MxuRecognition};
+use crate::commands::variables::{add_values, compare_value, get_value, update_value};
+use crate::maa_ffi::{MaaId, MaaRect};
+
+/// 变量作用域
+#[derive(Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+enum VariableScope {
+    /// 按实例区分
+    #[default]
+    Instance,
+    /// 所有实例共享
+    Global,
+}
+
+/// 解析变量作用域：全局变量返回 None，实例变量返回当前任务所属实例
+fn resolve_scope(scope: &VariableScope, task_id: MaaId) -> Result<Option<String>, String> {
+    match scope {
+        VariableScope::Global => Ok(None),
+        VariableScope::Instance => {
+            let instance_id = crate::run_context::task_context(task_id).instance_id;
+            if instance_id.is_empty() {
+                Err("Cannot resolve instance of current task".to_string())
+            } else {
+                Ok(Some(instance_id))
+            }
+        }
+    }
+}
+
+/// 变量操作
+#[derive(Deserialize, JsonSchema, Default, Debug)]
+#[serde(rename_all = "lowercase")]
+enum VariableOp {
+    /// 设为 `value`
+    #[default]
+    Set,
+    /// 加上 `by`
+    Incr,
+    /// 减去 `by`
+    Decr,
+    /// 恢复为 `reset_value`
+    Reset,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct VariableParam {
+    /// 变量名
+    name: String,
+    /// 操作，默认 `set`
+    #[serde(default)]
+    op: VariableOp,
+    /// `set` 的值
+    #[serde(default)]
+    value: Option<serde_json::Value>,
+    /// `incr` / `decr` 的步长，默认 1
+    #[serde(default)]
+    by: Option<serde_json::Number>,
+    /// 作用域，默认 `instance`
+    #[serde(default)]
+    scope: VariableScope,
+    /// 每日重置时间（0-23 点）
+    #[serde(default)]
+    daily_reset_hour: Option<u8>,
+    /// 重置后的值，默认 0
+    #[serde(default)]
+    reset_value: Option<serde_json::Value>,
+}
+
+/// 对变量值的修改操作
+type VariableModifier =
+    Box<dyn FnOnce(&mut crate::commands::types::PersistentVariable) -> Result<(), String>>;
+
+/// 修改持久化变量
+pub struct VariableAction;
+
+impl MxuAction for VariableAction {
+    const NAME: &'static str = "MXU_VARIABLE_ACTION";
+    const TAG: &'static str = "MXU_VARIABLE";
+    type Param = VariableParam;
+
+    fn run(ctx: &ActionContext, param: VariableParam) -> Result<(), String> {
+        let name = non_empty(Some(param.name)).ok_or("Empty 'name' parameter")?;
+        let instance_id = resolve_scope(&param.scope, ctx.task_id)?;
+
+        let modify: VariableModifier = match param.op {
+            VariableOp::Set => {
+                let value = param
+                    .value
+                    .ok_or("Missing 'value' parameter for op 'set'")?;
+                Box::new(move |var| {
+                    var.value = value;
+                    Ok(())
+                })
+            }
+            VariableOp::Incr | VariableOp::Decr => {
+                let by = param.by.unwrap_or_else(|| 1.into());
+                let by = if matches!(param.op, VariableOp::Decr) {
+                    match (by.as_i64(), by.as_f64()) {
+                        (Some(i), _) => serde_json::Value::from(-i),
+                        (None, Some(f)) => serde_json::Value::from(-f),
+                        _ => return Err(format!("Cannot negate 'by' value {}", by)),
+                    }
+                } else {
+                    serde_json::Value::Number(by)
+                };
+                Box::new(move |var| {
+                    var.value = add_values(&var.value, &by)?;
+                    Ok(())
+                })
+            }
+            VariableOp::Reset => Box::new(|var| {
+                var.value = var.reset_value.clone();
+                Ok(())
+            }),
+        };
+
+        let value = update_value(
+            instance_id.as_deref(),
+            &name,
+            param.daily_reset_hour,
+            param.reset_value,
+            modify,
+        )?;
+        info!(
+            "[MXU_VARIABLE] {:?} {} (instance={:?}) -> {}",
+            param.op, name, instance_id, value
+        );
+        Ok(())
+    }
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct VariableRecognitionParam {
+    /// 变量名
+    name: String,
+    /// 作用域，默认 `instance`
+    #[serde(default)]
+    scope: VariableScope,
+    /// `==`（默认）/ `!=` / `<` / `<=` / `>` / `>=` / `exists` / `not_exists`
+    #[serde(default = "default_compare_op")]
+    op: String,
+    /// 比较的值
+    #[serde(default)]
+    value: serde_json::Value,
+}
+
+fn default_compare_op() -> String {
+    "==".to_string()
+}
+
+/// 比较变量值，满足条件时命中；box 为识别区域（roi），detail 为 `{"name": ..., "value": ...}`
+pub struct VariableRecognition;
+
+impl MxuRecognition for VariableRecognition {
+    const NAME: &'static str = "MXU_VARIABLE_RECOGNITION";
+    const TAG: &'static str = "MXU_VARIABLE";
+    type Param = VariableRecognitionParam;
+
+    fn recognize(
+        ctx: &ActionContext,
+        roi: MaaRect,
+        param: VariableRecognitionParam,
+    ) -> Result<Option<(MaaRect, serde_json::Value)>, String> {
+        let name = non_empty(Some(param.name)).ok_or("Empty 'name' parameter")?;
+        let instance_id = resolve_scope(&param.scope, ctx.task_id)?;
+        let actual = get_value(instance_id.as_deref(), &name)?;
+        let hit = compare_value(actual.as_ref(), &param.op, &param.value)?;
+        info!(
+            "[MXU_VARIABLE] {} ({:?}) {} {} -> {}",
+            name, actual, param.op, param.value, hit
+        );
+        Ok(hit.then(|| (roi, serde_json::json!({ "name": name, "value": actual }))))
+    }
+}
@@ -0,0 +1,180 @@
+//! MXU_WAIT_PROCESS / MXU_WAIT_PORT：轮询等待进程出现或退出、TCP 端口可连接或关闭
+
+use std::time::Duration;
+
+use log::info;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::launch::PortTarget;
+use super::{non_empty, ActionContext, MxuAction, PollOutcome};
+use crate::process_util::{find_processes, is_port_open, ProcessMatcher};
+
+/// 默认检测间隔（秒）
+const WAIT_DEFAULT_INTERVAL: f64 = 1.0;
+/// 最小检测间隔（秒）
+const WAIT_MIN_INTERVAL: f64 = 0.1;
+
+/// 超时（0 或缺省表示不限）与检测间隔
+fn wait_timing(timeout: Option<u64>, interval: Option<f64>) -> (Option<Duration>, Duration) {
+    (
+        timeout.filter(|t| *t > 0).map(Duration::from_secs),
+        Duration::from_secs_f64(
+            interval
+                .unwrap_or(WAIT_DEFAULT_INTERVAL)
+                .max(WAIT_MIN_INTERVAL),
+        ),
+    )
+}
+
+/// 将轮询结果转换为动作结果
+fn finish(
+    tag: &str,
+    outcome: PollOutcome,
+    what: &str,
+    timeout: Option<Duration>,
+) -> Result<(), String> {
+    match outcome {
+        PollOutcome::Satisfied => {
+            info!("[{}] {}", tag, what);
+            Ok(())
+        }
+        PollOutcome::TimedOut => Err(format!(
+            "Timed out after {}s waiting until {}",
+            timeout.unwrap_or_default().as_secs(),
+            what
+        )),
+        PollOutcome::Cancelled => Err("Wait cancelled by stop request".to_string()),
+    }
+}
+
+/// 等待的进程状态
+#[derive(Deserialize, JsonSchema, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum ProcessState {
+    /// 出现匹配的进程
+    #[default]
+    Appear,
+    /// 所有匹配的进程都已退出
+    Disappear,
+}
+
+/// `process_name` 与 `name_regex` 至少提供一个，同时提供时需同时满足
+#[derive(Deserialize, JsonSchema)]
+pub struct WaitProcessParam {
+    /// 精确进程名（忽略大小写，可省略 .exe）
+    #[serde(default)]
+    process_name: Option<String>,
+    /// 进程名正则
+    #[serde(default)]
+    name_regex: Option<String>,
+    /// `appear`（默认）/ `disappear`
+    #[serde(default)]
+    state: ProcessState,
+    /// 超时（秒），超时后返回失败，0 或缺省表示不限
+    #[serde(default)]
+    timeout: Option<u64>,
+    /// 检测间隔（秒），默认 1
+    #[serde(default)]
+    interval: Option<f64>,
+}
+
+/// 等待进程出现或退出，可被停止任务打断
+pub struct WaitProcessAction;
+
+impl MxuAction for WaitProcessAction {
+    const NAME: &'static str = "MXU_WAIT_PROCESS_ACTION";
+    const TAG: &'static str = "MXU_WAIT_PROCESS";
+    type Param = WaitProcessParam;
+
+    fn run(ctx: &ActionContext, param: WaitProcessParam) -> Result<(), String> {
+        let mut matcher = ProcessMatcher {
+            name: non_empty(param.process_name),
+            ..Default::default()
+        };
+        if let Some(pattern) = non_empty(param.name_regex) {
+            let re = regex::Regex::new(&pattern)
+                .map_err(|e| format!("Invalid name_regex '{}': {}", pattern, e))?;
+            matcher.name_regex = Some(re);
+        }
+        if matcher.is_empty() {
+            return Err("Missing 'process_name' or 'name_regex' parameter".to_string());
+        }
+
+        let (timeout, interval) = wait_timing(param.timeout, param.interval);
+        let state = param.state;
+        info!(
+            "[MXU_WAIT_PROCESS] Waiting for process {:?} to {:?}, timeout={:?}",
+            matcher, state, timeout
+        );
+
+        let outcome = ctx.poll_until(Self::TAG, timeout, interval, || {
+            let running = !find_processes(&matcher).is_empty();
+            Ok(running == (state == ProcessState::Appear))
+        })?;
+        let what = match state {
+            ProcessState::Appear => "process appeared",
+            ProcessState::Disappear => "process exited",
+        };
+        finish(Self::TAG, outcome, what, timeout)
+    }
+}
+
+/// 等待的端口状态
+#[derive(Deserialize, JsonSchema, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum PortState {
+    /// 可连接
+    #[default]
+    Open,
+    /// 无法连接
+    Closed,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct WaitPortParam {
+    /// 端口，纯数字表示本机端口，或 `host:port`
+    address: PortTarget,
+    /// `open`（默认）/ `closed`
+    #[serde(default)]
+    state: PortState,
+    /// 超时（秒），超时后返回失败，0 或缺省表示不限
+    #[serde(default)]
+    timeout: Option<u64>,
+    /// 检测间隔（秒），默认 1
+    #[serde(default)]
+    interval: Option<f64>,
+}
+
+/// 等待 TCP 端口可连接或关闭，可被停止任务打断
+pub struct WaitPortAction;
+
+impl MxuAction for WaitPortAction {
+    const NAME: &'static str = "MXU_WAIT_PORT_ACTION";
+    const TAG: &'static str = "MXU_WAIT_PORT";
+    type Param = WaitPortParam;
+
+    fn run(ctx: &ActionContext, param: WaitPortParam) -> Result<(), String> {
+        let address = match param.address {
+            PortTarget::Port(port) => port.to_string(),
+            PortTarget::Address(address) => {
+                non_empty(Some(address)).ok_or("Empty 'address' parameter")?
+            }
+        };
+        let (timeout, interval) = wait_timing(param.timeout, param.interval);
+        let state = param.state;
+        info!(
+            "[MXU_WAIT_PORT] Waiting for {} to be {:?}, timeout={:?}",
+            address, state, timeout
+        );
+
+        let outcome = ctx.poll_until(Self::TAG, timeout, interval, || {
+            Ok(is_port_open(&address)? == (state == PortState::Open))
+        })?;
+        let what = match state {
+            PortState::Open => format!("{} is open", address),
+            PortState::Closed => format!("{} is closed", address),
+        };
+        finish(Self::TAG, outcome, &what, timeout)
+    }
+}
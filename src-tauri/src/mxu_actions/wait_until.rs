@@ -0,0 +1,55 @@
+//! MXU_WAITUNTIL：等待到最近的目标时间点
+
+use std::time::{Duration, Instant};
+
+use log::info;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::{ActionContext, MxuAction};
+use crate::wait_target::{collect_target_specs, next_wait_target, WaitTimezone};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct WaitUntilParam {
+    /// 目标时间，字符串或字符串数组，支持的格式见 `wait_target` 模块
+    target_time: serde_json::Value,
+    /// 时区，如 `UTC` / `Asia/Shanghai` / `+08:00`，默认本地时区
+    #[serde(default)]
+    timezone: String,
+}
+
+/// 等待期间发送倒计时事件，暂停不推迟目标时刻；停止任务时提前结束并返回失败；参数无效时节点失败
+pub struct WaitUntilAction;
+
+impl MxuAction for WaitUntilAction {
+    const NAME: &'static str = "MXU_WAITUNTIL_ACTION";
+    const TAG: &'static str = "MXU_WAITUNTIL";
+    type Param = WaitUntilParam;
+
+    fn run(ctx: &ActionContext, param: WaitUntilParam) -> Result<(), String> {
+        let specs = collect_target_specs(&param.target_time)?;
+        let tz = WaitTimezone::parse(&param.timezone)?;
+
+        // 计算最近的目标时刻
+        let now = chrono::Utc::now();
+        let (target, matched) = next_wait_target(&specs, tz, now)?;
+
+        let wait_secs = (target - now).num_seconds().max(0) as u64;
+        info!(
+            "[MXU_WAITUNTIL] Waiting until {} (target '{}', {:?}), {}s from now",
+            target.with_timezone(&chrono::Local),
+            matched,
+            tz,
+            wait_secs
+        );
+
+        // 目标是绝对时刻，暂停不推迟截止时间
+        let deadline = Instant::now() + Duration::from_secs(wait_secs);
+        if !ctx.wait_until(Self::TAG, deadline) {
+            return Err("Wait cancelled by stop request".to_string());
+        }
+
+        info!("[MXU_WAITUNTIL] Wait completed, target time reached");
+        Ok(())
+    }
+}
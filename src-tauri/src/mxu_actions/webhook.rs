@@ -0,0 +1,278 @@
+//! MXU_WEBHOOK：发送 HTTP 请求，支持模板变量、重试与代理
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use log::{info, warn};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::template::{Escape, Template};
+use super::{non_empty, ActionContext, MxuAction};
+
+/// 默认请求超时（秒）
+const WEBHOOK_DEFAULT_TIMEOUT_SECS: u64 = 10;
+/// 默认首次重试间隔（毫秒），之后每次翻倍
+const WEBHOOK_DEFAULT_RETRY_DELAY_MS: u64 = 1000;
+/// 重试间隔上限（毫秒）
+const WEBHOOK_MAX_RETRY_DELAY_MS: u64 = 60_000;
+
+/// url、headers、body 中可使用占位符（见 [`super::template`]），替换值按所在位置转义
+#[derive(Deserialize, JsonSchema)]
+pub struct WebhookParam {
+    /// 请求地址
+    url: String,
+    /// HTTP 方法，默认 GET
+    #[serde(default)]
+    method: Option<String>,
+    /// 请求头
+    #[serde(default)]
+    headers: Option<BTreeMap<String, serde_json::Value>>,
+    /// 请求体，可为字符串或 JSON 对象
+    #[serde(default)]
+    body: Option<serde_json::Value>,
+    /// 请求体编码：`json`（默认）/ `form` / `text`
+    #[serde(default)]
+    body_type: Option<String>,
+    /// 成功状态码策略：`2xx`（默认）、`any`、`200,204`、`200-399` 或状态码数组
+    #[serde(default)]
+    success_status: Option<serde_json::Value>,
+    /// 超时秒数，默认 10
+    #[serde(default)]
+    timeout: Option<u64>,
+    /// 失败重试次数
+    #[serde(default)]
+    retries: u64,
+    /// 首次重试间隔（毫秒），默认 1000，之后逐次翻倍，最长 60000
+    #[serde(default)]
+    retry_delay: Option<u64>,
+    /// 代理地址，如 `http://127.0.0.1:7890`
+    #[serde(default)]
+    proxy: Option<String>,
+}
+
+/// 请求体编码方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WebhookBodyType {
+    Json,
+    Form,
+    Text,
+}
+
+/// 判断响应状态码是否视为成功
+///
+/// 支持 `"2xx"`（默认）、`"any"`、逗号分隔的状态码或区间（如 `"200,204"`、`"200-399"`），
+/// 以及状态码数组（如 `[200, 201]`）
+fn webhook_status_matches(policy: Option<&serde_json::Value>, status: u16) -> Result<bool, String> {
+    let policy = match policy {
+        None | Some(serde_json::Value::Null) => return Ok((200..300).contains(&status)),
+        Some(serde_json::Value::Array(codes)) => {
+            return Ok(codes.iter().any(|c| c.as_u64() == Some(u64::from(status))))
+        }
+        Some(serde_json::Value::Number(code)) => {
+            return Ok(code.as_u64() == Some(u64::from(status)))
+        }
+        Some(serde_json::Value::String(s)) => s.trim().to_ascii_lowercase(),
+        Some(other) => return Err(format!("Invalid success_status: {}", other)),
+    };
+
+    if policy.is_empty() || policy == "2xx" {
+        return Ok((200..300).contains(&status));
+    }
+    if policy == "any" {
+        return Ok(true);
+    }
+
+    for part in policy.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let matched = if let Some(class) = part.strip_suffix("xx") {
+            let class: u16 = class
+                .parse()
+                .map_err(|_| format!("Invalid success_status: {}", part))?;
+            status / 100 == class
+        } else if let Some((lo, hi)) = part.split_once('-') {
+            let lo: u16 = lo
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid success_status: {}", part))?;
+            let hi: u16 = hi
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid success_status: {}", part))?;
+            (lo..=hi).contains(&status)
+        } else {
+            let code: u16 = part
+                .parse()
+                .map_err(|_| format!("Invalid success_status: {}", part))?;
+            code == status
+        };
+        if matched {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// 发送请求，响应状态码不满足 `success_status` 时按 `retries` 重试
+pub struct WebhookAction;
+
+impl MxuAction for WebhookAction {
+    const NAME: &'static str = "MXU_WEBHOOK_ACTION";
+    const TAG: &'static str = "MXU_WEBHOOK";
+    type Param = WebhookParam;
+
+    fn run(ctx: &ActionContext, param: WebhookParam) -> Result<(), String> {
+        let url_template = non_empty(Some(param.url)).ok_or("Empty 'url' parameter")?;
+
+        let template = Template::new(ctx);
+        let url = template.render(&url_template, Escape::Url);
+
+        let method_str = param
+            .method
+            .as_deref()
+            .unwrap_or("GET")
+            .trim()
+            .to_ascii_uppercase();
+        let method = reqwest::Method::from_bytes(method_str.as_bytes())
+            .map_err(|_| format!("Invalid HTTP method: {}", method_str))?;
+
+        let body_type = match param
+            .body_type
+            .map(|s| s.trim().to_ascii_lowercase())
+            .as_deref()
+        {
+            None | Some("") | Some("json") => WebhookBodyType::Json,
+            Some("form") => WebhookBodyType::Form,
+            Some("text") => WebhookBodyType::Text,
+            Some(other) => return Err(format!("Invalid body_type: {}", other)),
+        };
+
+        // 渲染请求体，返回 (内容, 默认 Content-Type)
+        let body: Option<(String, &str)> = match &param.body {
+            None | Some(serde_json::Value::Null) => None,
+            Some(serde_json::Value::String(s)) => Some(match body_type {
+                WebhookBodyType::Json => (template.render(s, Escape::Json), "application/json"),
+                WebhookBodyType::Form => (
+                    template.render(s, Escape::Url),
+                    "application/x-www-form-urlencoded",
+                ),
+                WebhookBodyType::Text => {
+                    (template.render(s, Escape::Raw), "text/plain; charset=utf-8")
+                }
+            }),
+            Some(value) => {
+                let rendered = template.render_json(value);
+                Some(match body_type {
+                    WebhookBodyType::Form => {
+                        let map = rendered.as_object().ok_or("Form body must be an object")?;
+                        let encoded = map
+                            .iter()
+                            .map(|(k, v)| {
+                                let v = match v {
+                                    serde_json::Value::String(s) => s.clone(),
+                                    other => other.to_string(),
+                                };
+                                format!("{}={}", urlencoding::encode(k), urlencoding::encode(&v))
+                            })
+                            .collect::<Vec<_>>()
+                            .join("&");
+                        (encoded, "application/x-www-form-urlencoded")
+                    }
+                    _ => (rendered.to_string(), "application/json"),
+                })
+            }
+        };
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (name, value) in param.headers.unwrap_or_default() {
+            let value = match value {
+                serde_json::Value::String(s) => s,
+                other => other.to_string(),
+            };
+            let value = template.render(&value, Escape::Raw);
+            let (Ok(header_name), Ok(header_value)) = (
+                reqwest::header::HeaderName::from_bytes(name.as_bytes()),
+                reqwest::header::HeaderValue::from_str(&value),
+            ) else {
+                return Err(format!("Invalid header: {}", name));
+            };
+            headers.insert(header_name, header_value);
+        }
+        if let Some((_, content_type)) = &body {
+            if !headers.contains_key(reqwest::header::CONTENT_TYPE) {
+                headers.insert(
+                    reqwest::header::CONTENT_TYPE,
+                    reqwest::header::HeaderValue::from_static(content_type),
+                );
+            }
+        }
+
+        let timeout_secs = param
+            .timeout
+            .filter(|t| *t > 0)
+            .unwrap_or(WEBHOOK_DEFAULT_TIMEOUT_SECS);
+        let retries = param.retries;
+        let mut retry_delay_ms = param
+            .retry_delay
+            .unwrap_or(WEBHOOK_DEFAULT_RETRY_DELAY_MS)
+            .min(WEBHOOK_MAX_RETRY_DELAY_MS);
+        let success_status = param.success_status.as_ref();
+        // 提前校验状态码策略，避免请求发出后才发现配置错误
+        webhook_status_matches(success_status, 200)?;
+
+        let mut builder = reqwest::blocking::Client::builder()
+            .timeout(std::time::Duration::from_secs(timeout_secs));
+        if let Some(proxy) = non_empty(param.proxy) {
+            let p = reqwest::Proxy::all(&proxy)
+                .map_err(|e| format!("Invalid proxy '{}': {}", proxy, e))?;
+            builder = builder.proxy(p);
+        }
+        let client = builder
+            .build()
+            .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+
+        for attempt in 0..=retries {
+            if attempt > 0 {
+                info!(
+                    "[MXU_WEBHOOK] Retrying in {} ms ({}/{})",
+                    retry_delay_ms, attempt, retries
+                );
+                // 可被停止请求打断，暂停期间不计时
+                if !ctx.wait(Self::TAG, Duration::from_millis(retry_delay_ms)) {
+                    return Err("Retry cancelled by stop request".to_string());
+                }
+                retry_delay_ms = retry_delay_ms
+                    .saturating_mul(2)
+                    .min(WEBHOOK_MAX_RETRY_DELAY_MS);
+            }
+
+            info!("[MXU_WEBHOOK] Sending {} request to: {}", method, url);
+            let mut request = client
+                .request(method.clone(), &url)
+                .headers(headers.clone());
+            if let Some((content, _)) = &body {
+                request = request.body(content.clone());
+            }
+
+            match request.send() {
+                Ok(resp) => {
+                    let status = resp.status();
+                    info!("[MXU_WEBHOOK] Response status: {}", status);
+                    if webhook_status_matches(success_status, status.as_u16()).unwrap_or(false) {
+                        return Ok(());
+                    }
+                    let text = resp.text().unwrap_or_default();
+                    warn!(
+                        "[MXU_WEBHOOK] Status {} not accepted, response: {}",
+                        status,
+                        text.chars().take(500).collect::<String>()
+                    );
+                }
+                Err(e) => {
+                    log::error!("[MXU_WEBHOOK] Request failed: {}", e);
+                }
+            }
+        }
+
+        Err(format!("Webhook failed after {} attempt(s)", retries + 1))
+    }
+}
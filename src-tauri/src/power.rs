@@ -0,0 +1,439 @@
+//! MXU_POWER 电源操作
+//!
+//! - 各平台的关机 / 重启 / 息屏 / 睡眠 / 休眠 / 注销命令（Linux 使用 systemctl / loginctl）
+//! - 倒计时期间的待执行操作，按 ID 登记，可通过 `cancel_power_action` 命令取消指定或全部操作
+//! - 「没有其它实例在运行」的守卫检查
+//!
+//! 外部命令通过 [`CommandRunner`] 执行，便于替换为不实际执行的实现。
+
+use std::collections::HashMap;
+use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use tauri::Manager;
+
+use crate::commands::types::MaaState;
+use crate::maa_ffi::{callback_fns, get_app_handle};
+
+/// 判断命令是否立即失败的观察时间
+const COMMAND_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// 电源操作类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerAction {
+    Shutdown,
+    Restart,
+    ScreenOff,
+    Sleep,
+    Hibernate,
+    Logoff,
+}
+
+impl PowerAction {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "shutdown" | "poweroff" => Some(Self::Shutdown),
+            "restart" | "reboot" => Some(Self::Restart),
+            "screenoff" => Some(Self::ScreenOff),
+            "sleep" | "suspend" => Some(Self::Sleep),
+            "hibernate" => Some(Self::Hibernate),
+            "logoff" | "logout" => Some(Self::Logoff),
+            _ => None,
+        }
+    }
+
+    /// 在通知文案表 `power_actions` 中的键
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Shutdown => "shutdown",
+            Self::Restart => "restart",
+            Self::ScreenOff => "screenoff",
+            Self::Sleep => "sleep",
+            Self::Hibernate => "hibernate",
+            Self::Logoff => "logoff",
+        }
+    }
+
+    /// 当前平台的候选命令，按顺序尝试直到有一个成功
+    pub fn commands(&self) -> Vec<(&'static str, Vec<String>)> {
+        let args = |items: &[&str]| items.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        #[cfg(windows)]
+        {
+            match self {
+                Self::Shutdown => vec![("shutdown", args(&["/s", "/f", "/t", "0"]))],
+                Self::Restart => vec![("shutdown", args(&["/r", "/f", "/t", "0"]))],
+                Self::Sleep => vec![(
+                    "rundll32.exe",
+                    args(&["powrprof.dll,SetSuspendState", "0,1,0"]),
+                )],
+                Self::Hibernate => vec![("shutdown", args(&["/h"]))],
+                Self::Logoff => vec![("shutdown", args(&["/l"]))],
+                // 息屏通过 SendMessageW 实现，不依赖外部命令
+                Self::ScreenOff => Vec::new(),
+            }
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            let osascript = |cmd: &str| {
+                (
+                    "osascript",
+                    args(&["-e", &format!("tell app \"System Events\" to {}", cmd)]),
+                )
+            };
+            match self {
+                Self::Shutdown => vec![osascript("shut down")],
+                Self::Restart => vec![osascript("restart")],
+                Self::ScreenOff => vec![("pmset", args(&["displaysleepnow"]))],
+                // macOS 的睡眠是否写入磁盘由 hibernatemode 决定
+                Self::Sleep | Self::Hibernate => vec![("pmset", args(&["sleepnow"]))],
+                Self::Logoff => vec![osascript("log out")],
+            }
+        }
+
+        #[cfg(not(any(windows, target_os = "macos")))]
+        {
+            match self {
+                Self::Shutdown => vec![
+                    ("systemctl", args(&["poweroff"])),
+                    ("loginctl", args(&["poweroff"])),
+                ],
+                Self::Restart => vec![
+                    ("systemctl", args(&["reboot"])),
+                    ("loginctl", args(&["reboot"])),
+                ],
+                Self::ScreenOff => vec![("xset", args(&["dpms", "force", "off"]))],
+                Self::Sleep => vec![
+                    ("systemctl", args(&["suspend"])),
+                    ("loginctl", args(&["suspend"])),
+                ],
+                Self::Hibernate => vec![
+                    ("systemctl", args(&["hibernate"])),
+                    ("loginctl", args(&["hibernate"])),
+                ],
+                Self::Logoff => {
+                    let mut commands = Vec::new();
+                    if let Ok(session) = std::env::var("XDG_SESSION_ID") {
+                        commands.push(("loginctl", args(&["terminate-session", &session])));
+                    }
+                    if let Ok(user) = std::env::var("USER") {
+                        commands.push(("loginctl", args(&["terminate-user", &user])));
+                    }
+                    commands
+                }
+            }
+        }
+    }
+}
+
+/// 外部命令执行器
+pub trait CommandRunner {
+    fn run(&self, program: &str, args: &[String]) -> Result<(), String>;
+}
+
+/// 实际启动系统命令的执行器
+///
+/// 命令在观察时间内以非零状态退出视为失败；仍在运行（如睡眠命令直到唤醒才返回）视为成功
+pub struct SystemCommandRunner;
+
+impl CommandRunner for SystemCommandRunner {
+    fn run(&self, program: &str, args: &[String]) -> Result<(), String> {
+        let mut child = Command::new(program)
+            .args(args)
+            .spawn()
+            .map_err(|e| e.to_string())?;
+        let deadline = Instant::now() + COMMAND_CHECK_TIMEOUT;
+        while Instant::now() < deadline {
+            match child.try_wait() {
+                Ok(Some(status)) if status.success() => return Ok(()),
+                Ok(Some(status)) => return Err(format!("exited with {}", status)),
+                Ok(None) => std::thread::sleep(Duration::from_millis(100)),
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(windows)]
+fn windows_screen_off() {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::SendMessageW;
+
+    // WM_SYSCOMMAND = 0x0112, SC_MONITORPOWER = 0xF170, LPARAM(2) = turn off
+    const WM_SYSCOMMAND: u32 = 0x0112;
+    const SC_MONITORPOWER: usize = 0xF170;
+
+    unsafe {
+        SendMessageW(
+            HWND(0xFFFF as *mut std::ffi::c_void), // HWND_BROADCAST
+            WM_SYSCOMMAND,
+            windows::Win32::Foundation::WPARAM(SC_MONITORPOWER),
+            windows::Win32::Foundation::LPARAM(2), // 2 = turn off monitor
+        );
+    }
+}
+
+/// 执行电源操作，依次尝试候选命令
+pub fn execute_power_action(action: PowerAction, runner: &dyn CommandRunner) -> Result<(), String> {
+    #[cfg(windows)]
+    if action == PowerAction::ScreenOff {
+        windows_screen_off();
+        info!("[MXU_POWER] Screen off command issued (Windows)");
+        return Ok(());
+    }
+
+    let mut errors = Vec::new();
+    for (program, args) in action.commands() {
+        match runner.run(program, &args) {
+            Ok(()) => {
+                info!(
+                    "[MXU_POWER] {:?} command issued: {} {}",
+                    action,
+                    program,
+                    args.join(" ")
+                );
+                return Ok(());
+            }
+            Err(e) => {
+                warn!("[MXU_POWER] {} {} failed: {}", program, args.join(" "), e);
+                errors.push(format!("{}: {}", program, e));
+            }
+        }
+    }
+    if errors.is_empty() {
+        Err(format!("{:?} is not supported on this platform", action))
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+// ============================================================================
+// 待执行的电源操作
+// ============================================================================
+
+/// 倒计时中的电源操作登记表：ID -> 是否已取消
+#[derive(Default)]
+pub struct PendingRegistry {
+    next_id: AtomicU64,
+    entries: Mutex<HashMap<u64, bool>>,
+}
+
+impl PendingRegistry {
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<u64, bool>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 登记一个倒计时中的电源操作，返回其 ID
+    pub fn begin(&self) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        self.entries().insert(id, false);
+        id
+    }
+
+    /// 倒计时结束或被取消后清除登记
+    pub fn end(&self, id: u64) {
+        self.entries().remove(&id);
+    }
+
+    /// 该电源操作是否已被取消
+    pub fn is_cancelled(&self, id: u64) -> bool {
+        self.entries().get(&id).copied().unwrap_or(false)
+    }
+
+    /// 取消指定 ID 的电源操作，`id` 为 None 时取消全部，返回新取消的数量
+    pub fn cancel(&self, id: Option<u64>) -> usize {
+        let mut entries = self.entries();
+        let mut count = 0;
+        for (current, cancelled) in entries.iter_mut() {
+            if id.is_none_or(|id| id == *current) && !*cancelled {
+                *cancelled = true;
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+static PENDING: Lazy<PendingRegistry> = Lazy::new(PendingRegistry::default);
+
+/// 登记一个倒计时中的电源操作，返回其 ID
+pub fn begin_pending() -> u64 {
+    PENDING.begin()
+}
+
+/// 倒计时结束或被取消后清除登记
+pub fn end_pending(id: u64) {
+    PENDING.end(id)
+}
+
+/// 该电源操作是否已被取消
+pub fn is_pending_cancelled(id: u64) -> bool {
+    PENDING.is_cancelled(id)
+}
+
+/// 取消倒计时中的电源操作，`id` 为 None 时取消全部，返回新取消的数量
+pub fn cancel_pending(id: Option<u64>) -> usize {
+    PENDING.cancel(id)
+}
+
+/// 获取除 `current_tasker` 外仍在运行任务的实例 ID
+pub fn other_running_instances(current_tasker: Option<usize>) -> Vec<String> {
+    let Some(handle) = get_app_handle() else {
+        return Vec::new();
+    };
+    let Some(state) = handle.try_state::<Arc<MaaState>>() else {
+        return Vec::new();
+    };
+
+    // 由 MXU_POWER 在工作线程上调用，使用缓存的函数指针而不锁定 MAA_LIBRARY
+    let Ok(fns) = callback_fns() else {
+        return Vec::new();
+    };
+
+    let Ok(instances) = state.instances.lock() else {
+        return Vec::new();
+    };
+    let mut running: Vec<String> = instances
+        .iter()
+        .filter_map(|(id, instance)| {
+            let tasker = instance.tasker?;
+            if current_tasker == Some(tasker as usize) {
+                return None;
+            }
+            (unsafe { (fns.tasker_running)(tasker) } != 0).then(|| id.clone())
+        })
+        .collect();
+    running.sort();
+    running
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// 记录命令行而不实际执行的执行器，`failing` 中的程序返回失败
+    #[derive(Default)]
+    pub(crate) struct RecordingRunner {
+        pub calls: RefCell<Vec<String>>,
+        pub failing: Vec<&'static str>,
+    }
+
+    impl CommandRunner for RecordingRunner {
+        fn run(&self, program: &str, args: &[String]) -> Result<(), String> {
+            let mut line = vec![program.to_string()];
+            line.extend(args.iter().cloned());
+            self.calls.borrow_mut().push(line.join(" "));
+            if self.failing.contains(&program) {
+                Err("exited with exit status: 1".to_string())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn parse_accepts_aliases() {
+        assert_eq!(
+            PowerAction::parse(" PowerOff "),
+            Some(PowerAction::Shutdown)
+        );
+        assert_eq!(PowerAction::parse("reboot"), Some(PowerAction::Restart));
+        assert_eq!(PowerAction::parse("suspend"), Some(PowerAction::Sleep));
+        assert_eq!(PowerAction::parse("logout"), Some(PowerAction::Logoff));
+        assert_eq!(PowerAction::parse("explode"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux_uses_systemctl_first() {
+        for (action, line) in [
+            (PowerAction::Shutdown, "systemctl poweroff"),
+            (PowerAction::Restart, "systemctl reboot"),
+            (PowerAction::Sleep, "systemctl suspend"),
+            (PowerAction::Hibernate, "systemctl hibernate"),
+            (PowerAction::ScreenOff, "xset dpms force off"),
+        ] {
+            let runner = RecordingRunner::default();
+            execute_power_action(action, &runner).unwrap();
+            assert_eq!(*runner.calls.borrow(), [line]);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux_falls_back_to_loginctl() {
+        let runner = RecordingRunner {
+            failing: vec!["systemctl"],
+            ..Default::default()
+        };
+        execute_power_action(PowerAction::Restart, &runner).unwrap();
+        assert_eq!(
+            *runner.calls.borrow(),
+            ["systemctl reboot", "loginctl reboot"]
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux_reports_all_failures() {
+        let runner = RecordingRunner {
+            failing: vec!["systemctl", "loginctl"],
+            ..Default::default()
+        };
+        let err = execute_power_action(PowerAction::Hibernate, &runner).unwrap_err();
+        assert_eq!(
+            err,
+            "systemctl: exited with exit status: 1; loginctl: exited with exit status: 1"
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux_logoff_terminates_session_then_user() {
+        std::env::set_var("XDG_SESSION_ID", "c7");
+        std::env::set_var("USER", "mxu");
+        let runner = RecordingRunner {
+            failing: vec!["loginctl"],
+            ..Default::default()
+        };
+        assert!(execute_power_action(PowerAction::Logoff, &runner).is_err());
+        assert_eq!(
+            *runner.calls.borrow(),
+            [
+                "loginctl terminate-session c7",
+                "loginctl terminate-user mxu"
+            ]
+        );
+    }
+
+    #[test]
+    fn pending_cancel_by_id_or_all() {
+        let registry = PendingRegistry::default();
+        let first = registry.begin();
+        let second = registry.begin();
+        assert_ne!(first, second);
+
+        assert_eq!(registry.cancel(Some(second)), 1);
+        assert!(!registry.is_cancelled(first));
+        assert!(registry.is_cancelled(second));
+        // 已取消的不重复计数，未知 ID 不影响其它操作
+        assert_eq!(registry.cancel(Some(second)), 0);
+        assert_eq!(registry.cancel(Some(second + 100)), 0);
+
+        let third = registry.begin();
+        assert_eq!(registry.cancel(None), 2);
+        assert!(registry.is_cancelled(first));
+        assert!(registry.is_cancelled(third));
+
+        registry.end(first);
+        assert!(!registry.is_cancelled(first));
+        assert_eq!(registry.cancel(None), 0);
+    }
+}
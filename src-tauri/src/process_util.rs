@@ -0,0 +1,552 @@
+//! 进程、端口与窗口探测工具
+//!
+//! 供 MXU_LAUNCH 等动作判断外部程序是否就绪：
+//! - 按进程名查找运行中的进程（跨平台，基于 sysinfo）
+//! - 按名称 / 正则 / 可执行文件路径 / 命令行匹配进程并结束（供 MXU_KILLPROC 使用）
+//! - 检测 TCP 端口是否可连接
+//! - 在后台读取子进程输出（供 MXU_ADB_SHELL、插件与模拟器命令使用）
+//! - 按标题查找可见窗口（Windows 使用 EnumWindows，Linux 使用 wmctrl，macOS 使用 System Events）
+
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use log::warn;
+use regex::Regex;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessStatus, ProcessesToUpdate, System, UpdateKind};
+
+/// 在后台线程中读取子进程输出的全部内容
+pub fn read_to_end_in_background<R: std::io::Read + Send + 'static>(
+    mut reader: R,
+) -> std::thread::JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = reader.read_to_end(&mut buf);
+        String::from_utf8_lossy(&buf).into_owned()
+    })
+}
+
+/// 端口探测的连接超时
+const PORT_CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// 获取当前所有进程的快照
+fn process_snapshot() -> System {
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing().without_tasks(),
+    );
+    sys
+}
+
+/// 进程名是否匹配（忽略大小写，允许省略 Windows 的 .exe 后缀）
+pub fn process_name_matches(actual: &str, expected: &str) -> bool {
+    let strip = |s: &str| {
+        let lower = s.trim().to_lowercase();
+        lower
+            .strip_suffix(".exe")
+            .map(str::to_string)
+            .unwrap_or(lower)
+    };
+    strip(actual) == strip(expected)
+}
+
+/// 是否存在指定名称的进程
+pub fn is_process_running(name: &str) -> bool {
+    process_snapshot()
+        .processes()
+        .values()
+        .filter(|p| p.thread_kind().is_none())
+        .any(|p| process_name_matches(&p.name().to_string_lossy(), name))
+}
+
+/// 进程快照信息
+#[derive(Debug, Clone)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub name: String,
+    pub exe: Option<PathBuf>,
+    pub cmdline: String,
+}
+
+/// 获取当前所有进程的名称、可执行文件路径和命令行
+pub fn list_processes() -> Vec<ProcessInfo> {
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing()
+            .without_tasks()
+            .with_exe(UpdateKind::Always)
+            .with_cmd(UpdateKind::Always),
+    );
+    sys.processes()
+        .values()
+        .filter(|p| p.thread_kind().is_none())
+        .map(|p| ProcessInfo {
+            pid: p.pid().as_u32(),
+            name: p.name().to_string_lossy().to_string(),
+            exe: p.exe().map(Path::to_path_buf),
+            cmdline: p
+                .cmd()
+                .iter()
+                .map(|arg| arg.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(" "),
+        })
+        .collect()
+}
+
+/// 两个路径是否指向同一文件（Windows 下忽略大小写和分隔符差异）
+fn paths_equal(a: &Path, b: &Path) -> bool {
+    if cfg!(windows) {
+        let normalize = |p: &Path| p.to_string_lossy().replace('/', "\\").to_lowercase();
+        normalize(a) == normalize(b)
+    } else {
+        a == b
+    }
+}
+
+/// 进程匹配条件，所有已设置的条件同时满足才算匹配
+#[derive(Debug, Default)]
+pub struct ProcessMatcher {
+    /// 精确进程名（忽略大小写，可省略 .exe）
+    pub name: Option<String>,
+    /// 进程名正则
+    pub name_regex: Option<Regex>,
+    /// 可执行文件完整路径
+    pub exe_path: Option<PathBuf>,
+    /// 命令行子串（区分大小写）
+    pub cmdline_contains: Option<String>,
+}
+
+impl ProcessMatcher {
+    /// 是否未设置任何条件
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.name_regex.is_none()
+            && self.exe_path.is_none()
+            && self.cmdline_contains.is_none()
+    }
+
+    pub fn matches(&self, process: &ProcessInfo) -> bool {
+        if let Some(name) = &self.name {
+            if !process_name_matches(&process.name, name) {
+                return false;
+            }
+        }
+        if let Some(regex) = &self.name_regex {
+            if !regex.is_match(&process.name) {
+                return false;
+            }
+        }
+        if let Some(exe_path) = &self.exe_path {
+            // 路径可能以符号链接形式给出，同时比较规范化后的路径
+            let canonical = std::fs::canonicalize(exe_path).ok();
+            let matched = process.exe.as_deref().is_some_and(|exe| {
+                paths_equal(exe, exe_path)
+                    || canonical.as_deref().is_some_and(|c| paths_equal(exe, c))
+            });
+            if !matched {
+                return false;
+            }
+        }
+        if let Some(needle) = &self.cmdline_contains {
+            if !process.cmdline.contains(needle.as_str()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// 查找匹配的进程，按 PID 排序
+pub fn find_processes(matcher: &ProcessMatcher) -> Vec<ProcessInfo> {
+    let mut found: Vec<ProcessInfo> = list_processes()
+        .into_iter()
+        .filter(|p| matcher.matches(p))
+        .collect();
+    found.sort_by_key(|p| p.pid);
+    found
+}
+
+/// 结束进程的结果
+#[derive(Debug, Default)]
+pub struct TerminateReport {
+    /// 收到关闭请求后在宽限期内自行退出的进程
+    pub exited: Vec<u32>,
+    /// 被强制结束的进程
+    pub killed: Vec<u32>,
+    /// 无法结束的进程
+    pub failed: Vec<u32>,
+}
+
+/// 请求进程正常退出（Unix 发送 SIGTERM，Windows 使用不带 /F 的 taskkill）
+fn request_graceful_exit(sys: &System, pid: u32) -> bool {
+    #[cfg(windows)]
+    {
+        let _ = sys;
+        std::process::Command::new("taskkill")
+            .args(["/PID", &pid.to_string()])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    #[cfg(not(windows))]
+    {
+        sys.process(Pid::from_u32(pid))
+            .and_then(|p| p.kill_with(sysinfo::Signal::Term))
+            .unwrap_or(false)
+    }
+}
+
+/// 刷新指定进程并返回仍在运行的 PID
+fn still_running(sys: &mut System, pids: &[u32]) -> Vec<u32> {
+    let sys_pids: Vec<Pid> = pids.iter().map(|p| Pid::from_u32(*p)).collect();
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&sys_pids),
+        true,
+        ProcessRefreshKind::nothing().without_tasks(),
+    );
+    pids.iter()
+        .copied()
+        .filter(|pid| {
+            sys.process(Pid::from_u32(*pid))
+                .is_some_and(|p| p.status() != ProcessStatus::Zombie)
+        })
+        .collect()
+}
+
+/// 结束进程：`grace_period` 为 Some 时先请求正常退出，等待宽限期后再强制结束剩余进程
+pub fn terminate_processes(pids: &[u32], grace_period: Option<Duration>) -> TerminateReport {
+    let mut report = TerminateReport::default();
+    let mut sys = System::new();
+    let mut remaining = still_running(&mut sys, pids);
+
+    if let Some(grace) = grace_period {
+        for pid in &remaining {
+            if !request_graceful_exit(&sys, *pid) {
+                log::debug!("Graceful exit request failed for pid {}", pid);
+            }
+        }
+        let deadline = Instant::now() + grace;
+        loop {
+            let alive = still_running(&mut sys, &remaining);
+            report
+                .exited
+                .extend(remaining.iter().filter(|pid| !alive.contains(pid)));
+            remaining = alive;
+            if remaining.is_empty() || Instant::now() >= deadline {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(200));
+        }
+    }
+
+    for pid in remaining {
+        let killed = sys
+            .process(Pid::from_u32(pid))
+            .map(|p| p.kill())
+            .unwrap_or(true); // 已不存在视为结束
+        if killed {
+            report.killed.push(pid);
+        } else {
+            report.failed.push(pid);
+        }
+    }
+    report
+}
+
+/// 解析端口探测地址：纯数字视为本机端口，否则按 `host:port` 解析
+fn resolve_port_target(target: &str) -> Result<Vec<SocketAddr>, String> {
+    let target = target.trim();
+    if let Ok(port) = target.parse::<u16>() {
+        return Ok(vec![SocketAddr::from(([127, 0, 0, 1], port))]);
+    }
+    target
+        .to_socket_addrs()
+        .map(|addrs| addrs.collect())
+        .map_err(|e| format!("Invalid address '{}': {}", target, e))
+}
+
+/// TCP 端口是否可连接
+pub fn is_port_open(target: &str) -> Result<bool, String> {
+    let addrs = resolve_port_target(target)?;
+    Ok(addrs
+        .iter()
+        .any(|addr| TcpStream::connect_timeout(addr, PORT_CONNECT_TIMEOUT).is_ok()))
+}
+
+/// 是否存在标题包含 `pattern` 的可见窗口（忽略大小写）
+pub fn window_title_exists(pattern: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    list_window_titles()
+        .iter()
+        .any(|title| title.to_lowercase().contains(&pattern))
+}
+
+/// 列出所有可见顶层窗口的标题
+#[cfg(windows)]
+pub fn list_window_titles() -> Vec<String> {
+    use windows::Win32::Foundation::{BOOL, HWND, LPARAM};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        EnumWindows, GetWindowTextLengthW, GetWindowTextW, IsWindowVisible,
+    };
+
+    unsafe extern "system" fn collect(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let titles = &mut *(lparam.0 as *mut Vec<String>);
+        if IsWindowVisible(hwnd).as_bool() {
+            let len = GetWindowTextLengthW(hwnd);
+            if len > 0 {
+                let mut buf = vec![0u16; len as usize + 1];
+                let copied = GetWindowTextW(hwnd, &mut buf);
+                if copied > 0 {
+                    titles.push(String::from_utf16_lossy(&buf[..copied as usize]));
+                }
+            }
+        }
+        BOOL(1)
+    }
+
+    let mut titles: Vec<String> = Vec::new();
+    unsafe {
+        if let Err(e) = EnumWindows(Some(collect), LPARAM(&mut titles as *mut _ as isize)) {
+            warn!("EnumWindows failed: {}", e);
+        }
+    }
+    titles
+}
+
+/// 列出所有可见顶层窗口的标题
+#[cfg(target_os = "macos")]
+pub fn list_window_titles() -> Vec<String> {
+    let script = "tell application \"System Events\" to get name of every window of every process whose visible is true";
+    match std::process::Command::new("osascript")
+        .args(["-e", script])
+        .output()
+    {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .split(", ")
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty() && s != "missing value")
+            .collect(),
+        Ok(output) => {
+            log::debug!(
+                "osascript failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            Vec::new()
+        }
+        Err(e) => {
+            warn!("Failed to execute osascript: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// 列出所有可见顶层窗口的标题（依赖 wmctrl）
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn list_window_titles() -> Vec<String> {
+    match std::process::Command::new("wmctrl").arg("-l").output() {
+        // 每行格式：<窗口 ID> <桌面> <主机名> <标题>
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let mut rest = line;
+                for _ in 0..3 {
+                    rest = rest.trim_start().split_once(char::is_whitespace)?.1;
+                }
+                Some(rest.trim().to_string())
+            })
+            .filter(|s| !s.is_empty())
+            .collect(),
+        Ok(output) => {
+            log::debug!(
+                "wmctrl failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            Vec::new()
+        }
+        Err(e) => {
+            warn!("Failed to execute wmctrl (is it installed?): {}", e);
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::process::{Child, Command};
+
+    /// 测试用的 sleep 子进程，参数带唯一标记以便按命令行区分，drop 时强制结束
+    struct SleepChild {
+        child: Child,
+        marker: String,
+    }
+
+    impl SleepChild {
+        fn spawn(tag: u32) -> Self {
+            Self::spawn_with(tag, false)
+        }
+
+        /// `ignore_term` 为 true 时子进程忽略 SIGTERM，只能被强制结束
+        fn spawn_with(tag: u32, ignore_term: bool) -> Self {
+            let marker = format!("3600.{}{}", tag, std::process::id());
+            let child = if ignore_term {
+                Command::new("sh")
+                    .args(["-c", &format!("trap '' TERM; exec sleep {}", marker)])
+                    .spawn()
+            } else {
+                Command::new("sleep").arg(&marker).spawn()
+            }
+            .expect("failed to spawn sleep");
+            let child = Self { child, marker };
+            // 等待 exec 完成，避免快照中仍是 sh
+            let started = ProcessMatcher {
+                name: Some("sleep".to_string()),
+                ..child.cmdline_matcher()
+            };
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while find_processes(&started).is_empty() {
+                assert!(Instant::now() < deadline, "sleep child did not start");
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            child
+        }
+
+        fn pid(&self) -> u32 {
+            self.child.id()
+        }
+
+        fn cmdline_matcher(&self) -> ProcessMatcher {
+            ProcessMatcher {
+                cmdline_contains: Some(self.marker.clone()),
+                ..Default::default()
+            }
+        }
+
+        fn has_exited(&mut self) -> bool {
+            self.child.try_wait().unwrap().is_some()
+        }
+    }
+
+    impl Drop for SleepChild {
+        fn drop(&mut self) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+
+    fn pids(found: Vec<ProcessInfo>) -> Vec<u32> {
+        found.into_iter().map(|p| p.pid).collect()
+    }
+
+    /// 在 PATH 中查找可执行文件
+    fn which(program: &str) -> PathBuf {
+        std::env::var_os("PATH")
+            .and_then(|paths| {
+                std::env::split_paths(&paths)
+                    .map(|dir| dir.join(program))
+                    .find(|path| path.is_file())
+            })
+            .expect("program not found in PATH")
+    }
+
+    #[test]
+    fn name_matches_ignore_case_and_exe_suffix() {
+        assert!(process_name_matches("Sleep.EXE", "sleep"));
+        assert!(process_name_matches("sleep", " SLEEP.exe "));
+        assert!(!process_name_matches("sleeper", "sleep"));
+    }
+
+    #[test]
+    fn matches_by_cmdline() {
+        let child = SleepChild::spawn(1);
+        assert_eq!(
+            pids(find_processes(&child.cmdline_matcher())),
+            [child.pid()]
+        );
+
+        let matcher = ProcessMatcher {
+            cmdline_contains: Some(format!("{}9", child.marker)),
+            ..Default::default()
+        };
+        assert!(find_processes(&matcher).is_empty());
+    }
+
+    #[test]
+    fn matches_by_name_and_regex() {
+        let child = SleepChild::spawn(2);
+        let matcher = ProcessMatcher {
+            name: Some("SLEEP".to_string()),
+            name_regex: Some(Regex::new("^sl(e)+p$").unwrap()),
+            ..child.cmdline_matcher()
+        };
+        assert_eq!(pids(find_processes(&matcher)), [child.pid()]);
+
+        let matcher = ProcessMatcher {
+            name_regex: Some(Regex::new("^sh$").unwrap()),
+            ..child.cmdline_matcher()
+        };
+        assert!(find_processes(&matcher).is_empty());
+    }
+
+    #[test]
+    fn matches_by_exe_path() {
+        let child = SleepChild::spawn(3);
+        // PATH 中的路径可能是符号链接（如 coreutils 多调用程序），依赖规范化比较
+        let matcher = ProcessMatcher {
+            exe_path: Some(which("sleep")),
+            ..child.cmdline_matcher()
+        };
+        assert_eq!(pids(find_processes(&matcher)), [child.pid()]);
+
+        let matcher = ProcessMatcher {
+            exe_path: Some(PathBuf::from("/nonexistent/sleep")),
+            ..child.cmdline_matcher()
+        };
+        assert!(find_processes(&matcher).is_empty());
+    }
+
+    #[test]
+    fn graceful_terminate_reports_exited() {
+        let mut child = SleepChild::spawn(4);
+        let report = terminate_processes(&[child.pid()], Some(Duration::from_secs(5)));
+        assert_eq!(report.exited, [child.pid()]);
+        assert!(report.killed.is_empty());
+        assert!(report.failed.is_empty());
+        assert!(child.has_exited());
+    }
+
+    #[test]
+    fn graceful_terminate_falls_back_to_kill() {
+        let mut child = SleepChild::spawn_with(5, true);
+        let report = terminate_processes(&[child.pid()], Some(Duration::from_millis(500)));
+        assert!(report.exited.is_empty());
+        assert_eq!(report.killed, [child.pid()]);
+        assert!(report.failed.is_empty());
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !child.has_exited() {
+            assert!(Instant::now() < deadline, "child survived SIGKILL");
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn forceful_terminate_without_grace_period() {
+        let mut child = SleepChild::spawn(6);
+        let report = terminate_processes(&[child.pid()], None);
+        assert!(report.exited.is_empty());
+        assert_eq!(report.killed, [child.pid()]);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !child.has_exited() {
+            assert!(Instant::now() < deadline, "child survived SIGKILL");
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+}
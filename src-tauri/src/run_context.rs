@@ -0,0 +1,263 @@
+//! 任务运行上下文
+//!
+//! 记录 task_id 与所属实例、任务入口的对应关系，以及各实例最近一次的失败信息，
+//! 供 MXU 动作在参数模板中引用（如 MXU_WEBHOOK 的 `{instance}`、`{task}`、`{last_error}`）。
+//! 同时跟踪每轮运行中尚未结束的任务，全部结束时触发运行结束通知，
+//! 并将本轮结果与 MXU_LOG 写入的日志保存到运行历史。
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+use crate::commands::types::{RunHistoryEntry, RunLogEntry};
+use crate::maa_ffi::MaaId;
+
+/// 已提交任务的信息
+struct TaskRunInfo {
+    instance_id: String,
+    entry: String,
+}
+
+/// 进行中的一轮运行
+struct RunRecord {
+    started_at: String,
+    logs: Vec<RunLogEntry>,
+}
+
+impl RunRecord {
+    fn start() -> Self {
+        Self {
+            started_at: now_string(),
+            logs: Vec::new(),
+        }
+    }
+
+    fn finish(self, instance_id: String, result: &str, last_error: String) -> RunHistoryEntry {
+        RunHistoryEntry {
+            instance_id,
+            started_at: self.started_at,
+            finished_at: now_string(),
+            result: result.to_string(),
+            last_error,
+            logs: self.logs,
+        }
+    }
+}
+
+fn now_string() -> String {
+    chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// 在后台写入运行历史，避免阻塞 MaaFramework 回调线程
+fn save_run(entry: RunHistoryEntry) {
+    std::thread::spawn(move || crate::commands::run_history::append_run(entry));
+}
+
+#[derive(Default)]
+struct RunContextStore {
+    tasks: HashMap<MaaId, TaskRunInfo>,
+    /// 实例 ID -> 最近一次失败信息
+    last_errors: HashMap<String, String>,
+    /// 实例 ID -> 本轮尚未结束的任务
+    pending: HashMap<String, HashSet<MaaId>>,
+    /// 本轮存在失败任务的实例
+    failed_runs: HashSet<String>,
+    /// 实例 ID -> 进行中的运行记录
+    runs: HashMap<String, RunRecord>,
+}
+
+impl RunContextStore {
+    fn clear_instance(&mut self, instance_id: &str) {
+        self.tasks.retain(|_, info| info.instance_id != instance_id);
+        self.last_errors.remove(instance_id);
+        self.pending.remove(instance_id);
+        self.failed_runs.remove(instance_id);
+        self.runs.remove(instance_id);
+    }
+}
+
+static STORE: Lazy<Mutex<RunContextStore>> = Lazy::new(|| Mutex::new(RunContextStore::default()));
+
+/// MXU 动作可引用的任务上下文
+#[derive(Debug, Clone, Default)]
+pub struct TaskRunContext {
+    pub instance_id: String,
+    pub entry: String,
+    pub last_error: String,
+}
+
+/// 开始新一轮运行：清除该实例之前的任务记录和失败信息
+pub fn begin_run(instance_id: &str) {
+    if let Ok(mut store) = STORE.lock() {
+        store.clear_instance(instance_id);
+        store
+            .runs
+            .insert(instance_id.to_string(), RunRecord::start());
+    }
+}
+
+/// 用户主动停止：放弃本轮的结束通知，运行历史记为 stopped
+pub fn mark_stopped(instance_id: &str) {
+    let stopped_run = {
+        let Ok(mut store) = STORE.lock() else {
+            return;
+        };
+        let was_running = store.pending.remove(instance_id).is_some();
+        store.failed_runs.remove(instance_id);
+        let last_error = store
+            .last_errors
+            .get(instance_id)
+            .cloned()
+            .unwrap_or_default();
+        store
+            .runs
+            .remove(instance_id)
+            .filter(|_| was_running)
+            .map(|run| run.finish(instance_id.to_string(), "stopped", last_error))
+    };
+    if let Some(entry) = stopped_run {
+        save_run(entry);
+    }
+}
+
+/// 记录已提交的任务
+pub fn record_task(task_id: MaaId, instance_id: &str, entry: &str) {
+    if let Ok(mut store) = STORE.lock() {
+        store.tasks.insert(
+            task_id,
+            TaskRunInfo {
+                instance_id: instance_id.to_string(),
+                entry: entry.to_string(),
+            },
+        );
+        store
+            .pending
+            .entry(instance_id.to_string())
+            .or_default()
+            .insert(task_id);
+        store
+            .runs
+            .entry(instance_id.to_string())
+            .or_insert_with(RunRecord::start);
+    }
+}
+
+/// 实例销毁时清除其所有记录
+pub fn forget_instance(instance_id: &str) {
+    if let Ok(mut store) = STORE.lock() {
+        store.clear_instance(instance_id);
+    }
+}
+
+/// 将 MXU_LOG 的日志追加到任务所属实例的本轮运行记录
+pub fn record_run_log(task_id: MaaId, entry: RunLogEntry) {
+    if let Ok(mut store) = STORE.lock() {
+        let Some(instance_id) = store.tasks.get(&task_id).map(|t| t.instance_id.clone()) else {
+            return;
+        };
+        store
+            .runs
+            .entry(instance_id)
+            .or_insert_with(RunRecord::start)
+            .logs
+            .push(entry);
+    }
+}
+
+/// 从 MaaFramework 回调中提取失败信息并跟踪任务结束（由 maa_event_callback 调用）
+pub fn record_callback(message: &str, details: &str) {
+    let is_failure = message.ends_with(".Failed")
+        && (message.starts_with("Node.") || message == "Tasker.Task.Failed");
+    let is_task_end = message == "Tasker.Task.Succeeded" || message == "Tasker.Task.Failed";
+    if !is_failure && !is_task_end {
+        return;
+    }
+
+    let Ok(details) = serde_json::from_str::<serde_json::Value>(details) else {
+        return;
+    };
+    let Some(task_id) = details.get("task_id").and_then(|v| v.as_i64()) else {
+        return;
+    };
+    if is_task_end {
+        crate::mxu_actions::forget_task(task_id);
+    }
+    let name = details
+        .get("name")
+        .or_else(|| details.get("entry"))
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+    // 停止任务产生的回调不是真正的任务结果
+    if name == "MaaTaskerPostStop" {
+        return;
+    }
+
+    // (实例 ID, 是否成功, 最近失败信息, 运行记录)
+    let finished_run = {
+        let Ok(mut store) = STORE.lock() else {
+            return;
+        };
+        let Some(instance_id) = store.tasks.get(&task_id).map(|t| t.instance_id.clone()) else {
+            return;
+        };
+
+        if is_failure {
+            store
+                .last_errors
+                .insert(instance_id.clone(), format!("{} ({})", name, message));
+        }
+
+        if !is_task_end {
+            None
+        } else {
+            if message == "Tasker.Task.Failed" {
+                store.failed_runs.insert(instance_id.clone());
+            }
+            let run_finished = match store.pending.get_mut(&instance_id) {
+                Some(pending) => pending.remove(&task_id) && pending.is_empty(),
+                None => false,
+            };
+            if run_finished {
+                store.pending.remove(&instance_id);
+                let succeeded = !store.failed_runs.remove(&instance_id);
+                let last_error = store
+                    .last_errors
+                    .get(&instance_id)
+                    .cloned()
+                    .unwrap_or_default();
+                let run = store.runs.remove(&instance_id);
+                Some((instance_id, succeeded, last_error, run))
+            } else {
+                None
+            }
+        }
+    };
+
+    if let Some((instance_id, succeeded, last_error, run)) = finished_run {
+        crate::commands::notification::notify_run_finished(&instance_id, succeeded, &last_error);
+        if let Some(run) = run {
+            let result = if succeeded { "succeeded" } else { "failed" };
+            save_run(run.finish(instance_id, result, last_error));
+        }
+    }
+}
+
+/// 获取任务的运行上下文，未记录的任务返回空字段
+pub fn task_context(task_id: MaaId) -> TaskRunContext {
+    let Ok(store) = STORE.lock() else {
+        return TaskRunContext::default();
+    };
+    let Some(info) = store.tasks.get(&task_id) else {
+        return TaskRunContext::default();
+    };
+    TaskRunContext {
+        instance_id: info.instance_id.clone(),
+        entry: info.entry.clone(),
+        last_error: store
+            .last_errors
+            .get(&info.instance_id)
+            .cloned()
+            .unwrap_or_default(),
+    }
+}
@@ -0,0 +1,233 @@
+//! MXU_WAITUNTIL 目标时间解析
+//!
+//! `target_time` 支持以下格式（可为字符串或字符串数组，数组/逗号分隔时取最近的一个）：
+//! - `HH:MM` / `HH:MM:SS`：每天的该时间，已过则为次日
+//! - `YYYY-MM-DD HH:MM[:SS]`（也可用 `T` 分隔）：指定日期时间，已过则视为无效
+//! - RFC 3339 时间，如 `2026-10-20T04:00:00+08:00`：自带时区偏移
+//! - `<星期> HH:MM[:SS]`：每周的该时间，星期可写作 `Mon` / `Monday` / `周一` / `星期一`
+//!
+//! `timezone` 可选，支持 `Local`（默认）、`UTC`、IANA 名称（如 `Asia/Shanghai`）
+//! 以及 UTC 偏移（如 `+08:00`、`UTC-5`）。
+//!
+//! 夏令时处理：重复出现的本地时间取较早的一次；不存在的本地时间顺延到跳变后的第一个有效时刻。
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Local, LocalResult, NaiveDate, NaiveDateTime,
+    NaiveTime, TimeZone, Utc, Weekday,
+};
+
+/// 目标时间所在时区
+#[derive(Debug, Clone, Copy)]
+pub enum WaitTimezone {
+    Local,
+    Fixed(FixedOffset),
+    Named(chrono_tz::Tz),
+}
+
+impl WaitTimezone {
+    /// 解析时区参数，空字符串视为本地时区
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        if s.is_empty() || s.eq_ignore_ascii_case("local") {
+            return Ok(Self::Local);
+        }
+        if s.eq_ignore_ascii_case("utc") || s.eq_ignore_ascii_case("gmt") || s == "Z" {
+            return Ok(Self::Fixed(FixedOffset::east_opt(0).unwrap()));
+        }
+        if let Some(offset) = parse_utc_offset(s) {
+            return Ok(Self::Fixed(offset));
+        }
+        s.parse::<chrono_tz::Tz>()
+            .map(Self::Named)
+            .map_err(|_| format!("Unknown timezone '{}'", s))
+    }
+
+    /// 将该时区下的本地日期时间转换为 UTC
+    fn resolve(&self, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Local => resolve_in(&Local, naive),
+            Self::Fixed(offset) => resolve_in(offset, naive),
+            Self::Named(tz) => resolve_in(tz, naive),
+        }
+    }
+
+    /// 当前时刻在该时区下的日期
+    fn today(&self, now: DateTime<Utc>) -> NaiveDate {
+        match self {
+            Self::Local => now.with_timezone(&Local).date_naive(),
+            Self::Fixed(offset) => now.with_timezone(offset).date_naive(),
+            Self::Named(tz) => now.with_timezone(tz).date_naive(),
+        }
+    }
+}
+
+/// 解析 `+08:00`、`-0530`、`UTC+8`、`GMT-5` 形式的偏移
+fn parse_utc_offset(s: &str) -> Option<FixedOffset> {
+    let upper = s.to_ascii_uppercase();
+    let rest = upper
+        .strip_prefix("UTC")
+        .or_else(|| upper.strip_prefix("GMT"))
+        .unwrap_or(&upper);
+    let (sign, digits) = match rest.chars().next()? {
+        '+' => (1, &rest[1..]),
+        '-' => (-1, &rest[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((h, m)) => (h.parse::<i32>().ok()?, m.parse::<i32>().ok()?),
+        None if digits.len() == 4 => (digits[..2].parse().ok()?, digits[2..].parse().ok()?),
+        None => (digits.parse::<i32>().ok()?, 0),
+    };
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// 按夏令时规则将本地时间转换为 UTC
+fn resolve_in<T: TimeZone>(tz: &T, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+    match tz.from_local_datetime(&naive) {
+        LocalResult::Single(dt) => Some(dt.with_timezone(&Utc)),
+        // 时钟回拨导致的重复时间，取较早的一次
+        LocalResult::Ambiguous(earliest, _) => Some(earliest.with_timezone(&Utc)),
+        // 时钟前拨导致的不存在时间，顺延到跳变后的第一个有效分钟
+        LocalResult::None => (1..=180).find_map(|minutes| {
+            tz.from_local_datetime(&(naive + Duration::minutes(minutes)))
+                .earliest()
+                .map(|dt| dt.with_timezone(&Utc))
+        }),
+    }
+}
+
+/// 单个目标时间规格
+#[derive(Debug, Clone)]
+enum TargetSpec {
+    Daily(NaiveTime),
+    Weekly(Weekday, NaiveTime),
+    At(NaiveDateTime),
+    Absolute(DateTime<Utc>),
+}
+
+fn parse_time_of_day(s: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(s, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(s, "%H:%M"))
+        .ok()
+}
+
+fn parse_weekday(s: &str) -> Option<Weekday> {
+    if let Ok(day) = s.parse::<Weekday>() {
+        return Some(day);
+    }
+    let day = s.strip_prefix("星期").or_else(|| s.strip_prefix("周"))?;
+    match day {
+        "一" => Some(Weekday::Mon),
+        "二" => Some(Weekday::Tue),
+        "三" => Some(Weekday::Wed),
+        "四" => Some(Weekday::Thu),
+        "五" => Some(Weekday::Fri),
+        "六" => Some(Weekday::Sat),
+        "日" | "天" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn parse_spec(s: &str) -> Result<TargetSpec, String> {
+    let s = s.trim();
+
+    if let Some(time) = parse_time_of_day(s) {
+        return Ok(TargetSpec::Daily(time));
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Ok(TargetSpec::Absolute(dt.with_timezone(&Utc)));
+    }
+    for fmt in [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+    ] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(s, fmt) {
+            return Ok(TargetSpec::At(dt));
+        }
+    }
+    if let Some((day, time)) = s.split_once(char::is_whitespace) {
+        if let (Some(day), Some(time)) = (parse_weekday(day), parse_time_of_day(time.trim())) {
+            return Ok(TargetSpec::Weekly(day, time));
+        }
+    }
+
+    Err(format!(
+        "Invalid target_time '{}', expected HH:MM, YYYY-MM-DD HH:MM, RFC 3339 or '<weekday> HH:MM'",
+        s
+    ))
+}
+
+impl TargetSpec {
+    /// 计算 `now` 之后最近一次到达该目标的时刻，已过去的固定时间返回 None
+    fn next_after(&self, tz: WaitTimezone, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Absolute(dt) => (*dt > now).then_some(*dt),
+            Self::At(naive) => tz.resolve(*naive).filter(|dt| *dt > now),
+            Self::Daily(time) => {
+                let today = tz.today(now);
+                (0..=2)
+                    .filter_map(|offset| {
+                        tz.resolve((today + Duration::days(offset)).and_time(*time))
+                    })
+                    .find(|dt| *dt > now)
+            }
+            Self::Weekly(weekday, time) => {
+                let today = tz.today(now);
+                (0..=14)
+                    .map(|offset| today + Duration::days(offset))
+                    .filter(|date| date.weekday() == *weekday)
+                    .filter_map(|date| tz.resolve(date.and_time(*time)))
+                    .find(|dt| *dt > now)
+            }
+        }
+    }
+}
+
+/// 将 `target_time` 参数（字符串或字符串数组）展开为规格字符串列表
+pub fn collect_target_specs(value: &serde_json::Value) -> Result<Vec<String>, String> {
+    let specs: Vec<String> = match value {
+        serde_json::Value::String(s) => s.split(',').map(|p| p.trim().to_string()).collect(),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(|item| {
+                item.as_str()
+                    .map(|s| s.trim().to_string())
+                    .ok_or_else(|| format!("target_time entries must be strings, got {}", item))
+            })
+            .collect::<Result<_, _>>()?,
+        other => {
+            return Err(format!(
+                "target_time must be a string or array, got {}",
+                other
+            ))
+        }
+    };
+    let specs: Vec<String> = specs.into_iter().filter(|s| !s.is_empty()).collect();
+    if specs.is_empty() {
+        return Err("target_time is empty".to_string());
+    }
+    Ok(specs)
+}
+
+/// 计算多个目标时间中最近的一个，返回目标时刻及对应的规格字符串
+pub fn next_wait_target(
+    specs: &[String],
+    tz: WaitTimezone,
+    now: DateTime<Utc>,
+) -> Result<(DateTime<Utc>, String), String> {
+    let mut best: Option<(DateTime<Utc>, String)> = None;
+    for spec in specs {
+        let parsed = parse_spec(spec)?;
+        if let Some(target) = parsed.next_after(tz, now) {
+            if best.as_ref().is_none_or(|(current, _)| target < *current) {
+                best = Some((target, spec.clone()));
+            }
+        }
+    }
+    best.ok_or_else(|| format!("All target times are in the past: {}", specs.join(", ")))
+}
@@ -40,7 +40,12 @@ import { useTranslation } from 'react-i18next';
 import { invoke } from '@tauri-apps/api/core';
 import { register, unregisterAll } from '@tauri-apps/plugin-global-shortcut';
 import { loggers } from '@/utils/logger';
-import { useMaaCallbackLogger, useMaaAgentLogger } from '@/utils/useMaaCallbackLogger';
+import {
+  useMaaCallbackLogger,
+  useMaaAgentLogger,
+  backfillAgentOutput,
+} from '@/utils/useMaaCallbackLogger';
+import { checkMxuSpecialTasksAgainstBackend } from '@/utils/specialTaskSchema';
 import { getInterfaceLangKey } from '@/i18n';
 import { applyTheme, resolveThemeMode } from '@/themes';
 import {
@@ -63,6 +68,9 @@ type LoadingState = 'loading' | 'success' | 'error';
 // 页面过渡动画时长（ms）
 const PAGE_TRANSITION_DURATION = 120;
 
+/** MXU_POWER 通知中的电源操作，名称随界面语言下发到后端 */
+const POWER_ACTION_KEYS = ['shutdown', 'restart', 'screenoff', 'sleep', 'hibernate', 'logoff'];
+
 function App() {
   const [loadingState, setLoadingState] = useState<LoadingState>('loading');
   const [errorMessage, setErrorMessage] = useState<string>('');
@@ -294,6 +302,29 @@ function App() {
     }
   }, [projectInterface, language, interfaceTranslations]);
 
+  // 同步运行结束自动通知的文案到后端（随界面语言切换）
+  useEffect(() => {
+    maaService
+      .setNotificationMessages({
+        title: 'MXU',
+        run_succeeded: t('notificationChannels.messages.runSucceeded'),
+        run_failed: t('notificationChannels.messages.runFailed'),
+        run_failed_with_error: t('notificationChannels.messages.runFailedWithError'),
+        power_countdown: t('notificationChannels.messages.powerCountdown'),
+        power_cancelled: t('notificationChannels.messages.powerCancelled'),
+        power_skipped: t('notificationChannels.messages.powerSkipped'),
+        power_actions: Object.fromEntries(
+          POWER_ACTION_KEYS.map((key) => [
+            key,
+            t(`notificationChannels.messages.powerActions.${key}`),
+          ]),
+        ),
+      })
+      .catch((err) => {
+        log.warn('同步通知文案失败:', err);
+      });
+  }, [t, language]);
+
   // 设置窗口图标（根据 ProjectInterface V2 协议）
   useEffect(() => {
     if (!projectInterface?.icon || !isTauri()) return;
@@ -400,6 +431,10 @@ function App() {
         if (backendStates) {
           restoreBackendStates(backendStates);
           log.info('已恢复后端状态:', Object.keys(backendStates.instances).length, '个实例');
+          // 回填刷新前 agent 的输出
+          for (const instanceId of Object.keys(backendStates.instances)) {
+            await backfillAgentOutput(instanceId);
+          }
         }
       } catch (err) {
         log.warn('恢复后端状态失败:', err);
@@ -623,6 +658,11 @@ function App() {
         } catch (err) {
           log.warn('检查程序路径失败:', err);
         }
+
+        // 开发模式下校验特殊任务定义与后端动作参数是否一致
+        if (import.meta.env.DEV) {
+          checkMxuSpecialTasksAgainstBackend();
+        }
       }
 
       // 路径没问题，继续加载 interface
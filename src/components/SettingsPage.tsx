@@ -1,6 +1,17 @@
 import { useState, useEffect, useRef, useCallback, useMemo } from 'react';
 import { useTranslation } from 'react-i18next';
-import { ArrowLeft, Paintbrush, Key, Settings2, Download, Bug, Info } from 'lucide-react';
+import {
+  ArrowLeft,
+  Paintbrush,
+  Key,
+  Bell,
+  Database,
+  Puzzle,
+  Settings2,
+  Download,
+  Bug,
+  Info,
+} from 'lucide-react';
 import clsx from 'clsx';
 
 import { useAppStore } from '@/stores/appStore';
@@ -9,6 +20,9 @@ import { ConfirmDialog } from './ConfirmDialog';
 import {
   AppearanceSection,
   HotkeySection,
+  NotificationSection,
+  VariablesSection,
+  PluginsSection,
   GeneralSection,
   UpdateSection,
   DebugSection,
@@ -124,6 +138,9 @@ export function SettingsPage({ onClose }: SettingsPageProps) {
     const items = [{ id: 'appearance', icon: Paintbrush, labelKey: 'settings.appearance' }];
     items.push({ id: 'general', icon: Settings2, labelKey: 'settings.general' });
     items.push({ id: 'hotkeys', icon: Key, labelKey: 'settings.hotkeys' });
+    items.push({ id: 'notifications', icon: Bell, labelKey: 'notificationChannels.title' });
+    items.push({ id: 'variables', icon: Database, labelKey: 'persistentVariables.title' });
+    items.push({ id: 'plugins', icon: Puzzle, labelKey: 'plugins.title' });
     if (projectInterface?.mirrorchyan_rid) {
       items.push({ id: 'update', icon: Download, labelKey: 'mirrorChyan.title' });
     }
@@ -236,6 +253,15 @@ export function SettingsPage({ onClose }: SettingsPageProps) {
             {/* 快捷键设置 */}
             <HotkeySection />
 
+            {/* 通知渠道 */}
+            <NotificationSection />
+
+            {/* 持久化变量 */}
+            <VariablesSection />
+
+            {/* 插件 */}
+            <PluginsSection />
+
             {/* MirrorChyan 更新设置 */}
             <UpdateSection />
 
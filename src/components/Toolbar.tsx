@@ -7,15 +7,18 @@ import {
   ChevronsDownUp,
   Plus,
   Play,
+  Pause,
   StopCircle,
   Loader2,
+  Timer,
+  X,
 } from 'lucide-react';
 import { useAppStore } from '@/stores/appStore';
 import { maaService } from '@/services/maaService';
 import clsx from 'clsx';
 import { loggers, generateTaskPipelineOverride, computeResourcePaths } from '@/utils';
 import { getMxuSpecialTask } from '@/types/specialTasks';
-import type { TaskConfig, ControllerConfig } from '@/types/maa';
+import type { TaskConfig, ControllerConfig, ActionCountdownEvent } from '@/types/maa';
 import { normalizeAgentConfigs } from '@/types/interface';
 import { parseWin32ScreencapMethod, parseWin32InputMethod } from '@/types/maa';
 import { SchedulePanel } from './SchedulePanel';
@@ -93,6 +96,10 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
 
   // 自动连接状态
   const [autoConnectPhase, setAutoConnectPhase] = useState<AutoConnectPhase>('idle');
+  // 启动期间仍在连接的 agent 序号
+  const [connectingAgents, setConnectingAgents] = useState<number[]>([]);
+  // 当前实例正在进行的等待类动作倒计时
+  const [actionCountdown, setActionCountdown] = useState<ActionCountdownEvent | null>(null);
   const [autoConnectError, setAutoConnectError] = useState<string | null>(null);
 
   // 权限提示弹窗状态
@@ -125,6 +132,58 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
   // 只要有启用的任务就可以运行（连接和资源加载会在 startTasksForInstance 中自动处理）
   const canRun = tasks.some((t) => t.enabled);
 
+  // 显示当前实例 MXU_SLEEP / MXU_WAITUNTIL 等动作的剩余等待时间
+  useEffect(() => {
+    setActionCountdown(null);
+    if (!instance?.isRunning || pendingTaskIds.length === 0) return;
+
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+    maaService
+      .onActionCountdown((event) => {
+        if (!pendingTaskIds.includes(event.task_id)) return;
+        setActionCountdown(event.remaining_secs > 0 ? event : null);
+      })
+      .then((fn) => {
+        if (disposed) fn();
+        else unlisten = fn;
+      });
+
+    return () => {
+      disposed = true;
+      if (unlisten) unlisten();
+    };
+  }, [instance?.isRunning, pendingTaskIds]);
+
+  // 启动期间跟踪各 agent 的连接进度，在启动按钮上显示仍在连接的 agent
+  useEffect(() => {
+    if (!isStarting || !instanceId) {
+      setConnectingAgents([]);
+      return;
+    }
+
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+    maaService
+      .onAgentProgress(({ instance_id, agent_index, stage }) => {
+        if (instance_id !== instanceId) return;
+        setConnectingAgents((prev) => {
+          const rest = prev.filter((index) => index !== agent_index);
+          if (stage === 'connected' || stage === 'failed') return rest;
+          return [...rest, agent_index].sort((a, b) => a - b);
+        });
+      })
+      .then((fn) => {
+        if (disposed) fn();
+        else unlisten = fn;
+      });
+
+    return () => {
+      disposed = true;
+      if (unlisten) unlisten();
+    };
+  }, [isStarting, instanceId]);
+
   // 监听任务完成回调
   useEffect(() => {
     if (pendingTaskIds.length === 0) return;
@@ -178,7 +237,7 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
             }
 
             setInstanceTaskStatus(runningInstanceId, 'Succeeded');
-            updateInstance(runningInstanceId, { isRunning: false });
+            updateInstance(runningInstanceId, { isRunning: false, isPaused: false });
             setInstanceCurrentTaskId(runningInstanceId, null);
             clearPendingTasks(runningInstanceId);
             runningInstanceIdRef.current = null;
@@ -220,7 +279,7 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
             }
 
             setInstanceTaskStatus(runningInstanceId, 'Failed');
-            updateInstance(runningInstanceId, { isRunning: false });
+            updateInstance(runningInstanceId, { isRunning: false, isPaused: false });
             setInstanceCurrentTaskId(runningInstanceId, null);
             clearPendingTasks(runningInstanceId);
             runningInstanceIdRef.current = null;
@@ -809,7 +868,7 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
         // 准备 Agent 配置（支持单个或多个 Agent）
         const agentConfigs = normalizeAgentConfigs(projectInterface?.agent);
 
-        updateInstance(targetId, { isRunning: true });
+        updateInstance(targetId, { isRunning: true, isPaused: false });
         setInstanceTaskStatus(targetId, 'Running');
         setShowAddTaskPanel(false);
 
@@ -883,7 +942,7 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
           }
         }
 
-        updateInstance(targetId, { isRunning: false });
+        updateInstance(targetId, { isRunning: false, isPaused: false });
         setInstanceTaskStatus(targetId, 'Failed');
         clearTaskRunStatus(targetId);
         clearPendingTasks(targetId);
@@ -1091,7 +1150,7 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
         // 任务已停止后再断开 agent，避免释放顺序问题
         await maaService.stopAgent(targetInstanceId);
       }
-      updateInstance(targetInstanceId, { isRunning: false });
+      updateInstance(targetInstanceId, { isRunning: false, isPaused: false });
       setInstanceTaskStatus(targetInstanceId, null);
       setInstanceCurrentTaskId(targetInstanceId, null);
       clearTaskRunStatus(targetInstanceId);
@@ -1103,6 +1162,29 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
     }
   };
 
+  const handlePauseResume = async () => {
+    if (!instance?.isRunning || isStopping) return;
+    const paused = !instance.isPaused;
+    try {
+      if (paused) {
+        await maaService.pauseTask(instance.id);
+      } else {
+        await maaService.resumeTask(instance.id);
+      }
+      updateInstance(instance.id, { isPaused: paused });
+    } catch (err) {
+      log.error(paused ? '暂停任务失败:' : '恢复任务失败:', err);
+    }
+  };
+
+  const handleCancelPowerAction = async (cancelId: number) => {
+    try {
+      await maaService.cancelPowerAction(cancelId);
+    } catch (err) {
+      log.error('取消电源操作失败:', err);
+    }
+  };
+
   const handleStartStop = async () => {
     if (!instance) return;
 
@@ -1237,6 +1319,11 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
 
   // 获取启动按钮的文本
   const getStartButtonText = () => {
+    if (isStarting && connectingAgents.length > 0) {
+      return t('taskList.autoConnect.waitingAgents', {
+        agents: connectingAgents.map((index) => `#${index + 1}`).join(', '),
+      });
+    }
     if (isStarting) {
       switch (autoConnectPhase) {
         case 'searching':
@@ -1344,6 +1431,46 @@ export function Toolbar({ showAddPanel, onToggleAddPanel }: ToolbarProps) {
           onRestart={handleRestartAsAdmin}
         />
 
+        {/* 等待类动作倒计时 */}
+        {actionCountdown && (
+          <span
+            className="flex items-center gap-1 px-2 py-1 rounded-md text-xs text-text-secondary bg-bg-tertiary"
+            title={`${actionCountdown.action} · ${actionCountdown.node}`}
+          >
+            <Timer className="w-3.5 h-3.5" />
+            {t('taskList.actionCountdown', {
+              remaining: actionCountdown.remaining_secs,
+              total: actionCountdown.total_secs,
+            })}
+            {actionCountdown.cancel_id != null && (
+              <button
+                onClick={() => handleCancelPowerAction(actionCountdown.cancel_id!)}
+                className="ml-1 p-0.5 rounded hover:bg-bg-hover hover:text-error transition-colors"
+                title={t('taskList.cancelPowerAction')}
+              >
+                <X className="w-3.5 h-3.5" />
+              </button>
+            )}
+          </span>
+        )}
+
+        {/* 暂停/恢复按钮 */}
+        {instance?.isRunning && !isStopping && (
+          <button
+            data-role="pause-resume-button"
+            onClick={handlePauseResume}
+            className={clsx(
+              'flex items-center gap-2 px-4 py-2 rounded-lg text-sm font-medium transition-colors',
+              instance.isPaused
+                ? 'bg-accent hover:bg-accent-hover text-white'
+                : 'bg-warning hover:bg-warning/90 text-white',
+            )}
+          >
+            {instance.isPaused ? <Play className="w-4 h-4" /> : <Pause className="w-4 h-4" />}
+            <span>{instance.isPaused ? t('taskList.resumeTasks') : t('taskList.pauseTasks')}</span>
+          </button>
+        )}
+
         {/* 开始/停止按钮 */}
         <button
           data-role="start-stop-button"
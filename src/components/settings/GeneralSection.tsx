@@ -12,7 +12,11 @@ import {
   Gamepad2,
   ChevronDown,
   Check,
+  FolderLock,
+  FolderPlus,
+  X,
 } from 'lucide-react';
+import { open } from '@tauri-apps/plugin-dialog';
 
 import { useAppStore } from '@/stores/appStore';
 import { defaultWindowSize } from '@/types/config';
@@ -39,6 +43,8 @@ export function GeneralSection() {
     autoStartRemovedInstanceName,
     gamePath,
     setGamePath,
+    fileActionRoots,
+    setFileActionRoots,
   } = useAppStore();
 
   // 开机自启动状态（直接从 Tauri 插件查询，不走 store）
@@ -49,6 +55,17 @@ export function GeneralSection() {
   const [instanceDropdownOpen, setInstanceDropdownOpen] = useState(false);
   const dropdownRef = useRef<HTMLDivElement>(null);
 
+  const handleAddFileActionRoot = async () => {
+    try {
+      const selected = await open({ directory: true, multiple: false });
+      if (selected && typeof selected === 'string' && !fileActionRoots.includes(selected)) {
+        setFileActionRoots([...fileActionRoots, selected]);
+      }
+    } catch (err) {
+      console.error('Failed to open folder dialog:', err);
+    }
+  };
+
   useEffect(() => {
     if (!isTauri()) return;
     import('@tauri-apps/plugin-autostart').then(({ isEnabled }) => {
@@ -258,6 +275,53 @@ export function GeneralSection() {
         />
       </div>
 
+      {/* 文件操作允许目录（MXU_FILE 动作只能访问这些目录） */}
+      {isTauri() && (
+        <div className="bg-bg-secondary rounded-xl p-4 border border-border">
+          <div className="flex items-center justify-between gap-3">
+            <div className="flex items-center gap-3">
+              <FolderLock className="w-5 h-5 text-accent" />
+              <div>
+                <span className="font-medium text-text-primary">
+                  {t('settings.fileActionRoots')}
+                </span>
+                <p className="text-xs text-text-muted mt-0.5">
+                  {t('settings.fileActionRootsHint')}
+                </p>
+              </div>
+            </div>
+            <button
+              onClick={handleAddFileActionRoot}
+              className="flex items-center gap-1.5 px-3 py-1.5 text-sm bg-bg-tertiary hover:bg-bg-hover rounded-lg transition-colors shrink-0"
+            >
+              <FolderPlus className="w-4 h-4" />
+              {t('settings.fileActionRootsAdd')}
+            </button>
+          </div>
+          {fileActionRoots.length > 0 && (
+            <div className="mt-3 space-y-1.5">
+              {fileActionRoots.map((root) => (
+                <div
+                  key={root}
+                  className="flex items-center gap-2 px-3 py-1.5 text-sm rounded-md bg-bg-tertiary"
+                >
+                  <span className="flex-1 truncate text-text-secondary" title={root}>
+                    {root}
+                  </span>
+                  <button
+                    onClick={() => setFileActionRoots(fileActionRoots.filter((r) => r !== root))}
+                    className="p-0.5 rounded text-text-muted hover:text-error transition-colors"
+                    title={t('common.delete')}
+                  >
+                    <X className="w-3.5 h-3.5" />
+                  </button>
+                </div>
+              ))}
+            </div>
+          )}
+        </div>
+      )}
+
       {/* ④ 最小化到托盘 */}
       <div className="bg-bg-secondary rounded-xl p-4 border border-border">
         <div className="flex items-center justify-between">
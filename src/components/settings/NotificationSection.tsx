@@ -0,0 +1,472 @@
+import { useState, useEffect } from 'react';
+import { useTranslation } from 'react-i18next';
+import { Bell, Plus, Save, Send, Trash2, Loader2 } from 'lucide-react';
+import clsx from 'clsx';
+
+import { maaService } from '@/services/maaService';
+import type { NotificationChannel, NotificationEvent } from '@/types/maa';
+import { loggers } from '@/utils/logger';
+import { isTauri } from '@/utils/paths';
+import { SwitchButton } from '@/components/FormControls';
+
+type ChannelType = NotificationChannel['type'];
+
+interface FieldSpec {
+  /** 渠道配置中的字段名 */
+  key: string;
+  /** notificationChannels.fields 下的翻译键 */
+  label: string;
+  kind?: 'text' | 'password' | 'number' | 'list' | 'headers' | 'textarea';
+  placeholder?: string;
+  /** 下拉选项（设置后渲染为 select） */
+  options?: string[];
+  /** 必填字段清空时保留空字符串，可选字段清空时置为 null */
+  required?: boolean;
+}
+
+const CHANNEL_TYPES: ChannelType[] = [
+  'telegram',
+  'discord',
+  'slack',
+  'bark',
+  'server_chan',
+  'ntfy',
+  'smtp',
+  'http',
+];
+
+const CHANNEL_FIELDS: Record<ChannelType, FieldSpec[]> = {
+  smtp: [
+    { key: 'host', label: 'host', placeholder: 'smtp.example.com', required: true },
+    { key: 'port', label: 'port', kind: 'number', placeholder: '465' },
+    { key: 'security', label: 'security', options: ['tls', 'starttls', 'none'] },
+    { key: 'username', label: 'username' },
+    { key: 'password', label: 'password', kind: 'password' },
+    { key: 'from', label: 'from', placeholder: 'MXU <mxu@example.com>', required: true },
+    { key: 'to', label: 'to', kind: 'list', placeholder: 'a@example.com, b@example.com' },
+  ],
+  telegram: [
+    { key: 'bot_token', label: 'botToken', kind: 'password', required: true },
+    { key: 'chat_id', label: 'chatId', required: true },
+    { key: 'api_base', label: 'apiBase', placeholder: 'https://api.telegram.org' },
+  ],
+  discord: [
+    {
+      key: 'webhook_url',
+      label: 'webhookUrl',
+      placeholder: 'https://discord.com/api/webhooks/...',
+      required: true,
+    },
+  ],
+  slack: [
+    {
+      key: 'webhook_url',
+      label: 'webhookUrl',
+      placeholder: 'https://hooks.slack.com/services/...',
+      required: true,
+    },
+  ],
+  bark: [
+    { key: 'device_key', label: 'deviceKey', kind: 'password', required: true },
+    { key: 'server', label: 'server', placeholder: 'https://api.day.app' },
+    { key: 'group', label: 'group' },
+    { key: 'sound', label: 'sound' },
+  ],
+  server_chan: [
+    { key: 'send_key', label: 'sendKey', kind: 'password', required: true },
+    { key: 'server', label: 'server' },
+  ],
+  ntfy: [
+    { key: 'topic', label: 'topic', required: true },
+    { key: 'server', label: 'server', placeholder: 'https://ntfy.sh' },
+    { key: 'token', label: 'token', kind: 'password' },
+    { key: 'priority', label: 'priority', kind: 'number', placeholder: '3' },
+  ],
+  http: [
+    { key: 'url', label: 'url', placeholder: 'https://example.com/webhook', required: true },
+    { key: 'method', label: 'method', options: ['POST', 'PUT', 'GET'] },
+    { key: 'headers', label: 'headers', kind: 'headers', placeholder: 'Authorization: Bearer xxx' },
+    {
+      key: 'body',
+      label: 'body',
+      kind: 'textarea',
+      placeholder: '{"title":"{title}","body":"{body}"}',
+    },
+  ],
+};
+
+const NOTIFICATION_EVENTS: NotificationEvent[] = ['run_succeeded', 'run_failed'];
+
+const inputClass =
+  'w-full px-3 py-2 rounded-lg bg-bg-tertiary border border-border text-sm text-text-primary focus:outline-none focus:ring-2 focus:ring-accent/50';
+
+/** 创建指定类型的空渠道 */
+function createChannel(type: ChannelType, name: string): NotificationChannel {
+  const base = {
+    id: `${type}-${Date.now().toString(36)}`,
+    name,
+    enabled: true,
+    events: [] as NotificationEvent[],
+  };
+  switch (type) {
+    case 'smtp':
+      return { ...base, type, host: '', from: '', to: [] };
+    case 'telegram':
+      return { ...base, type, bot_token: '', chat_id: '' };
+    case 'discord':
+    case 'slack':
+      return { ...base, type, webhook_url: '' };
+    case 'bark':
+      return { ...base, type, device_key: '' };
+    case 'server_chan':
+      return { ...base, type, send_key: '' };
+    case 'ntfy':
+      return { ...base, type, topic: '' };
+    case 'http':
+      return { ...base, type, url: '' };
+  }
+}
+
+/** 将 "Name: value" 多行文本解析为请求头 */
+function parseHeaders(text: string): Record<string, string> | null {
+  const headers: Record<string, string> = {};
+  for (const line of text.split('\n')) {
+    const index = line.indexOf(':');
+    if (index <= 0) continue;
+    headers[line.slice(0, index).trim()] = line.slice(index + 1).trim();
+  }
+  return Object.keys(headers).length > 0 ? headers : null;
+}
+
+function formatHeaders(headers: unknown): string {
+  if (!headers || typeof headers !== 'object') return '';
+  return Object.entries(headers as Record<string, string>)
+    .map(([name, value]) => `${name}: ${value}`)
+    .join('\n');
+}
+
+/** 请求头编辑框：保留未完成的行，仅向上同步解析结果 */
+function HeadersInput({
+  value,
+  placeholder,
+  onChange,
+}: {
+  value: unknown;
+  placeholder?: string;
+  onChange: (headers: Record<string, string> | null) => void;
+}) {
+  const [text, setText] = useState(() => formatHeaders(value));
+  return (
+    <textarea
+      value={text}
+      rows={2}
+      placeholder={placeholder}
+      onChange={(e) => {
+        setText(e.target.value);
+        onChange(parseHeaders(e.target.value));
+      }}
+      className={clsx(inputClass, 'font-mono resize-y')}
+    />
+  );
+}
+
+function ChannelField({
+  field,
+  channel,
+  onChange,
+}: {
+  field: FieldSpec;
+  channel: NotificationChannel;
+  onChange: (value: unknown) => void;
+}) {
+  const raw = (channel as unknown as Record<string, unknown>)[field.key];
+  const emptyValue = field.required ? '' : null;
+
+  if (field.kind === 'headers') {
+    return <HeadersInput value={raw} placeholder={field.placeholder} onChange={onChange} />;
+  }
+  if (field.options) {
+    return (
+      <select
+        value={(raw as string | null) ?? ''}
+        onChange={(e) => onChange(e.target.value || null)}
+        className={inputClass}
+      >
+        <option value="">-</option>
+        {field.options.map((option) => (
+          <option key={option} value={option}>
+            {option}
+          </option>
+        ))}
+      </select>
+    );
+  }
+  if (field.kind === 'textarea') {
+    return (
+      <textarea
+        value={(raw as string | null) ?? ''}
+        rows={2}
+        placeholder={field.placeholder}
+        onChange={(e) => onChange(e.target.value || emptyValue)}
+        className={clsx(inputClass, 'font-mono resize-y')}
+      />
+    );
+  }
+
+  let value = (raw as string | number | null | undefined) ?? '';
+  if (field.kind === 'list') {
+    value = Array.isArray(raw) ? raw.join(', ') : '';
+  }
+  return (
+    <input
+      type={field.kind === 'password' ? 'password' : field.kind === 'number' ? 'number' : 'text'}
+      value={value}
+      placeholder={field.placeholder}
+      onChange={(e) => {
+        const text = e.target.value;
+        if (field.kind === 'list') {
+          onChange(text.split(',').map((item) => item.trimStart()));
+        } else if (field.kind === 'number') {
+          const num = parseInt(text, 10);
+          onChange(isNaN(num) ? null : num);
+        } else {
+          onChange(text || emptyValue);
+        }
+      }}
+      className={inputClass}
+    />
+  );
+}
+
+export function NotificationSection() {
+  const { t } = useTranslation();
+  const [channels, setChannels] = useState<NotificationChannel[]>([]);
+  const [newType, setNewType] = useState<ChannelType>('telegram');
+  const [dirty, setDirty] = useState(false);
+  const [saving, setSaving] = useState(false);
+  const [testingId, setTestingId] = useState<string | null>(null);
+  const [loadError, setLoadError] = useState<string | null>(null);
+  // 最近一次保存 / 测试的结果，id 为空表示保存结果
+  const [result, setResult] = useState<{ id?: string; ok: boolean; message: string } | null>(null);
+
+  useEffect(() => {
+    if (!isTauri()) return;
+    maaService
+      .getNotificationChannels()
+      .then(setChannels)
+      .catch((err) => {
+        loggers.ui.error('加载通知渠道失败:', err);
+        setLoadError(String(err));
+      });
+  }, []);
+
+  const updateChannel = (id: string, patch: Record<string, unknown>) => {
+    setChannels((prev) =>
+      prev.map((c) => (c.id === id ? ({ ...c, ...patch } as NotificationChannel) : c)),
+    );
+    setDirty(true);
+  };
+
+  const handleAdd = () => {
+    setChannels((prev) => [
+      ...prev,
+      createChannel(newType, t(`notificationChannels.types.${newType}`)),
+    ]);
+    setDirty(true);
+  };
+
+  const handleDelete = (id: string) => {
+    setChannels((prev) => prev.filter((c) => c.id !== id));
+    setDirty(true);
+  };
+
+  const toggleEvent = (channel: NotificationChannel, event: NotificationEvent) => {
+    const events = channel.events.includes(event)
+      ? channel.events.filter((e) => e !== event)
+      : [...channel.events, event];
+    updateChannel(channel.id, { events });
+  };
+
+  const handleSave = async () => {
+    setSaving(true);
+    try {
+      const cleaned = channels.map((c) =>
+        c.type === 'smtp' ? { ...c, to: c.to.map((to) => to.trim()).filter(Boolean) } : c,
+      );
+      await maaService.saveNotificationChannels(cleaned);
+      setChannels(cleaned);
+      setDirty(false);
+      setResult({ ok: true, message: t('notificationChannels.saved') });
+    } catch (err) {
+      loggers.ui.error('保存通知渠道失败:', err);
+      setResult({ ok: false, message: String(err) });
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleTest = async (channel: NotificationChannel) => {
+    setTestingId(channel.id);
+    try {
+      await maaService.testNotificationChannel(channel, t('notificationChannels.testMessage'));
+      setResult({ id: channel.id, ok: true, message: t('notificationChannels.testSucceeded') });
+    } catch (err) {
+      setResult({ id: channel.id, ok: false, message: String(err) });
+    } finally {
+      setTestingId(null);
+    }
+  };
+
+  return (
+    <section id="section-notifications" className="space-y-4 scroll-mt-4">
+      <h2 className="text-sm font-semibold text-text-primary uppercase tracking-wider flex items-center gap-2">
+        <Bell className="w-4 h-4" />
+        {t('notificationChannels.title')}
+      </h2>
+
+      <div className="bg-bg-secondary rounded-xl p-4 border border-border space-y-4">
+        <p className="text-xs text-text-muted">{t('notificationChannels.hint')}</p>
+        <p className="text-xs text-warning">{t('notificationChannels.secretsHint')}</p>
+
+        {loadError && (
+          <p className="text-xs text-error">
+            {t('notificationChannels.loadFailed', { error: loadError })}
+          </p>
+        )}
+
+        {channels.length === 0 && !loadError && (
+          <p className="text-sm text-text-muted text-center py-2">
+            {t('notificationChannels.empty')}
+          </p>
+        )}
+
+        {channels.map((channel) => (
+          <div
+            key={channel.id}
+            className="rounded-lg border border-border bg-bg-primary p-3 space-y-3"
+          >
+            <div className="flex items-center gap-2">
+              <span className="px-2 py-0.5 rounded text-xs bg-accent/10 text-accent whitespace-nowrap">
+                {t(`notificationChannels.types.${channel.type}`)}
+              </span>
+              <input
+                type="text"
+                value={channel.name}
+                placeholder={t('notificationChannels.name')}
+                onChange={(e) => updateChannel(channel.id, { name: e.target.value })}
+                className={clsx(inputClass, 'flex-1 py-1.5')}
+              />
+              <SwitchButton
+                value={channel.enabled}
+                onChange={(enabled) => updateChannel(channel.id, { enabled })}
+              />
+              <button
+                onClick={() => handleTest(channel)}
+                disabled={testingId !== null}
+                className="p-2 rounded-lg text-text-secondary hover:bg-bg-hover transition-colors disabled:opacity-50"
+                title={t('notificationChannels.test')}
+              >
+                {testingId === channel.id ? (
+                  <Loader2 className="w-4 h-4 animate-spin" />
+                ) : (
+                  <Send className="w-4 h-4" />
+                )}
+              </button>
+              <button
+                onClick={() => handleDelete(channel.id)}
+                className="p-2 rounded-lg text-text-secondary hover:bg-bg-hover hover:text-error transition-colors"
+                title={t('notificationChannels.delete')}
+              >
+                <Trash2 className="w-4 h-4" />
+              </button>
+            </div>
+
+            <p className="text-[10px] text-text-muted font-mono">ID: {channel.id}</p>
+
+            <div className="grid grid-cols-1 sm:grid-cols-2 gap-3">
+              {CHANNEL_FIELDS[channel.type].map((field) => (
+                <div
+                  key={field.key}
+                  className={clsx(
+                    'space-y-1',
+                    (field.kind === 'headers' || field.kind === 'textarea') && 'sm:col-span-2',
+                  )}
+                >
+                  <label className="text-xs text-text-secondary">
+                    {t(`notificationChannels.fields.${field.label}`)}
+                  </label>
+                  <ChannelField
+                    field={field}
+                    channel={channel}
+                    onChange={(value) => updateChannel(channel.id, { [field.key]: value })}
+                  />
+                </div>
+              ))}
+            </div>
+
+            <div className="flex flex-wrap items-center gap-4">
+              <span className="text-xs text-text-secondary">
+                {t('notificationChannels.events')}
+              </span>
+              {NOTIFICATION_EVENTS.map((event) => (
+                <label
+                  key={event}
+                  className="flex items-center gap-1.5 text-xs text-text-primary cursor-pointer"
+                >
+                  <input
+                    type="checkbox"
+                    checked={channel.events.includes(event)}
+                    onChange={() => toggleEvent(channel, event)}
+                    className="accent-accent"
+                  />
+                  {t(`notificationChannels.eventNames.${event}`)}
+                </label>
+              ))}
+            </div>
+
+            {result?.id === channel.id && (
+              <p className={clsx('text-xs', result.ok ? 'text-success' : 'text-error')}>
+                {result.message}
+              </p>
+            )}
+          </div>
+        ))}
+
+        <div className="flex flex-wrap items-center gap-2">
+          <select
+            value={newType}
+            onChange={(e) => setNewType(e.target.value as ChannelType)}
+            className="px-3 py-2 rounded-lg bg-bg-tertiary border border-border text-sm text-text-primary focus:outline-none focus:ring-2 focus:ring-accent/50"
+          >
+            {CHANNEL_TYPES.map((type) => (
+              <option key={type} value={type}>
+                {t(`notificationChannels.types.${type}`)}
+              </option>
+            ))}
+          </select>
+          <button
+            onClick={handleAdd}
+            className="flex items-center gap-2 px-3 py-2 text-sm bg-bg-tertiary hover:bg-bg-hover rounded-lg transition-colors"
+          >
+            <Plus className="w-4 h-4" />
+            {t('notificationChannels.add')}
+          </button>
+          <div className="flex-1" />
+          {result && !result.id && (
+            <span className={clsx('text-xs', result.ok ? 'text-success' : 'text-error')}>
+              {result.message}
+            </span>
+          )}
+          <button
+            onClick={handleSave}
+            disabled={!dirty || saving}
+            className="flex items-center gap-2 px-3 py-2 text-sm bg-accent text-white hover:bg-accent-hover rounded-lg transition-colors disabled:opacity-50"
+          >
+            {saving ? <Loader2 className="w-4 h-4 animate-spin" /> : <Save className="w-4 h-4" />}
+            {t('notificationChannels.save')}
+          </button>
+        </div>
+      </div>
+    </section>
+  );
+}
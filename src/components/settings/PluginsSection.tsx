@@ -0,0 +1,116 @@
+import { useState, useEffect, useCallback } from 'react';
+import { useTranslation } from 'react-i18next';
+import { Puzzle, RefreshCw } from 'lucide-react';
+import clsx from 'clsx';
+
+import { maaService } from '@/services/maaService';
+import { useAppStore } from '@/stores/appStore';
+import type { PluginInfo } from '@/types/maa';
+import { loggers } from '@/utils/logger';
+import { isTauri } from '@/utils/paths';
+import { SwitchButton } from '@/components/FormControls';
+
+const STATUS_CLASS: Record<PluginInfo['status'], string> = {
+  loaded: 'bg-success/10 text-success',
+  failed: 'bg-error/10 text-error',
+  loading: 'bg-accent/10 text-accent',
+  not_loaded: 'bg-bg-tertiary text-text-muted',
+};
+
+export function PluginsSection() {
+  const { t } = useTranslation();
+  const { allowedPlugins, setAllowedPlugins } = useAppStore();
+  const [plugins, setPlugins] = useState<PluginInfo[]>([]);
+  const [loadError, setLoadError] = useState<string | null>(null);
+
+  const reload = useCallback(async () => {
+    if (!isTauri()) return;
+    try {
+      setPlugins(await maaService.getPlugins());
+      setLoadError(null);
+    } catch (err) {
+      loggers.ui.error('加载插件列表失败:', err);
+      setLoadError(String(err));
+    }
+  }, []);
+
+  useEffect(() => {
+    reload();
+  }, [reload, allowedPlugins]);
+
+  const toggleAllowed = (dir: string, allowed: boolean) => {
+    setAllowedPlugins(allowed ? [...allowedPlugins, dir] : allowedPlugins.filter((d) => d !== dir));
+  };
+
+  return (
+    <section id="section-plugins" className="space-y-4 scroll-mt-4">
+      <h2 className="text-sm font-semibold text-text-primary uppercase tracking-wider flex items-center gap-2">
+        <Puzzle className="w-4 h-4" />
+        {t('plugins.title')}
+      </h2>
+
+      <div className="bg-bg-secondary rounded-xl p-4 border border-border space-y-4">
+        <div className="flex items-start gap-2">
+          <p className="flex-1 text-xs text-text-muted">{t('plugins.hint')}</p>
+          <button
+            onClick={reload}
+            className="p-2 rounded-lg text-text-secondary hover:bg-bg-hover transition-colors"
+            title={t('plugins.refresh')}
+          >
+            <RefreshCw className="w-4 h-4" />
+          </button>
+        </div>
+
+        {loadError && (
+          <p className="text-xs text-error">{t('plugins.loadFailed', { error: loadError })}</p>
+        )}
+
+        {plugins.length === 0 && !loadError && (
+          <p className="text-sm text-text-muted text-center py-2">{t('plugins.empty')}</p>
+        )}
+
+        {plugins.map((plugin) => (
+          <div
+            key={plugin.dir}
+            className="rounded-lg border border-border bg-bg-primary p-3 space-y-2"
+          >
+            <div className="flex items-center gap-2">
+              <span className="text-sm font-medium text-text-primary truncate">{plugin.name}</span>
+              {plugin.kind && (
+                <span className="px-2 py-0.5 rounded text-xs bg-accent/10 text-accent whitespace-nowrap">
+                  {t(`plugins.kinds.${plugin.kind}`)}
+                </span>
+              )}
+              <span
+                className={clsx(
+                  'px-2 py-0.5 rounded text-xs whitespace-nowrap',
+                  STATUS_CLASS[plugin.status],
+                )}
+              >
+                {t(`plugins.status.${plugin.status}`)}
+              </span>
+              <div className="flex-1" />
+              <span className="text-xs text-text-secondary">{t('plugins.allow')}</span>
+              <SwitchButton
+                value={plugin.allowed}
+                onChange={(allowed) => toggleAllowed(plugin.dir, allowed)}
+              />
+            </div>
+
+            <p className="text-[10px] text-text-muted font-mono break-all">{plugin.dir}</p>
+
+            {plugin.actions.length > 0 && (
+              <p className="text-xs text-text-secondary">
+                {t('plugins.actions', { actions: plugin.actions.join(', ') })}
+              </p>
+            )}
+            {plugin.error && <p className="text-xs text-error">{plugin.error}</p>}
+            {plugin.restart_required && (
+              <p className="text-xs text-warning">{t('plugins.restartRequired')}</p>
+            )}
+          </div>
+        ))}
+      </div>
+    </section>
+  );
+}
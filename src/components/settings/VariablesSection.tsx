@@ -0,0 +1,275 @@
+import { useState, useEffect, useCallback } from 'react';
+import { useTranslation } from 'react-i18next';
+import { Database, Plus, RefreshCw, Save, Trash2 } from 'lucide-react';
+import clsx from 'clsx';
+
+import { maaService } from '@/services/maaService';
+import { useAppStore } from '@/stores/appStore';
+import type { PersistentVariable, PersistentVariables } from '@/types/maa';
+import { loggers } from '@/utils/logger';
+import { isTauri } from '@/utils/paths';
+
+const inputClass =
+  'px-3 py-2 rounded-lg bg-bg-tertiary border border-border text-sm text-text-primary focus:outline-none focus:ring-2 focus:ring-accent/50';
+
+/** 以 JSON 显示变量值，字符串带引号以便与数字区分 */
+function formatValue(value: unknown): string {
+  return JSON.stringify(value ?? null);
+}
+
+/** 按 JSON 解析输入，解析失败时视为普通字符串 */
+function parseValue(text: string): unknown {
+  try {
+    return JSON.parse(text);
+  } catch {
+    return text;
+  }
+}
+
+interface VariableGroup {
+  /** 实例 ID，为 null 表示全局变量 */
+  instanceId: string | null;
+  label: string;
+  vars: [string, PersistentVariable][];
+}
+
+interface VariableRowProps {
+  name: string;
+  variable: PersistentVariable;
+  onSave: (value: unknown) => Promise<void>;
+  onDelete: () => void;
+}
+
+function VariableRow({ name, variable, onSave, onDelete }: VariableRowProps) {
+  const { t } = useTranslation();
+  const [text, setText] = useState(() => formatValue(variable.value));
+
+  useEffect(() => {
+    setText(formatValue(variable.value));
+  }, [variable.value]);
+
+  const dirty = text !== formatValue(variable.value);
+  const hasReset = variable.daily_reset_hour !== null && variable.daily_reset_hour !== undefined;
+
+  return (
+    <div className="flex items-center gap-2">
+      <span
+        className="w-40 shrink-0 truncate text-sm font-mono text-text-primary"
+        title={
+          variable.updated_at
+            ? t('persistentVariables.updatedAt', { time: variable.updated_at })
+            : name
+        }
+      >
+        {name}
+      </span>
+      <input
+        type="text"
+        value={text}
+        onChange={(e) => setText(e.target.value)}
+        onKeyDown={(e) => {
+          if (e.key === 'Enter' && dirty) onSave(parseValue(text));
+        }}
+        className={clsx(inputClass, 'flex-1 min-w-0 font-mono')}
+      />
+      {hasReset && (
+        <span className="text-xs text-text-muted whitespace-nowrap">
+          {t('persistentVariables.dailyReset', {
+            hour: variable.daily_reset_hour,
+            value: formatValue(variable.reset_value ?? 0),
+          })}
+        </span>
+      )}
+      <button
+        onClick={() => onSave(parseValue(text))}
+        disabled={!dirty}
+        className="p-2 rounded-lg text-text-secondary hover:bg-bg-hover transition-colors disabled:opacity-50"
+        title={t('persistentVariables.save')}
+      >
+        <Save className="w-4 h-4" />
+      </button>
+      <button
+        onClick={onDelete}
+        className="p-2 rounded-lg text-text-secondary hover:bg-bg-hover hover:text-error transition-colors"
+        title={t('persistentVariables.delete')}
+      >
+        <Trash2 className="w-4 h-4" />
+      </button>
+    </div>
+  );
+}
+
+export function VariablesSection() {
+  const { t } = useTranslation();
+  const instances = useAppStore((state) => state.instances);
+
+  const [variables, setVariables] = useState<PersistentVariables>({ global: {}, instances: {} });
+  const [error, setError] = useState<string | null>(null);
+  // 新增变量表单，scope 为空字符串表示全局
+  const [newScope, setNewScope] = useState('');
+  const [newName, setNewName] = useState('');
+  const [newValue, setNewValue] = useState('0');
+  const [newResetHour, setNewResetHour] = useState('');
+
+  const reload = useCallback(async () => {
+    if (!isTauri()) return;
+    try {
+      setVariables(await maaService.getPersistentVariables());
+      setError(null);
+    } catch (err) {
+      loggers.ui.error('加载持久化变量失败:', err);
+      setError(String(err));
+    }
+  }, []);
+
+  useEffect(() => {
+    reload();
+  }, [reload]);
+
+  const handleSave = async (instanceId: string | null, name: string, value: unknown) => {
+    try {
+      await maaService.setPersistentVariable(instanceId, name, value);
+      await reload();
+    } catch (err) {
+      loggers.ui.error('保存持久化变量失败:', err);
+      setError(String(err));
+    }
+  };
+
+  const handleDelete = async (instanceId: string | null, name: string) => {
+    try {
+      await maaService.deletePersistentVariable(instanceId, name);
+      await reload();
+    } catch (err) {
+      loggers.ui.error('删除持久化变量失败:', err);
+      setError(String(err));
+    }
+  };
+
+  const handleAdd = async () => {
+    const name = newName.trim();
+    if (!name) return;
+    const hour = newResetHour.trim() === '' ? null : Number(newResetHour);
+    if (hour !== null && (!Number.isInteger(hour) || hour < 0 || hour > 23)) {
+      setError(t('persistentVariables.invalidResetHour'));
+      return;
+    }
+    try {
+      await maaService.setPersistentVariable(newScope || null, name, parseValue(newValue), hour);
+      setNewName('');
+      await reload();
+    } catch (err) {
+      loggers.ui.error('添加持久化变量失败:', err);
+      setError(String(err));
+    }
+  };
+
+  const instanceName = (id: string) => instances.find((i) => i.id === id)?.name ?? id;
+
+  const groups: VariableGroup[] = [
+    {
+      instanceId: null,
+      label: t('persistentVariables.global'),
+      vars: Object.entries(variables.global),
+    },
+    ...Object.entries(variables.instances).map(([id, vars]) => ({
+      instanceId: id,
+      label: instanceName(id),
+      vars: Object.entries(vars),
+    })),
+  ].filter((group) => group.vars.length > 0);
+
+  return (
+    <section id="section-variables" className="space-y-4 scroll-mt-4">
+      <h2 className="text-sm font-semibold text-text-primary uppercase tracking-wider flex items-center gap-2">
+        <Database className="w-4 h-4" />
+        {t('persistentVariables.title')}
+      </h2>
+
+      <div className="bg-bg-secondary rounded-xl p-4 border border-border space-y-4">
+        <div className="flex items-start gap-2">
+          <p className="flex-1 text-xs text-text-muted">{t('persistentVariables.hint')}</p>
+          <button
+            onClick={reload}
+            className="p-2 rounded-lg text-text-secondary hover:bg-bg-hover transition-colors"
+            title={t('persistentVariables.refresh')}
+          >
+            <RefreshCw className="w-4 h-4" />
+          </button>
+        </div>
+
+        {error && <p className="text-xs text-error">{error}</p>}
+
+        {groups.length === 0 && (
+          <p className="text-sm text-text-muted text-center py-2">
+            {t('persistentVariables.empty')}
+          </p>
+        )}
+
+        {groups.map((group) => (
+          <div
+            key={group.instanceId ?? ''}
+            className="rounded-lg border border-border bg-bg-primary p-3 space-y-2"
+          >
+            <p className="text-xs text-text-secondary">{group.label}</p>
+            {group.vars.map(([name, variable]) => (
+              <VariableRow
+                key={name}
+                name={name}
+                variable={variable}
+                onSave={(value) => handleSave(group.instanceId, name, value)}
+                onDelete={() => handleDelete(group.instanceId, name)}
+              />
+            ))}
+          </div>
+        ))}
+
+        <div className="flex flex-wrap items-center gap-2">
+          <select
+            value={newScope}
+            onChange={(e) => setNewScope(e.target.value)}
+            className={inputClass}
+          >
+            <option value="">{t('persistentVariables.global')}</option>
+            {instances.map((instance) => (
+              <option key={instance.id} value={instance.id}>
+                {instance.name}
+              </option>
+            ))}
+          </select>
+          <input
+            type="text"
+            value={newName}
+            placeholder={t('persistentVariables.name')}
+            onChange={(e) => setNewName(e.target.value)}
+            className={clsx(inputClass, 'w-40 font-mono')}
+          />
+          <input
+            type="text"
+            value={newValue}
+            placeholder={t('persistentVariables.value')}
+            onChange={(e) => setNewValue(e.target.value)}
+            className={clsx(inputClass, 'w-32 font-mono')}
+          />
+          <input
+            type="number"
+            min={0}
+            max={23}
+            value={newResetHour}
+            placeholder={t('persistentVariables.resetHour')}
+            onChange={(e) => setNewResetHour(e.target.value)}
+            className={clsx(inputClass, 'w-32')}
+          />
+          <button
+            onClick={handleAdd}
+            disabled={!newName.trim()}
+            className="flex items-center gap-2 px-3 py-2 text-sm bg-bg-tertiary hover:bg-bg-hover rounded-lg transition-colors disabled:opacity-50"
+          >
+            <Plus className="w-4 h-4" />
+            {t('persistentVariables.add')}
+          </button>
+        </div>
+      </div>
+    </section>
+  );
+}
@@ -1,6 +1,9 @@
 export { AppearanceSection } from './AppearanceSection';
 export { SortableAccentTile } from './SortableAccentTile';
 export { HotkeySection } from './HotkeySection';
+export { NotificationSection } from './NotificationSection';
+export { VariablesSection } from './VariablesSection';
+export { PluginsSection } from './PluginsSection';
 export { GeneralSection } from './GeneralSection';
 export { UpdateSection } from './UpdateSection';
 export { DebugSection } from './DebugSection';
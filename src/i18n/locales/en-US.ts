@@ -101,6 +101,10 @@ export default {
     gamePathHint:
       'If no game window is found when starting tasks, launch this path and retry after 60 seconds',
     gamePathPlaceholder: 'Select game executable path (e.g. xxx.exe)',
+    fileActionRoots: 'File Action Folders',
+    fileActionRootsHint:
+      'MXU_FILE actions can only access the app folder, the data folder and the folders added here',
+    fileActionRootsAdd: 'Add Folder',
     confirmBeforeDelete: 'Confirm delete actions',
     confirmBeforeDeleteHint: 'Show confirmation before delete/clear list/import overwrite, etc.',
     maxLogsPerInstance: 'Max logs per instance',
@@ -124,6 +128,7 @@ export default {
       optionDescription:
         'Waits until the specified time before continuing. Only supports within 24 hours. If the target time has passed today, it will wait until that time tomorrow',
       inputLabel: 'Target Time',
+      timezoneLabel: 'Timezone (optional, e.g. Asia/Shanghai or +08:00)',
     },
     launch: {
       label: '▶️ Custom Program',
@@ -132,6 +137,16 @@ export default {
       programPlaceholder: 'Enter program path or click browse...',
       argsLabel: 'Additional Arguments',
       argsPlaceholder: 'Enter additional arguments (optional)',
+      cwdLabel: 'Working Directory',
+      cwdPlaceholder: "Leave empty to use the program's directory",
+      readyProcessLabel: 'Ready Process',
+      readyProcessPlaceholder: 'Wait until this process is running (optional)',
+      readyPortLabel: 'Ready Port',
+      readyPortPlaceholder:
+        'Wait until the port accepts connections, e.g. 5555 or 127.0.0.1:16384 (optional)',
+      readyWindowLabel: 'Ready Window Title',
+      readyWindowPlaceholder:
+        'Wait until a window whose title contains this text appears (optional)',
       waitLabel: 'Wait for Exit',
       waitDescription:
         'When enabled, waits for the process to exit before continuing, suitable for programs that need to complete synchronously; when disabled, continues immediately after launch',
@@ -144,7 +159,8 @@ export default {
       titleLabel: 'Title',
       titlePlaceholder: 'Enter notification title',
       bodyLabel: 'Content',
-      bodyPlaceholder: 'Enter notification content',
+      bodyPlaceholder:
+        'Enter notification content, placeholders like {instance}, {task}, {date:%H:%M} are supported',
     },
     webhook: {
       label: '🔔 Webhook',
@@ -170,6 +186,16 @@ export default {
       restart: 'Restart',
       screenoff: 'Turn Off Screen',
       sleep: 'Sleep',
+      hibernate: 'Hibernate',
+      logoff: 'Log Off',
+      countdownOptionLabel: 'Countdown',
+      countdownLabel: 'Countdown Before Action (seconds)',
+      countdownError: 'Please enter a non-negative integer',
+      idleLabel: 'Only When Idle',
+      idleDescription:
+        'When enabled, the action is skipped if other instances are still running tasks, so other accounts are not interrupted',
+      idleYes: 'Only when no other instance is running',
+      idleNo: 'Always',
     },
   },
 
@@ -205,6 +231,10 @@ export default {
     stopTasks: 'Stop Tasks',
     startingTasks: 'Starting...',
     stoppingTasks: 'Stopping...',
+    pauseTasks: 'Pause',
+    resumeTasks: 'Resume',
+    actionCountdown: 'Waiting {{remaining}}s / {{total}}s',
+    cancelPowerAction: 'Cancel power action',
     // Auto connect
     autoConnect: {
       searching: 'Searching devices...',
@@ -220,6 +250,7 @@ export default {
       connectFailed: 'Auto connect failed',
       resourceFailed: 'Resource loading failed',
       startFailed: 'Failed to start tasks',
+      waitingAgents: 'Waiting for agent {{agents}}...',
       needConfig:
         'Please connect device and load resource first, or save device config in connection panel',
     },
@@ -412,6 +443,12 @@ export default {
       agentConnected: 'Agent connected',
       agentDisconnected: 'Agent disconnected',
       agentFailed: 'Agent start failed',
+      agentProgressStarting: 'Agent #{{index}} starting...',
+      agentProgressWaiting: 'Agent #{{index}} waiting for external agent: {{address}}',
+      agentProgressConnecting: 'Agent #{{index}} connecting...',
+      agentProgressConnected: 'Agent #{{index}} connected',
+      agentProgressDisconnected: 'Agent #{{index}} disconnected, waiting for it to reconnect...',
+      agentProgressFailed: 'Agent #{{index}} failed: {{error}}',
       // Hotkeys
       hotkeyDetected: 'Hotkey detected: {{combo}} ({{action}})',
       hotkeyActionStart: 'Start tasks',
@@ -786,4 +823,116 @@ export default {
     invalid: 'Invalid proxy URL format',
     examples: 'Example Formats',
   },
+  // Notification Channels
+  notificationChannels: {
+    title: 'Notification Channels',
+    hint:
+      'Channels can be used by MXU_NOTIFY via its channel parameter (ID or name), and can also be notified automatically when a run finishes.',
+    secretsHint:
+      'Passwords and tokens are stored in plain text in the data directory. Do not share it with others.',
+    empty: 'No notification channels yet',
+    loadFailed: 'Failed to load notification channels: {{error}}',
+    name: 'Name',
+    add: 'Add Channel',
+    save: 'Save',
+    saved: 'Saved',
+    test: 'Send test notification',
+    testSucceeded: 'Test notification sent',
+    testMessage: 'This is a test notification from MXU',
+    delete: 'Delete',
+    events: 'Notify automatically when',
+    eventNames: {
+      run_succeeded: 'All tasks succeeded',
+      run_failed: 'Some task failed',
+    },
+    types: {
+      smtp: 'Email (SMTP)',
+      telegram: 'Telegram',
+      discord: 'Discord',
+      slack: 'Slack',
+      bark: 'Bark',
+      server_chan: 'ServerChan',
+      ntfy: 'ntfy',
+      http: 'HTTP Request',
+    },
+    fields: {
+      host: 'SMTP Host',
+      port: 'Port',
+      security: 'Encryption',
+      username: 'Username',
+      password: 'Password',
+      from: 'Sender',
+      to: 'Recipients (comma separated)',
+      botToken: 'Bot Token',
+      chatId: 'Chat ID',
+      apiBase: 'API Base URL',
+      webhookUrl: 'Webhook URL',
+      deviceKey: 'Device Key',
+      server: 'Server',
+      group: 'Group',
+      sound: 'Sound',
+      sendKey: 'SendKey',
+      topic: 'Topic',
+      token: 'Access Token',
+      priority: 'Priority (1-5)',
+      url: 'Request URL',
+      method: 'Method',
+      headers: 'Headers (one "Name: value" per line)',
+      body: 'Body Template ({title}, {body})',
+    },
+    messages: {
+      runSucceeded: 'All tasks of {instance} have finished',
+      runFailed: 'Tasks of {instance} failed',
+      runFailedWithError: 'Tasks of {instance} failed: {error}',
+      powerCountdown: '{action} in {seconds}s, you can cancel it in MXU',
+      powerCancelled: '{action} cancelled',
+      powerSkipped: 'Other instances are still running, {action} skipped',
+      powerActions: {
+        shutdown: 'Shut down',
+        restart: 'Restart',
+        screenoff: 'Turn off screen',
+        sleep: 'Sleep',
+        hibernate: 'Hibernate',
+        logoff: 'Log off',
+      },
+    },
+  },
+  persistentVariables: {
+    title: 'Persistent Variables',
+    hint:
+      'Variables saved by MXU_VARIABLE across runs. Values are JSON; plain text is stored as a string.',
+    refresh: 'Refresh',
+    empty: 'No persistent variables yet',
+    global: 'Global',
+    name: 'Variable name',
+    value: 'Value',
+    resetHour: 'Reset hour',
+    add: 'Add',
+    save: 'Save',
+    delete: 'Delete',
+    dailyReset: 'Resets to {{value}} daily at {{hour}}:00',
+    updatedAt: 'Last updated: {{time}}',
+    invalidResetHour: 'Reset hour must be an integer from 0 to 23',
+  },
+  plugins: {
+    title: 'Plugins',
+    hint:
+      'Plugins in the plugins folder run native code inside MXU, so none are loaded unless you allow them here. Only allow plugins you trust. Changes take effect after restarting MXU.',
+    refresh: 'Refresh',
+    empty: 'No plugins found in the plugins folder',
+    loadFailed: 'Failed to load plugins: {{error}}',
+    allow: 'Allow',
+    actions: 'Actions: {{actions}}',
+    restartRequired: 'Restart MXU to apply this change',
+    kinds: {
+      library: 'Library',
+      executable: 'Executable',
+    },
+    status: {
+      loading: 'Loading',
+      loaded: 'Loaded',
+      failed: 'Failed',
+      not_loaded: 'Not loaded',
+    },
+  },
 };
@@ -97,6 +97,10 @@ export default {
     autoRunOnLaunch: '手動起動時も自動実行',
     autoRunOnLaunchHint:
       '手動でアプリを開く際も、上で選択した設定を自動実行します（無効な場合はシステム起動時のみ実行）',
+    fileActionRoots: 'ファイル操作の許可フォルダー',
+    fileActionRootsHint:
+      'MXU_FILE アクションはアプリフォルダー、データフォルダー、ここで追加したフォルダーのみアクセスできます',
+    fileActionRootsAdd: 'フォルダーを追加',
     confirmBeforeDelete: '削除操作の前に確認する',
     confirmBeforeDeleteHint: '削除/一覧クリア/上書きインポート等の前に確認ダイアログを表示します',
     maxLogsPerInstance: 'インスタンスあたりのログ上限',
@@ -119,6 +123,7 @@ export default {
       optionDescription:
         '指定した時刻まで待機してから続行します。24時間以内のみ対応。目標時刻が過ぎている場合は翌日のその時刻まで待機します',
       inputLabel: '目標時刻',
+      timezoneLabel: 'タイムゾーン（任意、例: Asia/Tokyo または +09:00）',
     },
     launch: {
       label: '▶️ カスタムプログラム',
@@ -127,6 +132,14 @@ export default {
       programPlaceholder: 'プログラムパスを入力するか右側の参照をクリック...',
       argsLabel: '追加引数',
       argsPlaceholder: '追加引数を入力（任意）',
+      cwdLabel: '作業ディレクトリ',
+      cwdPlaceholder: '空欄の場合はプログラムのディレクトリを使用',
+      readyProcessLabel: '準備完了プロセス',
+      readyProcessPlaceholder: 'このプロセスが起動するまで待機（任意）',
+      readyPortLabel: '準備完了ポート',
+      readyPortPlaceholder: 'ポートに接続できるまで待機（例: 5555 や 127.0.0.1:16384、任意）',
+      readyWindowLabel: '準備完了ウィンドウタイトル',
+      readyWindowPlaceholder: 'タイトルにこの文字列を含むウィンドウが表示されるまで待機（任意）',
       waitLabel: '終了を待機',
       waitDescription:
         '有効時はプロセス終了後に続行します。プログラムなど同期完了が必要な操作に適しています。無効時は起動後すぐに続行します',
@@ -139,7 +152,8 @@ export default {
       titleLabel: '通知タイトル',
       titlePlaceholder: '通知タイトルを入力',
       bodyLabel: '通知内容',
-      bodyPlaceholder: '通知内容を入力',
+      bodyPlaceholder:
+        '通知内容を入力（{instance}、{task}、{date:%H:%M} などのプレースホルダーを使用可能）',
     },
     webhook: {
       label: '🔔 Webhook',
@@ -165,6 +179,16 @@ export default {
       restart: '再起動',
       screenoff: '画面オフ',
       sleep: 'スリープ',
+      hibernate: '休止状態',
+      logoff: 'サインアウト',
+      countdownOptionLabel: 'カウントダウン',
+      countdownLabel: '実行前のカウントダウン（秒）',
+      countdownError: '0 以上の整数を入力してください',
+      idleLabel: 'アイドル時のみ実行',
+      idleDescription:
+        '有効にすると、他のインスタンスがタスク実行中の場合はこの操作をスキップし、他のアカウントの中断を防ぎます',
+      idleYes: '他のインスタンスが実行中でない場合のみ',
+      idleNo: '常に実行',
     },
   },
 
@@ -200,6 +224,10 @@ export default {
     stopTasks: '実行停止',
     startingTasks: '開始中...',
     stoppingTasks: '停止中...',
+    pauseTasks: '一時停止',
+    resumeTasks: '再開',
+    actionCountdown: '待機中 {{remaining}}s / {{total}}s',
+    cancelPowerAction: '電源操作をキャンセル',
     // 自動接続関連
     autoConnect: {
       searching: 'デバイスを検索中...',
@@ -215,6 +243,7 @@ export default {
       connectFailed: '自動接続に失敗しました',
       resourceFailed: 'リソースの読み込みに失敗しました',
       startFailed: 'タスクの開始に失敗しました',
+      waitingAgents: 'Agent {{agents}} を待機中...',
       needConfig:
         'まずデバイスを接続してリソースを読み込むか、接続パネルでデバイス設定を保存してください',
     },
@@ -404,6 +433,12 @@ export default {
       agentConnected: 'Agent が接続しました',
       agentDisconnected: 'Agent が切断しました',
       agentFailed: 'Agent の起動に失敗しました',
+      agentProgressStarting: 'Agent #{{index}} 起動中...',
+      agentProgressWaiting: 'Agent #{{index}} 外部 agent の接続待ち：{{address}}',
+      agentProgressConnecting: 'Agent #{{index}} 接続中...',
+      agentProgressConnected: 'Agent #{{index}} 接続完了',
+      agentProgressDisconnected: 'Agent #{{index}} が切断されました。再接続を待機中...',
+      agentProgressFailed: 'Agent #{{index}} 起動失敗：{{error}}',
       // ショートカットキー
       hotkeyDetected: 'ショートカットキーを検出: {{combo}}（{{action}}）',
       hotkeyActionStart: 'タスク開始',
@@ -782,4 +817,116 @@ export default {
     invalid: 'プロキシ URL の形式が正しくありません',
     examples: '形式の例',
   },
+  // 通知チャンネル
+  notificationChannels: {
+    title: '通知チャンネル',
+    hint:
+      'チャンネルは MXU_NOTIFY の channel パラメータで ID または名前で指定でき、実行終了時に自動で通知することもできます。',
+    secretsHint:
+      'パスワードとトークンはデータディレクトリに平文で保存されます。データディレクトリを他人と共有しないでください',
+    empty: '通知チャンネルはまだありません',
+    loadFailed: '通知チャンネルの読み込みに失敗しました：{{error}}',
+    name: '名前',
+    add: 'チャンネルを追加',
+    save: '保存',
+    saved: '保存しました',
+    test: 'テスト通知を送信',
+    testSucceeded: 'テスト通知を送信しました',
+    testMessage: 'これは MXU からのテスト通知です',
+    delete: '削除',
+    events: '自動通知のタイミング',
+    eventNames: {
+      run_succeeded: 'すべてのタスクが成功',
+      run_failed: '失敗したタスクがある',
+    },
+    types: {
+      smtp: 'メール（SMTP）',
+      telegram: 'Telegram',
+      discord: 'Discord',
+      slack: 'Slack',
+      bark: 'Bark',
+      server_chan: 'ServerChan',
+      ntfy: 'ntfy',
+      http: 'HTTP リクエスト',
+    },
+    fields: {
+      host: 'SMTP サーバー',
+      port: 'ポート',
+      security: '暗号化',
+      username: 'ユーザー名',
+      password: 'パスワード',
+      from: '送信者',
+      to: '宛先（カンマ区切り）',
+      botToken: 'Bot Token',
+      chatId: 'Chat ID',
+      apiBase: 'API ベース URL',
+      webhookUrl: 'Webhook URL',
+      deviceKey: 'デバイスキー',
+      server: 'サーバー',
+      group: 'グループ',
+      sound: 'サウンド',
+      sendKey: 'SendKey',
+      topic: 'トピック',
+      token: 'アクセストークン',
+      priority: '優先度（1-5）',
+      url: 'リクエスト URL',
+      method: 'メソッド',
+      headers: 'ヘッダー（1 行に 1 つ "Name: value"）',
+      body: '本文テンプレート（{title}、{body}）',
+    },
+    messages: {
+      runSucceeded: '{instance} のタスクがすべて完了しました',
+      runFailed: '{instance} のタスクが失敗しました',
+      runFailedWithError: '{instance} のタスクが失敗しました：{error}',
+      powerCountdown: '{seconds} 秒後に{action}します。MXU でキャンセルできます',
+      powerCancelled: '{action}をキャンセルしました',
+      powerSkipped: '他のインスタンスが実行中のため、{action}をスキップしました',
+      powerActions: {
+        shutdown: 'シャットダウン',
+        restart: '再起動',
+        screenoff: '画面オフ',
+        sleep: 'スリープ',
+        hibernate: '休止状態',
+        logoff: 'ログオフ',
+      },
+    },
+  },
+  persistentVariables: {
+    title: '永続変数',
+    hint:
+      'MXU_VARIABLE が実行をまたいで保存する変数です。値は JSON として解釈され、解釈できないテキストは文字列として保存されます。',
+    refresh: '更新',
+    empty: '永続変数はまだありません',
+    global: 'グローバル',
+    name: '変数名',
+    value: '値',
+    resetHour: 'リセット時刻',
+    add: '追加',
+    save: '保存',
+    delete: '削除',
+    dailyReset: '毎日 {{hour}}:00 に {{value}} へリセット',
+    updatedAt: '最終更新：{{time}}',
+    invalidResetHour: 'リセット時刻は 0〜23 の整数で指定してください',
+  },
+  plugins: {
+    title: 'プラグイン',
+    hint:
+      'plugins フォルダーのプラグインは MXU 内でネイティブコードを実行するため、ここで許可したものだけが読み込まれます。信頼できるプラグインのみ許可してください。変更は MXU の再起動後に反映されます。',
+    refresh: '更新',
+    empty: 'plugins フォルダーにプラグインがありません',
+    loadFailed: 'プラグイン一覧の読み込みに失敗しました：{{error}}',
+    allow: '許可',
+    actions: 'アクション：{{actions}}',
+    restartRequired: 'MXU を再起動すると反映されます',
+    kinds: {
+      library: 'ライブラリ',
+      executable: '実行ファイル',
+    },
+    status: {
+      loading: '読み込み中',
+      loaded: '読み込み済み',
+      failed: '読み込み失敗',
+      not_loaded: '未読み込み',
+    },
+  },
 };
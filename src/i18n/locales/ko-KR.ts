@@ -96,6 +96,10 @@ export default {
     autoRunOnLaunch: '수동 실행 시에도 자동 실행',
     autoRunOnLaunchHint:
       '앱을 수동으로 열 때도 위에서 선택한 구성을 자동 실행합니다 (비활성화 시 시스템 시작 시에만 실행)',
+    fileActionRoots: '파일 작업 허용 폴더',
+    fileActionRootsHint:
+      'MXU_FILE 동작은 프로그램 폴더, 데이터 폴더 및 여기에 추가한 폴더에만 접근할 수 있습니다',
+    fileActionRootsAdd: '폴더 추가',
     confirmBeforeDelete: '삭제 작업 확인',
     confirmBeforeDeleteHint:
       '삭제/목록 비우기/가져오기 덮어쓰기 등 전에 확인 대화 상자를 표시합니다',
@@ -119,6 +123,7 @@ export default {
       optionDescription:
         '지정된 시간까지 대기한 후 계속합니다. 24시간 이내만 지원됩니다. 대상 시간이 이미 지났으면 다음 날 해당 시간까지 대기합니다',
       inputLabel: '대상 시간',
+      timezoneLabel: '시간대 (선택, 예: Asia/Seoul 또는 +09:00)',
     },
     launch: {
       label: '▶️ 사용자 지정 프로그램',
@@ -127,6 +132,15 @@ export default {
       programPlaceholder: '프로그램 경로를 입력하거나 오른쪽 찾아보기를 클릭...',
       argsLabel: '추가 인수',
       argsPlaceholder: '추가 인수 입력 (선택 사항)',
+      cwdLabel: '작업 디렉터리',
+      cwdPlaceholder: '비워 두면 프로그램 디렉터리를 사용',
+      readyProcessLabel: '준비 프로세스',
+      readyProcessPlaceholder: '이 프로세스가 실행될 때까지 대기 (선택 사항)',
+      readyPortLabel: '준비 포트',
+      readyPortPlaceholder:
+        '포트에 연결할 수 있을 때까지 대기, 예: 5555 또는 127.0.0.1:16384 (선택 사항)',
+      readyWindowLabel: '준비 창 제목',
+      readyWindowPlaceholder: '제목에 이 텍스트가 포함된 창이 나타날 때까지 대기 (선택 사항)',
       waitLabel: '종료 대기',
       waitDescription:
         '활성화하면 프로세스 종료 후 계속합니다. 프로그램 등 동기 완료가 필요한 작업에 적합합니다. 비활성화하면 실행 후 즉시 계속합니다',
@@ -139,7 +153,8 @@ export default {
       titleLabel: '알림 제목',
       titlePlaceholder: '알림 제목을 입력하세요',
       bodyLabel: '알림 내용',
-      bodyPlaceholder: '알림 내용을 입력하세요',
+      bodyPlaceholder:
+        '알림 내용을 입력하세요 ({instance}, {task}, {date:%H:%M} 등의 플레이스홀더 사용 가능)',
     },
     webhook: {
       label: '🔔 Webhook',
@@ -165,6 +180,16 @@ export default {
       restart: '재시작',
       screenoff: '화면 끄기',
       sleep: '절전 모드',
+      hibernate: '최대 절전',
+      logoff: '로그오프',
+      countdownOptionLabel: '카운트다운',
+      countdownLabel: '실행 전 카운트다운 (초)',
+      countdownError: '0 이상의 정수를 입력하세요',
+      idleLabel: '유휴 시에만 실행',
+      idleDescription:
+        '활성화하면 다른 인스턴스가 작업을 실행 중일 때 이 작업을 건너뛰어 다른 계정의 작업이 중단되지 않도록 합니다',
+      idleYes: '다른 인스턴스가 실행 중이 아닐 때만',
+      idleNo: '항상 실행',
     },
   },
 
@@ -200,6 +225,10 @@ export default {
     stopTasks: '실행 중지',
     startingTasks: '시작 중...',
     stoppingTasks: '중지 중...',
+    pauseTasks: '일시 정지',
+    resumeTasks: '재개',
+    actionCountdown: '대기 중 {{remaining}}s / {{total}}s',
+    cancelPowerAction: '전원 작업 취소',
     // 자동 연결 관련
     autoConnect: {
       searching: '기기 검색 중...',
@@ -215,6 +244,7 @@ export default {
       connectFailed: '자동 연결에 실패했습니다',
       resourceFailed: '리소스 로딩에 실패했습니다',
       startFailed: '작업 시작에 실패했습니다',
+      waitingAgents: 'Agent {{agents}} 대기 중...',
       needConfig: '먼저 기기를 연결하고 리소스를 로드하거나 연결 패널에서 기기 설정을 저장하세요',
     },
   },
@@ -402,6 +432,12 @@ export default {
       agentConnected: 'Agent가 연결되었습니다',
       agentDisconnected: 'Agent 연결이 끊어졌습니다',
       agentFailed: 'Agent 시작에 실패했습니다',
+      agentProgressStarting: 'Agent #{{index}} 시작 중...',
+      agentProgressWaiting: 'Agent #{{index}} 외부 agent 연결 대기: {{address}}',
+      agentProgressConnecting: 'Agent #{{index}} 연결 중...',
+      agentProgressConnected: 'Agent #{{index}} 연결됨',
+      agentProgressDisconnected: 'Agent #{{index}} 연결이 끊어졌습니다. 재연결 대기 중...',
+      agentProgressFailed: 'Agent #{{index}} 시작 실패: {{error}}',
       // 단축키
       hotkeyDetected: '단축키 감지: {{combo}} ({{action}})',
       hotkeyActionStart: '작업 시작',
@@ -775,4 +811,116 @@ export default {
     invalid: '프록시 주소 형식이 올바르지 않습니다',
     examples: '예시 형식',
   },
+  // 알림 채널
+  notificationChannels: {
+    title: '알림 채널',
+    hint:
+      '채널은 MXU_NOTIFY의 channel 매개변수에서 ID 또는 이름으로 지정할 수 있으며, 실행이 끝났을 때 자동으로 알릴 수도 있습니다.',
+    secretsHint:
+      '비밀번호와 토큰은 데이터 디렉터리에 평문으로 저장됩니다. 데이터 디렉터리를 다른 사람과 공유하지 마세요',
+    empty: '알림 채널이 없습니다',
+    loadFailed: '알림 채널을 불러오지 못했습니다: {{error}}',
+    name: '이름',
+    add: '채널 추가',
+    save: '저장',
+    saved: '저장됨',
+    test: '테스트 알림 보내기',
+    testSucceeded: '테스트 알림을 보냈습니다',
+    testMessage: 'MXU에서 보낸 테스트 알림입니다',
+    delete: '삭제',
+    events: '자동 알림 시점',
+    eventNames: {
+      run_succeeded: '모든 작업 성공',
+      run_failed: '실패한 작업 있음',
+    },
+    types: {
+      smtp: '이메일 (SMTP)',
+      telegram: 'Telegram',
+      discord: 'Discord',
+      slack: 'Slack',
+      bark: 'Bark',
+      server_chan: 'ServerChan',
+      ntfy: 'ntfy',
+      http: 'HTTP 요청',
+    },
+    fields: {
+      host: 'SMTP 서버',
+      port: '포트',
+      security: '암호화',
+      username: '사용자 이름',
+      password: '비밀번호',
+      from: '보낸 사람',
+      to: '받는 사람 (쉼표로 구분)',
+      botToken: 'Bot Token',
+      chatId: 'Chat ID',
+      apiBase: 'API 기본 URL',
+      webhookUrl: 'Webhook URL',
+      deviceKey: '기기 키',
+      server: '서버',
+      group: '그룹',
+      sound: '알림음',
+      sendKey: 'SendKey',
+      topic: '토픽',
+      token: '액세스 토큰',
+      priority: '우선순위 (1-5)',
+      url: '요청 URL',
+      method: '메서드',
+      headers: '헤더 (한 줄에 하나씩 "Name: value")',
+      body: '본문 템플릿 ({title}, {body})',
+    },
+    messages: {
+      runSucceeded: '{instance}의 작업이 모두 완료되었습니다',
+      runFailed: '{instance}의 작업이 실패했습니다',
+      runFailedWithError: '{instance}의 작업이 실패했습니다: {error}',
+      powerCountdown: '{seconds}초 후 {action}을(를) 실행합니다. MXU에서 취소할 수 있습니다',
+      powerCancelled: '{action}이(가) 취소되었습니다',
+      powerSkipped: '다른 인스턴스가 실행 중이어서 {action}을(를) 건너뛰었습니다',
+      powerActions: {
+        shutdown: '시스템 종료',
+        restart: '다시 시작',
+        screenoff: '화면 끄기',
+        sleep: '절전',
+        hibernate: '최대 절전',
+        logoff: '로그오프',
+      },
+    },
+  },
+  persistentVariables: {
+    title: '영구 변수',
+    hint:
+      'MXU_VARIABLE이 실행 간에 저장하는 변수입니다. 값은 JSON으로 해석되며, 해석할 수 없는 텍스트는 문자열로 저장됩니다.',
+    refresh: '새로고침',
+    empty: '영구 변수가 없습니다',
+    global: '전역',
+    name: '변수 이름',
+    value: '값',
+    resetHour: '초기화 시각',
+    add: '추가',
+    save: '저장',
+    delete: '삭제',
+    dailyReset: '매일 {{hour}}:00에 {{value}}(으)로 초기화',
+    updatedAt: '마지막 수정: {{time}}',
+    invalidResetHour: '초기화 시각은 0~23 사이의 정수여야 합니다',
+  },
+  plugins: {
+    title: '플러그인',
+    hint:
+      'plugins 폴더의 플러그인은 MXU 안에서 네이티브 코드를 실행하므로 여기에서 허용한 것만 로드됩니다. 신뢰할 수 있는 플러그인만 허용하세요. 변경 사항은 MXU를 다시 시작한 후 적용됩니다.',
+    refresh: '새로고침',
+    empty: 'plugins 폴더에 플러그인이 없습니다',
+    loadFailed: '플러그인 목록을 불러오지 못했습니다: {{error}}',
+    allow: '허용',
+    actions: '액션: {{actions}}',
+    restartRequired: 'MXU를 다시 시작하면 적용됩니다',
+    kinds: {
+      library: '라이브러리',
+      executable: '실행 파일',
+    },
+    status: {
+      loading: '로드 중',
+      loaded: '로드됨',
+      failed: '로드 실패',
+      not_loaded: '로드 안 됨',
+    },
+  },
 };
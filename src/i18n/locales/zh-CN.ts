@@ -99,6 +99,9 @@ export default {
     gamePath: '游戏路径',
     gamePathHint: '开始任务时若未找到游戏窗口，将启动此路径并等待 60 秒后重试',
     gamePathPlaceholder: '选择游戏可执行文件路径（如 xxx.exe）',
+    fileActionRoots: '文件操作允许目录',
+    fileActionRootsHint: 'MXU_FILE 动作只能读写程序目录、数据目录以及这里添加的目录',
+    fileActionRootsAdd: '添加目录',
     confirmBeforeDelete: '删除操作需要二次确认',
     confirmBeforeDeleteHint: '删除任务、清空列表、导入覆盖等操作会先弹出确认对话框',
     maxLogsPerInstance: '每个实例保留的日志上限',
@@ -121,6 +124,7 @@ export default {
       optionDescription:
         '等待到指定时间点后继续执行，仅支持 24 小时内。若目标时间已过则等待到次日该时间',
       inputLabel: '目标时间',
+      timezoneLabel: '时区（可选，如 Asia/Shanghai 或 +08:00）',
     },
     launch: {
       label: '▶️ 自定义程序',
@@ -129,6 +133,14 @@ export default {
       programPlaceholder: '输入程序路径或点击右侧浏览...',
       argsLabel: '附加参数',
       argsPlaceholder: '输入附加参数（可选）',
+      cwdLabel: '工作目录',
+      cwdPlaceholder: '留空则使用程序所在目录',
+      readyProcessLabel: '就绪进程名',
+      readyProcessPlaceholder: '等待该进程出现后再继续（可选）',
+      readyPortLabel: '就绪端口',
+      readyPortPlaceholder: '等待端口可连接，如 5555 或 127.0.0.1:16384（可选）',
+      readyWindowLabel: '就绪窗口标题',
+      readyWindowPlaceholder: '等待标题包含该文本的窗口出现（可选）',
       waitLabel: '等待退出',
       waitDescription:
         '启用时等待进程退出后再继续，适用于执行程序等需要同步完成的操作；禁用时启动进程后立即继续',
@@ -141,7 +153,7 @@ export default {
       titleLabel: '通知标题',
       titlePlaceholder: '输入通知标题',
       bodyLabel: '通知内容',
-      bodyPlaceholder: '输入通知内容',
+      bodyPlaceholder: '输入通知内容，可使用 {instance}、{task}、{date:%H:%M} 等占位符',
     },
     webhook: {
       label: '🔔 Webhook',
@@ -166,6 +178,15 @@ export default {
       restart: '重启',
       screenoff: '息屏',
       sleep: '睡眠',
+      hibernate: '休眠',
+      logoff: '注销',
+      countdownOptionLabel: '倒计时',
+      countdownLabel: '执行前倒计时（秒）',
+      countdownError: '请输入非负整数',
+      idleLabel: '仅在空闲时执行',
+      idleDescription: '启用时若其它实例仍在运行任务则跳过本操作，避免中断其它账号的任务',
+      idleYes: '没有其它实例运行时才执行',
+      idleNo: '总是执行',
     },
   },
 
@@ -199,6 +220,10 @@ export default {
     stopTasks: '停止任务',
     startingTasks: '启动中...',
     stoppingTasks: '停止中...',
+    pauseTasks: '暂停任务',
+    resumeTasks: '继续任务',
+    actionCountdown: '等待中 {{remaining}}s / {{total}}s',
+    cancelPowerAction: '取消电源操作',
     // 自动连接相关
     autoConnect: {
       searching: '搜索设备...',
@@ -214,6 +239,7 @@ export default {
       connectFailed: '自动连接失败',
       resourceFailed: '资源加载失败',
       startFailed: '任务启动失败',
+      waitingAgents: '等待 Agent {{agents}}...',
       needConfig: '请先连接设备并加载资源，或在连接面板保存设备配置',
     },
   },
@@ -405,6 +431,12 @@ export default {
       agentConnected: 'Agent 已连接',
       agentDisconnected: 'Agent 已断开',
       agentFailed: 'Agent 启动失败',
+      agentProgressStarting: 'Agent #{{index}} 启动中...',
+      agentProgressWaiting: 'Agent #{{index}} 等待外部 agent 连接：{{address}}',
+      agentProgressConnecting: 'Agent #{{index}} 连接中...',
+      agentProgressConnected: 'Agent #{{index}} 已连接',
+      agentProgressDisconnected: 'Agent #{{index}} 已断开，等待重新连接...',
+      agentProgressFailed: 'Agent #{{index}} 启动失败：{{error}}',
       // 快捷键
       hotkeyDetected: '检测到快捷键：{{combo}}（{{action}}）',
       hotkeyActionStart: '开始任务',
@@ -774,4 +806,113 @@ export default {
     hint: '小提示：建议解压到一个专门的文件夹，比如「D:\\MaaXXX」，别放桌面或者下载文件夹，那样更方便管理。',
     exit: '退出程序',
   },
+  // 通知渠道
+  notificationChannels: {
+    title: '通知渠道',
+    hint: '渠道可在 MXU_NOTIFY 的 channel 参数中按 ID 或名称引用，也可在运行结束时自动通知。',
+    secretsHint: '密码和 Token 以明文保存在数据目录中，请勿将数据目录分享给他人',
+    empty: '暂无通知渠道',
+    loadFailed: '加载通知渠道失败：{{error}}',
+    name: '名称',
+    add: '添加渠道',
+    save: '保存',
+    saved: '已保存',
+    test: '发送测试通知',
+    testSucceeded: '测试通知已发送',
+    testMessage: '这是一条来自 MXU 的测试通知',
+    delete: '删除',
+    events: '自动通知时机',
+    eventNames: {
+      run_succeeded: '任务全部成功',
+      run_failed: '存在失败的任务',
+    },
+    types: {
+      smtp: '邮件（SMTP）',
+      telegram: 'Telegram',
+      discord: 'Discord',
+      slack: 'Slack',
+      bark: 'Bark',
+      server_chan: 'Server 酱',
+      ntfy: 'ntfy',
+      http: 'HTTP 请求',
+    },
+    fields: {
+      host: 'SMTP 服务器',
+      port: '端口',
+      security: '加密方式',
+      username: '用户名',
+      password: '密码',
+      from: '发件人',
+      to: '收件人（逗号分隔）',
+      botToken: 'Bot Token',
+      chatId: 'Chat ID',
+      apiBase: 'API 地址',
+      webhookUrl: 'Webhook 地址',
+      deviceKey: '设备 Key',
+      server: '服务器地址',
+      group: '分组',
+      sound: '提示音',
+      sendKey: 'SendKey',
+      topic: '主题',
+      token: '访问令牌',
+      priority: '优先级（1-5）',
+      url: '请求地址',
+      method: '请求方法',
+      headers: '请求头（每行一个 "Name: value"）',
+      body: '请求体模板（{title}、{body}）',
+    },
+    messages: {
+      runSucceeded: '实例 {instance} 的任务已全部完成',
+      runFailed: '实例 {instance} 的任务执行失败',
+      runFailedWithError: '实例 {instance} 的任务执行失败：{error}',
+      powerCountdown: '将在 {seconds} 秒后{action}，可在 MXU 中取消',
+      powerCancelled: '已取消{action}',
+      powerSkipped: '其它实例仍在运行，已跳过{action}',
+      powerActions: {
+        shutdown: '关机',
+        restart: '重启',
+        screenoff: '息屏',
+        sleep: '睡眠',
+        hibernate: '休眠',
+        logoff: '注销',
+      },
+    },
+  },
+  persistentVariables: {
+    title: '持久化变量',
+    hint: '由 MXU_VARIABLE 跨运行保存的变量。值按 JSON 解析，无法解析的文本保存为字符串。',
+    refresh: '刷新',
+    empty: '暂无持久化变量',
+    global: '全局',
+    name: '变量名',
+    value: '值',
+    resetHour: '重置时间',
+    add: '添加',
+    save: '保存',
+    delete: '删除',
+    dailyReset: '每天 {{hour}}:00 重置为 {{value}}',
+    updatedAt: '最后修改：{{time}}',
+    invalidResetHour: '重置时间必须是 0-23 的整数',
+  },
+  plugins: {
+    title: '插件',
+    hint:
+      'plugins 目录中的插件会在 MXU 内运行原生代码，只有在此允许后才会加载。请只允许可信的插件，修改后需重启 MXU 生效。',
+    refresh: '刷新',
+    empty: 'plugins 目录中没有插件',
+    loadFailed: '加载插件列表失败：{{error}}',
+    allow: '允许',
+    actions: '动作：{{actions}}',
+    restartRequired: '重启 MXU 后生效',
+    kinds: {
+      library: '动态库',
+      executable: '可执行文件',
+    },
+    status: {
+      loading: '加载中',
+      loaded: '已加载',
+      failed: '加载失败',
+      not_loaded: '未加载',
+    },
+  },
 };
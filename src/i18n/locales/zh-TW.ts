@@ -95,6 +95,9 @@ export default {
     autoRunOnLaunch: '手動啟動時也自動執行',
     autoRunOnLaunchHint:
       '每次手動開啟程式時，也自動執行上方選定的配置（關閉則僅在開機自啟動時觸發）',
+    fileActionRoots: '檔案操作允許目錄',
+    fileActionRootsHint: 'MXU_FILE 動作只能讀寫程式目錄、資料目錄以及這裡新增的目錄',
+    fileActionRootsAdd: '新增目錄',
     confirmBeforeDelete: '刪除操作需要二次確認',
     confirmBeforeDeleteHint: '刪除任務、清空列表、匯入覆蓋等操作會先彈出確認對話框',
     maxLogsPerInstance: '每個實例保留的日誌上限',
@@ -117,6 +120,7 @@ export default {
       optionDescription:
         '等待到指定時間點後繼續執行，僅支援 24 小時內。若目標時間已過則等待到隔日該時間',
       inputLabel: '目標時間',
+      timezoneLabel: '時區（選填，如 Asia/Taipei 或 +08:00）',
     },
     launch: {
       label: '▶️ 自定義程式',
@@ -125,6 +129,14 @@ export default {
       programPlaceholder: '輸入程式路徑或點擊右側瀏覽...',
       argsLabel: '附加參數',
       argsPlaceholder: '輸入附加參數（可選）',
+      cwdLabel: '工作目錄',
+      cwdPlaceholder: '留空則使用程式所在目錄',
+      readyProcessLabel: '就緒程序名稱',
+      readyProcessPlaceholder: '等待該程序出現後再繼續（可選）',
+      readyPortLabel: '就緒連接埠',
+      readyPortPlaceholder: '等待連接埠可連線，如 5555 或 127.0.0.1:16384（可選）',
+      readyWindowLabel: '就緒視窗標題',
+      readyWindowPlaceholder: '等待標題包含該文字的視窗出現（可選）',
       waitLabel: '等待退出',
       waitDescription:
         '啟用時等待程序退出後再繼續，適用於執行程式等需要同步完成的操作；禁用時啟動程序後立即繼續',
@@ -137,7 +149,7 @@ export default {
       titleLabel: '通知標題',
       titlePlaceholder: '輸入通知標題',
       bodyLabel: '通知內容',
-      bodyPlaceholder: '輸入通知內容',
+      bodyPlaceholder: '輸入通知內容，可使用 {instance}、{task}、{date:%H:%M} 等佔位符',
     },
     webhook: {
       label: '🔔 Webhook',
@@ -162,6 +174,15 @@ export default {
       restart: '重新啟動',
       screenoff: '關閉螢幕',
       sleep: '睡眠',
+      hibernate: '休眠',
+      logoff: '登出',
+      countdownOptionLabel: '倒數計時',
+      countdownLabel: '執行前倒數（秒）',
+      countdownError: '請輸入非負整數',
+      idleLabel: '僅在閒置時執行',
+      idleDescription: '啟用時若其他實例仍在執行任務則略過本操作，避免中斷其他帳號的任務',
+      idleYes: '沒有其他實例執行時才執行',
+      idleNo: '總是執行',
     },
   },
 
@@ -195,6 +216,10 @@ export default {
     stopTasks: '停止任務',
     startingTasks: '啟動中...',
     stoppingTasks: '停止中...',
+    pauseTasks: '暫停任務',
+    resumeTasks: '繼續任務',
+    actionCountdown: '等待中 {{remaining}}s / {{total}}s',
+    cancelPowerAction: '取消電源操作',
     // 自動連接相关
     autoConnect: {
       searching: '搜尋裝置...',
@@ -210,6 +235,7 @@ export default {
       connectFailed: '自動連接失敗',
       resourceFailed: '資源載入失敗',
       startFailed: '任務啟動失敗',
+      waitingAgents: '等待 Agent {{agents}}...',
       needConfig: '請先連接裝置並載入資源，或在連接面板儲存裝置設定',
     },
   },
@@ -396,6 +422,12 @@ export default {
       agentConnected: 'Agent 已連接',
       agentDisconnected: 'Agent 已中斷',
       agentFailed: 'Agent 啟動失敗',
+      agentProgressStarting: 'Agent #{{index}} 啟動中...',
+      agentProgressWaiting: 'Agent #{{index}} 等待外部 agent 連線：{{address}}',
+      agentProgressConnecting: 'Agent #{{index}} 連線中...',
+      agentProgressConnected: 'Agent #{{index}} 已連線',
+      agentProgressDisconnected: 'Agent #{{index}} 已斷開，等待重新連線...',
+      agentProgressFailed: 'Agent #{{index}} 啟動失敗：{{error}}',
       // 快捷鍵
       hotkeyDetected: '偵測到快捷鍵：{{combo}}（{{action}}）',
       hotkeyActionStart: '開始任務',
@@ -764,4 +796,113 @@ export default {
     invalid: '代理位址格式不正確',
     examples: '示例格式',
   },
+  // 通知管道
+  notificationChannels: {
+    title: '通知管道',
+    hint: '管道可在 MXU_NOTIFY 的 channel 參數中以 ID 或名稱引用，也可在執行結束時自動通知。',
+    secretsHint: '密碼和 Token 以明文儲存在資料目錄中，請勿將資料目錄分享給他人',
+    empty: '尚無通知管道',
+    loadFailed: '載入通知管道失敗：{{error}}',
+    name: '名稱',
+    add: '新增管道',
+    save: '儲存',
+    saved: '已儲存',
+    test: '傳送測試通知',
+    testSucceeded: '測試通知已傳送',
+    testMessage: '這是一則來自 MXU 的測試通知',
+    delete: '刪除',
+    events: '自動通知時機',
+    eventNames: {
+      run_succeeded: '任務全部成功',
+      run_failed: '存在失敗的任務',
+    },
+    types: {
+      smtp: '郵件（SMTP）',
+      telegram: 'Telegram',
+      discord: 'Discord',
+      slack: 'Slack',
+      bark: 'Bark',
+      server_chan: 'Server 醬',
+      ntfy: 'ntfy',
+      http: 'HTTP 請求',
+    },
+    fields: {
+      host: 'SMTP 伺服器',
+      port: '連接埠',
+      security: '加密方式',
+      username: '使用者名稱',
+      password: '密碼',
+      from: '寄件者',
+      to: '收件者（逗號分隔）',
+      botToken: 'Bot Token',
+      chatId: 'Chat ID',
+      apiBase: 'API 位址',
+      webhookUrl: 'Webhook 位址',
+      deviceKey: '裝置 Key',
+      server: '伺服器位址',
+      group: '分組',
+      sound: '提示音',
+      sendKey: 'SendKey',
+      topic: '主題',
+      token: '存取權杖',
+      priority: '優先順序（1-5）',
+      url: '請求位址',
+      method: '請求方法',
+      headers: '請求標頭（每行一個 "Name: value"）',
+      body: '請求內容範本（{title}、{body}）',
+    },
+    messages: {
+      runSucceeded: '實例 {instance} 的任務已全部完成',
+      runFailed: '實例 {instance} 的任務執行失敗',
+      runFailedWithError: '實例 {instance} 的任務執行失敗：{error}',
+      powerCountdown: '將在 {seconds} 秒後{action}，可在 MXU 中取消',
+      powerCancelled: '已取消{action}',
+      powerSkipped: '其他實例仍在執行，已略過{action}',
+      powerActions: {
+        shutdown: '關機',
+        restart: '重新啟動',
+        screenoff: '關閉螢幕',
+        sleep: '睡眠',
+        hibernate: '休眠',
+        logoff: '登出',
+      },
+    },
+  },
+  persistentVariables: {
+    title: '持久化變數',
+    hint: '由 MXU_VARIABLE 跨執行保存的變數。值按 JSON 解析，無法解析的文字保存為字串。',
+    refresh: '重新整理',
+    empty: '尚無持久化變數',
+    global: '全域',
+    name: '變數名稱',
+    value: '值',
+    resetHour: '重置時間',
+    add: '新增',
+    save: '儲存',
+    delete: '刪除',
+    dailyReset: '每天 {{hour}}:00 重置為 {{value}}',
+    updatedAt: '最後修改：{{time}}',
+    invalidResetHour: '重置時間必須是 0-23 的整數',
+  },
+  plugins: {
+    title: '外掛',
+    hint:
+      'plugins 資料夾中的外掛會在 MXU 內執行原生程式碼，只有在此允許後才會載入。請只允許可信的外掛，修改後需重新啟動 MXU 才會生效。',
+    refresh: '重新整理',
+    empty: 'plugins 資料夾中沒有外掛',
+    loadFailed: '載入外掛列表失敗：{{error}}',
+    allow: '允許',
+    actions: '動作：{{actions}}',
+    restartRequired: '重新啟動 MXU 後生效',
+    kinds: {
+      library: '動態函式庫',
+      executable: '執行檔',
+    },
+    status: {
+      loading: '載入中',
+      loaded: '已載入',
+      failed: '載入失敗',
+      not_loaded: '未載入',
+    },
+  },
 };
@@ -12,6 +12,14 @@ import type {
   AgentConfig,
   TaskConfig,
   InstanceRuntimeInfo,
+  AgentOutputEntry,
+  AgentOutputPage,
+  AgentProgressEvent,
+  ActionCountdownEvent,
+  NotificationChannel,
+  NotificationMessages,
+  PersistentVariables,
+  PluginInfo,
 } from '@/types/maa';
 import { loggers } from '@/utils/logger';
 import { isTauri } from '@/utils/paths';
@@ -288,6 +296,26 @@ export const maaService = {
     log.info('停止任务请求已发送');
   },
 
+  /**
+   * 暂停任务（在下一个节点执行动作前暂停，恢复后从该节点继续）
+   * @param instanceId 实例 ID
+   */
+  async pauseTask(instanceId: string): Promise<void> {
+    log.info('暂停任务, 实例:', instanceId);
+    if (!isTauri()) return;
+    await invoke('maa_pause_task', { instanceId });
+  },
+
+  /**
+   * 恢复已暂停的任务
+   * @param instanceId 实例 ID
+   */
+  async resumeTask(instanceId: string): Promise<void> {
+    log.info('恢复任务, 实例:', instanceId);
+    if (!isTauri()) return;
+    await invoke('maa_resume_task', { instanceId });
+  },
+
   /**
    * 覆盖已提交任务的 Pipeline 配置（用于运行中修改尚未执行的任务选项）
    * @param instanceId 实例 ID
@@ -441,6 +469,41 @@ export const maaService = {
     });
   },
 
+  /**
+   * 监听 Agent 启动进度事件（maa-agent-progress）
+   * @param callback 回调函数
+   * @returns 取消监听的函数
+   */
+  async onAgentProgress(callback: (event: AgentProgressEvent) => void): Promise<UnlistenFn> {
+    if (!isTauri()) return () => {};
+    return await listen<AgentProgressEvent>('maa-agent-progress', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  /**
+   * 监听 MXU_SLEEP / MXU_WAITUNTIL 等等待类动作的倒计时事件（maa-action-countdown）
+   * @param callback 回调函数
+   * @returns 取消监听的函数
+   */
+  async onActionCountdown(callback: (event: ActionCountdownEvent) => void): Promise<UnlistenFn> {
+    if (!isTauri()) return () => {};
+    return await listen<ActionCountdownEvent>('maa-action-countdown', (event) => {
+      callback(event.payload);
+    });
+  },
+
+  /**
+   * 取消倒计时中的 MXU_POWER 操作
+   * @param id 倒计时事件中的 cancel_id，不传时取消全部
+   * @returns 是否有操作被取消
+   */
+  async cancelPowerAction(id?: number): Promise<boolean> {
+    log.info('取消电源操作:', id ?? 'all');
+    if (!isTauri()) return false;
+    return await invoke<boolean>('cancel_power_action', { id: id ?? null });
+  },
+
   /**
    * 等待单个操作完成的一次性回调（适用于截图等需要立即获取结果的场景）
    * 注意：此函数会阻塞调用者直到回调到达，适合在非 UI 线程或循环中使用
@@ -500,12 +563,14 @@ export const maaService = {
         resource_loaded: boolean;
         tasker_inited: boolean;
         is_running: boolean;
+        is_paused: boolean;
         task_ids: number[];
       }>('maa_get_instance_state', { instanceId });
       return {
         connectionStatus: state.connected ? 'Connected' : 'Disconnected',
         resourceLoaded: state.resource_loaded,
         isRunning: state.is_running,
+        isPaused: state.is_paused,
         currentTaskId: null,
         taskIds: state.task_ids,
       };
@@ -525,6 +590,7 @@ export const maaService = {
         resourceLoaded: boolean;
         taskerInited: boolean;
         isRunning: boolean;
+        isPaused: boolean;
         taskIds: number[];
       }
     >;
@@ -541,6 +607,7 @@ export const maaService = {
             resource_loaded: boolean;
             tasker_inited: boolean;
             is_running: boolean;
+            is_paused: boolean;
             task_ids: number[];
           }
         >;
@@ -556,6 +623,7 @@ export const maaService = {
           resourceLoaded: boolean;
           taskerInited: boolean;
           isRunning: boolean;
+          isPaused: boolean;
           taskIds: number[];
         }
       > = {};
@@ -566,6 +634,7 @@ export const maaService = {
           resourceLoaded: state.resource_loaded,
           taskerInited: state.tasker_inited,
           isRunning: state.is_running,
+          isPaused: state.is_paused,
           taskIds: state.task_ids,
         };
       }
@@ -605,6 +674,51 @@ export const maaService = {
     }
   },
 
+  /**
+   * 拉取实例最近的 agent 输出（用于刷新后回填日志）
+   * @param instanceId 实例 ID
+   * @param cursor 上次返回的 nextCursor，首次传 0
+   * @param limit 最多返回条数
+   */
+  async getAgentOutput(
+    instanceId: string,
+    cursor = 0,
+    limit?: number,
+  ): Promise<AgentOutputPage | null> {
+    if (!isTauri()) return null;
+    try {
+      const page = await invoke<{
+        entries: {
+          seq: number;
+          timestamp: number;
+          agent_index: number;
+          stream: string;
+          line: string;
+          record: AgentOutputEntry['record'];
+        }[];
+        next_cursor: number;
+        has_more: boolean;
+        truncated: boolean;
+      }>('maa_get_agent_output', { instanceId, cursor, limit: limit ?? null });
+      return {
+        entries: page.entries.map((e) => ({
+          seq: e.seq,
+          timestamp: e.timestamp,
+          agentIndex: e.agent_index,
+          stream: e.stream,
+          line: e.line,
+          record: e.record,
+        })),
+        nextCursor: page.next_cursor,
+        hasMore: page.has_more,
+        truncated: page.truncated,
+      };
+    } catch (err) {
+      log.error('获取 agent 输出失败:', err);
+      return null;
+    }
+  },
+
   /**
    * 检查当前进程是否以管理员权限运行
    */
@@ -677,6 +791,100 @@ export const maaService = {
       throw err;
     }
   },
+
+  /**
+   * 获取通知渠道配置
+   */
+  async getNotificationChannels(): Promise<NotificationChannel[]> {
+    if (!isTauri()) return [];
+    return await invoke<NotificationChannel[]>('get_notification_channels');
+  },
+
+  /**
+   * 保存通知渠道配置
+   * @param channels 全部渠道
+   */
+  async saveNotificationChannels(channels: NotificationChannel[]): Promise<void> {
+    if (!isTauri()) return;
+    log.info('保存通知渠道:', channels.length);
+    await invoke('save_notification_channels', { channels });
+  },
+
+  /**
+   * 通过指定渠道发送测试通知
+   * @param channel 渠道配置（可为未保存的草稿）
+   * @param message 测试通知内容
+   */
+  async testNotificationChannel(channel: NotificationChannel, message: string): Promise<void> {
+    if (!isTauri()) {
+      throw new Error('此功能仅在 Tauri 环境中可用');
+    }
+    log.info('发送测试通知, 渠道:', channel.id);
+    await invoke('test_notification_channel', { channel, message });
+  },
+
+  /**
+   * 设置运行结束自动通知的文案（随界面语言切换）
+   * @param messages 本地化文案
+   */
+  async setNotificationMessages(messages: NotificationMessages): Promise<void> {
+    if (!isTauri()) return;
+    await invoke('set_notification_messages', { messages });
+  },
+
+  /**
+   * 获取所有持久化变量（已按每日重置规则刷新）
+   */
+  async getPersistentVariables(): Promise<PersistentVariables> {
+    if (!isTauri()) return { global: {}, instances: {} };
+    return await invoke<PersistentVariables>('get_persistent_variables');
+  },
+
+  /**
+   * 设置持久化变量
+   * @param instanceId 实例 ID，为 null 时设置全局变量
+   * @param name 变量名
+   * @param value 变量值
+   * @param dailyResetHour 每日重置时间（0-23），为 null 时保持原设置
+   * @param resetValue 每日重置后的值，为 null 时保持原设置
+   */
+  async setPersistentVariable(
+    instanceId: string | null,
+    name: string,
+    value: unknown,
+    dailyResetHour: number | null = null,
+    resetValue: unknown = null,
+  ): Promise<void> {
+    if (!isTauri()) return;
+    log.info('设置持久化变量:', instanceId ?? 'global', name, value);
+    await invoke('set_persistent_variable', {
+      instanceId,
+      name,
+      value,
+      dailyResetHour,
+      resetValue,
+    });
+  },
+
+  /**
+   * 删除持久化变量
+   * @param instanceId 实例 ID，为 null 时删除全局变量
+   * @param name 变量名
+   * @returns 变量是否存在
+   */
+  async deletePersistentVariable(instanceId: string | null, name: string): Promise<boolean> {
+    if (!isTauri()) return false;
+    log.info('删除持久化变量:', instanceId ?? 'global', name);
+    return await invoke<boolean>('delete_persistent_variable', { instanceId, name });
+  },
+
+  /**
+   * 获取插件目录中的所有插件及其加载状态
+   */
+  async getPlugins(): Promise<PluginInfo[]> {
+    if (!isTauri()) return [];
+    return await invoke<PluginInfo[]>('get_plugins');
+  },
 };
 
 export default maaService;
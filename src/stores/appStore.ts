@@ -972,6 +972,8 @@ export const useAppStore = create<AppState>()(
         autoStartRemovedInstanceName: config.settings.autoStartRemovedInstanceName,
         minimizeToTray: config.settings.minimizeToTray ?? false,
         gamePath: config.settings.gamePath ?? '',
+        fileActionRoots: config.settings.fileActionRoots ?? [],
+        allowedPlugins: config.settings.allowedPlugins ?? [],
         onboardingCompleted: config.settings.onboardingCompleted ?? false,
         preActionConnectDelaySec: config.settings.preActionConnectDelaySec ?? 5,
         hotkeys: config.settings.hotkeys ?? {
@@ -1000,6 +1002,24 @@ export const useAppStore = create<AppState>()(
           });
         });
       }
+
+      // 同步 MXU_FILE 允许目录到后端
+      const fileActionRoots = config.settings.fileActionRoots ?? [];
+      if (fileActionRoots.length > 0) {
+        import('@tauri-apps/api/core').then(({ invoke }) => {
+          invoke('set_file_action_roots', { roots: fileActionRoots }).catch((err) => {
+            loggers.app.error('同步文件操作允许目录失败:', err);
+          });
+        });
+      }
+
+      // 同步允许的插件到后端（首次同步时后端开始加载插件，列表为空时也需同步）
+      const allowedPlugins = config.settings.allowedPlugins ?? [];
+      import('@tauri-apps/api/core').then(({ invoke }) => {
+        invoke('set_allowed_plugins', { plugins: allowedPlugins }).catch((err) => {
+          loggers.app.error('同步插件设置失败:', err);
+        });
+      });
     },
 
     // MaaFramework 状态
@@ -1152,6 +1172,7 @@ export const useAppStore = create<AppState>()(
             return {
               ...instance,
               isRunning,
+              isPaused: isRunning && backendState.isPaused,
             };
           }
           return instance;
@@ -1286,6 +1307,31 @@ export const useAppStore = create<AppState>()(
     gamePath: '',
     setGamePath: (path) => set({ gamePath: path }),
 
+    // MXU_FILE 动作额外允许访问的目录
+    fileActionRoots: [],
+    setFileActionRoots: async (roots) => {
+      set({ fileActionRoots: roots });
+      // 同步到后端
+      try {
+        const { invoke } = await import('@tauri-apps/api/core');
+        await invoke('set_file_action_roots', { roots });
+      } catch (err) {
+        loggers.app.error('设置文件操作允许目录失败:', err);
+      }
+    },
+
+    // 允许加载的插件目录（修改后需重启生效）
+    allowedPlugins: [],
+    setAllowedPlugins: async (plugins) => {
+      set({ allowedPlugins: plugins });
+      try {
+        const { invoke } = await import('@tauri-apps/api/core');
+        await invoke('set_allowed_plugins', { plugins });
+      } catch (err) {
+        loggers.app.error('设置允许的插件失败:', err);
+      }
+    },
+
     // 新用户引导
     onboardingCompleted: false,
     setOnboardingCompleted: (completed) => set({ onboardingCompleted: completed }),
@@ -1668,6 +1714,8 @@ function generateConfig(): MxuConfig {
       autoStartRemovedInstanceName: state.autoStartRemovedInstanceName,
       minimizeToTray: state.minimizeToTray,
       gamePath: state.gamePath,
+      fileActionRoots: state.fileActionRoots,
+      allowedPlugins: state.allowedPlugins,
       onboardingCompleted: state.onboardingCompleted,
       preActionConnectDelaySec: state.preActionConnectDelaySec,
       hotkeys: state.hotkeys,
@@ -1728,6 +1776,8 @@ useAppStore.subscribe(
     autoStartRemovedInstanceName: state.autoStartRemovedInstanceName,
     minimizeToTray: state.minimizeToTray,
     gamePath: state.gamePath,
+    fileActionRoots: state.fileActionRoots,
+    allowedPlugins: state.allowedPlugins,
     onboardingCompleted: state.onboardingCompleted,
     hotkeys: state.hotkeys,
     recentlyClosed: state.recentlyClosed,
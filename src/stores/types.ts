@@ -244,6 +244,7 @@ export interface AppState {
         resourceLoaded: boolean;
         taskerInited: boolean;
         isRunning: boolean;
+        isPaused: boolean;
         taskIds: number[];
       }
     >;
@@ -324,6 +325,14 @@ export interface AppState {
   gamePath: string;
   setGamePath: (path: string) => void;
 
+  // MXU_FILE 动作额外允许访问的目录
+  fileActionRoots: string[];
+  setFileActionRoots: (roots: string[]) => void;
+
+  // 允许加载的插件目录
+  allowedPlugins: string[];
+  setAllowedPlugins: (plugins: string[]) => void;
+
   // 启动后自动执行的实例 ID
   autoStartInstanceId: string | undefined;
   setAutoStartInstanceId: (id: string | undefined) => void;
@@ -403,7 +412,11 @@ export interface AppState {
 
   // 日志管理
   instanceLogs: Record<string, LogEntry[]>;
-  addLog: (instanceId: string, log: Omit<LogEntry, 'id' | 'timestamp'>) => void;
+  /** 添加日志，`timestamp` 省略时为当前时间（回填历史输出时传入原始时间） */
+  addLog: (
+    instanceId: string,
+    log: Omit<LogEntry, 'id' | 'timestamp'> & { timestamp?: Date },
+  ) => void;
   clearLogs: (instanceId: string) => void;
 
   // 回调 ID 与名称的映射
@@ -136,6 +136,10 @@ export interface AppSettings {
   preActionConnectDelaySec?: number;
   /** 游戏可执行文件路径（用于未找到窗口时自动拉起游戏） */
   gamePath?: string;
+  /** MXU_FILE 动作额外允许访问的目录（程序目录与数据目录始终允许） */
+  fileActionRoots?: string[];
+  /** 允许加载的插件目录（插件会运行原生代码，默认不加载任何插件） */
+  allowedPlugins?: string[];
 }
 
 // MXU 配置文件完整结构
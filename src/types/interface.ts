@@ -30,6 +30,13 @@ export interface AgentConfig {
   identifier?: string;
   /** 连接超时时间（毫秒），-1 表示无限等待 */
   timeout?: number;
+  /**
+   * 附加模式：不启动子进程，等待已在运行的 agent 通过 identifier 或 tcp_port 连接，
+   * 运行中断线时按 timeout 等待其重新连接
+   */
+  attach?: boolean;
+  /** 附加模式下使用的固定 TCP 端口（优先于 identifier），仅本机，远程 agent 需端口转发 */
+  tcp_port?: number;
 }
 
 /**
@@ -221,6 +228,8 @@ export interface Instance {
   savedDevice?: SavedDeviceInfo;
   selectedTasks: SelectedTask[];
   isRunning: boolean;
+  // 任务是否已暂停（运行中有效）
+  isPaused?: boolean;
   // 定时执行策略列表
   schedulePolicies?: SchedulePolicy[];
   preAction?: ActionConfig;
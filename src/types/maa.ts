@@ -74,11 +74,231 @@ export interface InstanceRuntimeInfo {
   connectionStatus: ConnectionStatus;
   resourceLoaded: boolean;
   isRunning: boolean;
+  /** 任务是否已暂停 */
+  isPaused: boolean;
   currentTaskId: number | null;
   /** 当前运行的任务 ID 列表 */
   taskIds: number[];
 }
 
+/** Agent 结构化日志记录（对应 agent stdout 上的 JSON 行协议） */
+export interface AgentLogRecord {
+  level: 'trace' | 'debug' | 'info' | 'warn' | 'error';
+  message: string;
+  node: string | null;
+  fields: Record<string, unknown>;
+  image: string | null;
+}
+
+/** 后端缓冲的单条 agent 输出 */
+export interface AgentOutputEntry {
+  /** 单调递增序号 */
+  seq: number;
+  /** 毫秒时间戳 */
+  timestamp: number;
+  agentIndex: number;
+  stream: string;
+  line: string;
+  record: AgentLogRecord | null;
+}
+
+/** agent 输出拉取结果 */
+export interface AgentOutputPage {
+  entries: AgentOutputEntry[];
+  /** 下次拉取时传入的游标 */
+  nextCursor: number;
+  hasMore: boolean;
+  /** 游标之后是否有记录因缓冲已满被丢弃 */
+  truncated: boolean;
+}
+
+/** MXU_SLEEP / MXU_WAITUNTIL / MXU_POWER 等待倒计时事件（maa-action-countdown） */
+export interface ActionCountdownEvent {
+  task_id: number;
+  /** 动作名称，如 MXU_SLEEP */
+  action: string;
+  /** 当前 pipeline 节点名 */
+  node: string;
+  /** 剩余秒数，0 表示等待结束 */
+  remaining_secs: number;
+  total_secs: number;
+  /** MXU_POWER 待执行操作的 ID，可传给 cancelPowerAction 取消；不可取消时为 null */
+  cancel_id?: number | null;
+}
+
+/** Agent 启动进度事件（maa-agent-progress） */
+export interface AgentProgressEvent {
+  instance_id: string;
+  agent_index: number;
+  /**
+   * starting / waiting（附加模式等待外部 agent）/ connecting / connected / failed，
+   * 附加模式运行中断线时为 disconnected，重连成功后再次为 connected
+   */
+  stage: 'starting' | 'waiting' | 'connecting' | 'connected' | 'disconnected' | 'failed';
+  /** waiting 时为连接地址，failed 时为错误信息 */
+  message: string | null;
+}
+
+/** MXU_LOG 日志事件（maa-action-log），日志字段与 agent 结构化日志相同 */
+export interface ActionLogEvent {
+  instance_id: string;
+  task_id: number;
+  level: 'trace' | 'debug' | 'info' | 'warn' | 'error';
+  message: string;
+  node: string | null;
+  fields: Record<string, unknown>;
+  /** 附带的截图路径 */
+  image: string | null;
+}
+
+/** 运行历史中的一条日志（由 MXU_LOG 写入） */
+export interface RunLogEntry {
+  time: string;
+  level: 'trace' | 'debug' | 'info' | 'warn' | 'error';
+  node: string;
+  message: string;
+  image?: string | null;
+}
+
+/** 一轮运行的历史记录（get_run_history） */
+export interface RunHistoryEntry {
+  instance_id: string;
+  started_at: string;
+  finished_at: string;
+  result: 'succeeded' | 'failed' | 'stopped';
+  last_error: string;
+  logs: RunLogEntry[];
+}
+
+/** MXU_KILLPROC 结果事件（maa-killproc-result） */
+export interface KillProcResultEvent {
+  task_id: number;
+  node: string;
+  /** 是否仅报告匹配结果 */
+  dry_run: boolean;
+  /** 匹配到的 PID */
+  matched: number[];
+  /** 已退出或被结束的 PID */
+  affected: number[];
+  /** 无法结束的 PID */
+  failed: number[];
+}
+
+/** 持久化变量（get_persistent_variables / set_persistent_variable） */
+export interface PersistentVariable {
+  value: unknown;
+  /** 每日重置时间（本地时间 0-23 点），为空表示不自动重置 */
+  daily_reset_hour?: number | null;
+  /** 每日重置后的值，默认 0 */
+  reset_value?: unknown;
+  /** 当前值所属的重置周期，如 2026-10-18 */
+  period?: string | null;
+  /** 最后修改时间（RFC 3339） */
+  updated_at?: string | null;
+}
+
+/** 持久化变量存储 */
+export interface PersistentVariables {
+  global: Record<string, PersistentVariable>;
+  /** 实例 ID -> 变量 */
+  instances: Record<string, Record<string, PersistentVariable>>;
+}
+
+/** 通知渠道自动触发的运行事件 */
+export type NotificationEvent = 'run_succeeded' | 'run_failed';
+
+/** 通知渠道类型及其参数 */
+export type NotificationChannelKind =
+  | {
+      type: 'smtp';
+      host: string;
+      port?: number | null;
+      /** tls（默认）/ starttls / none */
+      security?: string | null;
+      username?: string | null;
+      password?: string | null;
+      from: string;
+      to: string[];
+    }
+  | { type: 'telegram'; bot_token: string; chat_id: string; api_base?: string | null }
+  | { type: 'discord'; webhook_url: string }
+  | { type: 'slack'; webhook_url: string }
+  | {
+      type: 'bark';
+      device_key: string;
+      server?: string | null;
+      group?: string | null;
+      sound?: string | null;
+    }
+  | { type: 'server_chan'; send_key: string; server?: string | null }
+  | {
+      type: 'ntfy';
+      topic: string;
+      server?: string | null;
+      token?: string | null;
+      priority?: number | null;
+    }
+  | {
+      type: 'http';
+      url: string;
+      method?: string | null;
+      headers?: Record<string, string> | null;
+      /** 请求体模板，可使用 {title}、{body} 占位符 */
+      body?: string | null;
+    };
+
+/** 通知渠道配置 */
+export type NotificationChannel = {
+  /** 渠道 ID（MXU_NOTIFY 的 channel 参数可使用 ID 或名称） */
+  id: string;
+  name: string;
+  enabled: boolean;
+  events: NotificationEvent[];
+} & NotificationChannelKind;
+
+/** 运行结束自动通知的文案，可使用 {instance}、{error} 占位符 */
+export interface NotificationMessages {
+  title: string;
+  run_succeeded: string;
+  run_failed: string;
+  run_failed_with_error: string;
+  /** MXU_POWER 通知，可使用 {action}、{seconds} 占位符 */
+  power_countdown: string;
+  power_cancelled: string;
+  power_skipped: string;
+  /** 电源操作名称，键为 shutdown / restart / screenoff / sleep / hibernate / logoff */
+  power_actions: Record<string, string>;
+}
+
+/** MXU 内置动作 / 识别的参数定义 */
+export interface MxuActionSchema {
+  /** 注册名，如 MXU_SLEEP_ACTION */
+  name: string;
+  kind: 'action' | 'recognition';
+  /** 参数的 JSON Schema */
+  schema: Record<string, unknown>;
+  /** 来源插件名，内置动作为 null */
+  plugin?: string | null;
+  /** 插件声明的动作说明 */
+  description?: string | null;
+}
+
+/** 插件目录中的一个插件（get_plugins） */
+export interface PluginInfo {
+  /** 插件目录，作为允许列表中的标识 */
+  dir: string;
+  name: string;
+  /** plugin.json 无效时为 null */
+  kind: 'library' | 'executable' | null;
+  allowed: boolean;
+  status: 'loading' | 'loaded' | 'failed' | 'not_loaded';
+  /** 已注册的动作名 */
+  actions: string[];
+  error?: string | null;
+  /** 允许设置与本次启动加载的插件不一致，需重启生效 */
+  restart_required: boolean;
+}
+
 /** Win32 截图方法 */
 export const Win32ScreencapMethod = {
   None: 0n,
@@ -154,6 +374,13 @@ export interface AgentConfig {
   identifier?: string;
   /** 连接超时时间（毫秒），-1 表示无限等待 */
   timeout?: number;
+  /**
+   * 附加模式：不启动子进程，等待已在运行的 agent 通过 identifier 或 tcp_port 连接，
+   * 运行中断线时按 timeout 等待其重新连接
+   */
+  attach?: boolean;
+  /** 附加模式下使用的固定 TCP 端口（优先于 identifier），仅本机，远程 agent 需端口转发 */
+  tcp_port?: number;
 }
 
 /** 任务配置 */
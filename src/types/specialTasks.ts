@@ -137,11 +137,18 @@ const MXU_WAITUNTIL_OPTION_DEF_INTERNAL: InputOption = {
       pipeline_type: 'string',
       input_type: 'time',
     },
+    {
+      name: 'timezone',
+      label: 'specialTask.waitUntil.timezoneLabel',
+      default: '',
+      pipeline_type: 'string',
+    },
   ],
   pipeline_override: {
     [MXU_WAITUNTIL_ENTRY]: {
       custom_action_param: {
         target_time: '{target_time}',
+        timezone: '{timezone}',
       },
     },
   },
@@ -181,12 +188,46 @@ const MXU_LAUNCH_INPUT_OPTION_DEF_INTERNAL: InputOption = {
       pipeline_type: 'string',
       placeholder: 'specialTask.launch.argsPlaceholder',
     },
+    {
+      name: 'cwd',
+      label: 'specialTask.launch.cwdLabel',
+      default: '',
+      pipeline_type: 'string',
+      placeholder: 'specialTask.launch.cwdPlaceholder',
+    },
+    {
+      name: 'ready_process',
+      label: 'specialTask.launch.readyProcessLabel',
+      default: '',
+      pipeline_type: 'string',
+      placeholder: 'specialTask.launch.readyProcessPlaceholder',
+    },
+    {
+      name: 'ready_port',
+      label: 'specialTask.launch.readyPortLabel',
+      default: '',
+      pipeline_type: 'string',
+      placeholder: 'specialTask.launch.readyPortPlaceholder',
+    },
+    {
+      name: 'ready_window',
+      label: 'specialTask.launch.readyWindowLabel',
+      default: '',
+      pipeline_type: 'string',
+      placeholder: 'specialTask.launch.readyWindowPlaceholder',
+    },
   ],
   pipeline_override: {
     [MXU_LAUNCH_ENTRY]: {
       custom_action_param: {
         program: '{program}',
         args: '{args}',
+        cwd: '{cwd}',
+        ready: {
+          process: '{ready_process}',
+          port: '{ready_port}',
+          window: '{ready_window}',
+        },
       },
     },
   },
@@ -378,7 +419,11 @@ const MXU_POWER_TASK_DEF_INTERNAL: TaskItem = {
   name: MXU_POWER_TASK_NAME,
   label: 'specialTask.power.label',
   entry: MXU_POWER_ENTRY,
-  option: ['__MXU_POWER_OPTION__'],
+  option: [
+    '__MXU_POWER_OPTION__',
+    '__MXU_POWER_COUNTDOWN_OPTION__',
+    '__MXU_POWER_IDLE_OPTION__',
+  ],
   pipeline_override: {
     [MXU_POWER_ENTRY]: {
       action: 'Custom',
@@ -387,7 +432,7 @@ const MXU_POWER_TASK_DEF_INTERNAL: TaskItem = {
   },
 };
 
-// MXU_POWER 下拉选项定义（关机/重启/息屏/睡眠）
+// MXU_POWER 下拉选项定义（关机/重启/息屏/睡眠/休眠/注销）
 const MXU_POWER_OPTION_DEF_INTERNAL: SelectOption = {
   type: 'select',
   label: 'specialTask.power.optionLabel',
@@ -436,10 +481,89 @@ const MXU_POWER_OPTION_DEF_INTERNAL: SelectOption = {
         },
       },
     },
+    {
+      name: 'hibernate',
+      label: 'specialTask.power.hibernate',
+      pipeline_override: {
+        [MXU_POWER_ENTRY]: {
+          custom_action_param: {
+            power_action: 'hibernate',
+          },
+        },
+      },
+    },
+    {
+      name: 'logoff',
+      label: 'specialTask.power.logoff',
+      pipeline_override: {
+        [MXU_POWER_ENTRY]: {
+          custom_action_param: {
+            power_action: 'logoff',
+          },
+        },
+      },
+    },
   ],
   default_case: 'shutdown',
 };
 
+// MXU_POWER 倒计时选项定义
+const MXU_POWER_COUNTDOWN_OPTION_DEF_INTERNAL: InputOption = {
+  type: 'input',
+  label: 'specialTask.power.countdownOptionLabel',
+  inputs: [
+    {
+      name: 'countdown',
+      label: 'specialTask.power.countdownLabel',
+      // 默认不倒计时，与新增此选项前的行为一致
+      default: '0',
+      pipeline_type: 'int',
+      verify: '^\\d+$',
+      pattern_msg: 'specialTask.power.countdownError',
+    },
+  ],
+  pipeline_override: {
+    [MXU_POWER_ENTRY]: {
+      custom_action_param: {
+        countdown: '{countdown}',
+      },
+    },
+  },
+};
+
+// MXU_POWER 守卫选项定义（仅在没有其它实例运行时执行）
+const MXU_POWER_IDLE_OPTION_DEF_INTERNAL: SwitchOption = {
+  type: 'switch',
+  label: 'specialTask.power.idleLabel',
+  description: 'specialTask.power.idleDescription',
+  cases: [
+    {
+      name: 'Yes',
+      label: 'specialTask.power.idleYes',
+      pipeline_override: {
+        [MXU_POWER_ENTRY]: {
+          custom_action_param: {
+            only_if_idle: true,
+          },
+        },
+      },
+    },
+    {
+      name: 'No',
+      label: 'specialTask.power.idleNo',
+      pipeline_override: {
+        [MXU_POWER_ENTRY]: {
+          custom_action_param: {
+            only_if_idle: false,
+          },
+        },
+      },
+    },
+  ],
+  // 默认关闭，与新增此选项前的行为一致
+  default_case: 'No',
+};
+
 /**
  * MXU 特殊任务注册表
  * 所有 MXU 内置特殊任务都在这里注册
@@ -511,6 +635,8 @@ export const MXU_SPECIAL_TASKS: Record<string, MxuSpecialTaskDefinition> = {
     taskDef: MXU_POWER_TASK_DEF_INTERNAL,
     optionDefs: {
       __MXU_POWER_OPTION__: MXU_POWER_OPTION_DEF_INTERNAL,
+      __MXU_POWER_COUNTDOWN_OPTION__: MXU_POWER_COUNTDOWN_OPTION_DEF_INTERNAL,
+      __MXU_POWER_IDLE_OPTION__: MXU_POWER_IDLE_OPTION_DEF_INTERNAL,
     },
     iconName: 'Power',
     iconColorClass: 'text-warning/80',
@@ -0,0 +1,125 @@
+/**
+ * MXU 特殊任务参数校验
+ * 将 specialTasks.ts 中各任务及选项写入的 custom_action_param
+ * 与后端导出的参数 JSON Schema 对比，发现未知字段或缺少必填字段时输出警告
+ * （开发模式下启动时调用）
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { OptionDefinition } from '@/types/interface';
+import type { MxuActionSchema } from '@/types/maa';
+import { getAllMxuSpecialTasks } from '@/types/specialTasks';
+import { loggers } from './logger';
+
+const log = loggers.app;
+
+type JsonSchema = {
+  $ref?: string;
+  $defs?: Record<string, JsonSchema>;
+  properties?: Record<string, JsonSchema>;
+  required?: string[];
+  anyOf?: JsonSchema[];
+};
+
+type ParamObject = Record<string, unknown>;
+
+function isObject(value: unknown): value is ParamObject {
+  return typeof value === 'object' && value !== null && !Array.isArray(value);
+}
+
+/** 解析 $ref 以及 Option<T> 生成的 anyOf: [T, null] */
+function resolveSchema(schema: JsonSchema, root: JsonSchema): JsonSchema {
+  if (schema.$ref?.startsWith('#/$defs/')) {
+    const def = root.$defs?.[schema.$ref.slice('#/$defs/'.length)];
+    return def ? resolveSchema(def, root) : schema;
+  }
+  const objectVariant = schema.anyOf?.find((s) => resolveSchema(s, root).properties);
+  return objectVariant ? resolveSchema(objectVariant, root) : schema;
+}
+
+/** 从 pipeline_override 中取出入口节点的 custom_action_param */
+function paramOf(override: Record<string, unknown> | undefined, entry: string): ParamObject[] {
+  const node = override?.[entry];
+  return isObject(node) && isObject(node.custom_action_param) ? [node.custom_action_param] : [];
+}
+
+function optionParams(option: OptionDefinition, entry: string): ParamObject[] {
+  if (option.type === 'input') return paramOf(option.pipeline_override, entry);
+  return option.cases.flatMap((c) => paramOf(c.pipeline_override, entry));
+}
+
+/** 检查参数对象中的字段是否都在 Schema 中声明，返回问题列表 */
+function checkUnknownFields(
+  param: ParamObject,
+  schema: JsonSchema,
+  root: JsonSchema,
+  path: string,
+): string[] {
+  const resolved = resolveSchema(schema, root);
+  if (!resolved.properties) return [];
+  return Object.entries(param).flatMap(([key, value]) => {
+    const propSchema = resolved.properties?.[key];
+    if (!propSchema) return [`unknown field '${path}${key}'`];
+    return isObject(value) ? checkUnknownFields(value, propSchema, root, `${path}${key}.`) : [];
+  });
+}
+
+/**
+ * 对比特殊任务定义与后端参数 Schema
+ * @returns 问题列表，为空表示一致
+ */
+export function validateMxuSpecialTasks(schemas: MxuActionSchema[]): string[] {
+  const actions = new Map(schemas.filter((s) => s.kind === 'action').map((s) => [s.name, s]));
+  const problems: string[] = [];
+
+  for (const task of getAllMxuSpecialTasks()) {
+    const entryNode = task.taskDef.pipeline_override?.[task.entry];
+    const actionName = isObject(entryNode) ? entryNode.custom_action : undefined;
+    if (typeof actionName !== 'string') {
+      problems.push(`${task.taskName}: missing custom_action`);
+      continue;
+    }
+    const action = actions.get(actionName);
+    if (!action) {
+      problems.push(`${task.taskName}: backend has no action '${actionName}'`);
+      continue;
+    }
+
+    const root = action.schema as JsonSchema;
+    const params = [
+      ...paramOf(task.taskDef.pipeline_override, task.entry),
+      ...Object.values(task.optionDefs).flatMap((o) => optionParams(o, task.entry)),
+    ];
+    for (const param of params) {
+      for (const problem of checkUnknownFields(param, root, root, '')) {
+        problems.push(`${task.taskName}: ${problem}`);
+      }
+    }
+
+    const provided = new Set(params.flatMap((p) => Object.keys(p)));
+    for (const field of root.required ?? []) {
+      if (!provided.has(field)) {
+        problems.push(`${task.taskName}: required field '${field}' is never set`);
+      }
+    }
+  }
+
+  return problems;
+}
+
+/** 从后端获取参数 Schema 并校验特殊任务定义，结果写入日志 */
+export async function checkMxuSpecialTasksAgainstBackend(): Promise<void> {
+  try {
+    const schemas = await invoke<MxuActionSchema[]>('get_mxu_action_schemas');
+    const problems = validateMxuSpecialTasks(schemas);
+    if (problems.length === 0) {
+      log.debug('MXU 特殊任务定义与后端参数一致');
+      return;
+    }
+    for (const problem of problems) {
+      log.warn('MXU 特殊任务定义与后端参数不一致:', problem);
+    }
+  } catch (err) {
+    log.warn('获取 MXU 动作参数定义失败:', err);
+  }
+}
@@ -10,6 +10,7 @@ import { useAppStore, type LogType } from '@/stores/appStore';
 import { loggers } from '@/utils/logger';
 import i18n, { getInterfaceLangKey } from '@/i18n';
 import { getMxuSpecialTask } from '@/types/specialTasks';
+import type { ActionLogEvent, AgentLogRecord, AgentProgressEvent } from '@/types/maa';
 import {
   resolveI18nText,
   detectContentType,
@@ -466,12 +467,141 @@ function handleCallback(
   }
 }
 
+/** Agent 结构化日志事件载荷（maa-agent-log） */
+interface AgentLogPayload {
+  instance_id: string;
+  level: 'trace' | 'debug' | 'info' | 'warn' | 'error';
+  message: string;
+  node: string | null;
+  fields: Record<string, unknown>;
+  image: string | null;
+}
+
+/** 将 agent 日志级别映射为日志面板类型 */
+function agentLevelToLogType(level: AgentLogPayload['level']): LogType {
+  switch (level) {
+    case 'error':
+      return 'error';
+    case 'warn':
+      return 'warning';
+    default:
+      return 'agent';
+  }
+}
+
+/** 将结构化日志格式化为 Markdown 文本（节点前缀 + 附加字段 + 图片） */
+function formatAgentLogRecord(payload: AgentLogRecord): string {
+  let text = payload.node ? `[${payload.node}] ${payload.message}` : payload.message;
+  const fieldEntries = Object.entries(payload.fields ?? {});
+  if (fieldEntries.length > 0) {
+    const fieldsText = fieldEntries
+      .map(([key, value]) => `${key}=${typeof value === 'string' ? value : JSON.stringify(value)}`)
+      .join(', ');
+    text += ` (${fieldsText})`;
+  }
+  if (payload.image) {
+    text += `\n\n![](${payload.image.replace(/\\/g, '/')})`;
+  }
+  return text;
+}
+
+/** 将 MXU_LOG 日志级别映射为日志面板类型 */
+function actionLevelToLogType(level: ActionLogEvent['level']): LogType {
+  switch (level) {
+    case 'error':
+      return 'error';
+    case 'warn':
+      return 'warning';
+    default:
+      return 'info';
+  }
+}
+
+/**
+ * 监听 Agent 输出事件与 MXU_LOG 日志事件
+ */
 /**
- * 监听 Agent 输出事件
+ * 刷新页面后从后端回填实例缓存的 agent 输出（按游标分页拉取，保留原始时间）
+ * @param instanceId 实例 ID
  */
+export async function backfillAgentOutput(instanceId: string): Promise<void> {
+  const { addLog } = useAppStore.getState();
+  let cursor = 0;
+  for (;;) {
+    const page = await maaService.getAgentOutput(instanceId, cursor);
+    if (!page) return;
+
+    for (const entry of page.entries) {
+      const timestamp = new Date(entry.timestamp);
+      const record = entry.record;
+      if (!record) {
+        addLog(instanceId, { type: 'agent', message: entry.line, timestamp });
+        continue;
+      }
+      if (record.level === 'trace' || record.level === 'debug') continue;
+
+      const text = formatAgentLogRecord(record);
+      const basePath = useAppStore.getState().basePath;
+      const html = await markdownToHtmlWithLocalImages(text, basePath).catch(() => undefined);
+      addLog(instanceId, {
+        type: agentLevelToLogType(record.level),
+        message: text,
+        html,
+        timestamp,
+      });
+    }
+
+    if (!page.hasMore || page.nextCursor === cursor) return;
+    cursor = page.nextCursor;
+  }
+}
+
+/** 将 agent 启动进度格式化为日志条目 */
+function formatAgentProgress(event: AgentProgressEvent): { type: LogType; message: string } {
+  const index = event.agent_index + 1;
+  switch (event.stage) {
+    case 'starting':
+      return { type: 'info', message: i18n.t('logs.messages.agentProgressStarting', { index }) };
+    case 'waiting':
+      return {
+        type: 'info',
+        message: i18n.t('logs.messages.agentProgressWaiting', {
+          index,
+          address: event.message ?? '',
+        }),
+      };
+    case 'connecting':
+      return {
+        type: 'info',
+        message: i18n.t('logs.messages.agentProgressConnecting', { index }),
+      };
+    case 'connected':
+      return {
+        type: 'success',
+        message: i18n.t('logs.messages.agentProgressConnected', { index }),
+      };
+    case 'disconnected':
+      return {
+        type: 'warning',
+        message: i18n.t('logs.messages.agentProgressDisconnected', { index }),
+      };
+    case 'failed':
+      return {
+        type: 'error',
+        message: i18n.t('logs.messages.agentProgressFailed', {
+          index,
+          error: event.message ?? '',
+        }),
+      };
+  }
+}
+
 export function useMaaAgentLogger() {
   const { addLog } = useAppStore();
   const unlistenRef = useRef<(() => void) | null>(null);
+  const unlistenLogRef = useRef<(() => void) | null>(null);
+  const unlistenActionLogRef = useRef<(() => void) | null>(null);
+  const unlistenProgressRef = useRef<(() => void) | null>(null);
 
   useEffect(() => {
     let cancelled = false;
@@ -514,11 +644,69 @@ export function useMaaAgentLogger() {
           },
         );
 
+        // 监听 agent 结构化日志事件（按级别显示）
+        const unlistenLog = await listen<AgentLogPayload>('maa-agent-log', (event) => {
+          if (cancelled) return;
+
+          const payload = event.payload;
+          // trace/debug 仅写入后端日志，不在面板中显示
+          if (payload.level === 'trace' || payload.level === 'debug') return;
+
+          const type = agentLevelToLogType(payload.level);
+          const text = formatAgentLogRecord(payload);
+          const basePath = useAppStore.getState().basePath;
+          markdownToHtmlWithLocalImages(text, basePath)
+            .then((html) => {
+              if (cancelled) return;
+              addLog(payload.instance_id, { type, message: text, html });
+            })
+            .catch((err) => {
+              log.warn('Failed to render agent log:', err);
+              if (cancelled) return;
+              addLog(payload.instance_id, { type, message: text });
+            });
+        });
+
+        // 监听 MXU_LOG 动作写入的日志（与 agent 结构化日志格式相同）
+        const unlistenActionLog = await listen<ActionLogEvent>('maa-action-log', (event) => {
+          if (cancelled) return;
+
+          const payload = event.payload;
+          if (payload.level === 'trace' || payload.level === 'debug') return;
+
+          const type = actionLevelToLogType(payload.level);
+          const text = formatAgentLogRecord(payload);
+          const basePath = useAppStore.getState().basePath;
+          markdownToHtmlWithLocalImages(text, basePath)
+            .then((html) => {
+              if (cancelled) return;
+              addLog(payload.instance_id, { type, message: text, html });
+            })
+            .catch((err) => {
+              log.warn('Failed to render action log:', err);
+              if (cancelled) return;
+              addLog(payload.instance_id, { type, message: text });
+            });
+        });
+
+        // 监听 agent 启动进度，逐个显示各 agent 的连接状态
+        const unlistenProgress = await listen<AgentProgressEvent>('maa-agent-progress', (event) => {
+          if (cancelled) return;
+          const { type, message } = formatAgentProgress(event.payload);
+          addLog(event.payload.instance_id, { type, message });
+        });
+
         // 如果在等待期间组件已卸载，立即取消监听
         if (cancelled) {
           unlisten();
+          unlistenLog();
+          unlistenActionLog();
+          unlistenProgress();
         } else {
           unlistenRef.current = unlisten;
+          unlistenLogRef.current = unlistenLog;
+          unlistenActionLogRef.current = unlistenActionLog;
+          unlistenProgressRef.current = unlistenProgress;
         }
       } catch (err) {
         log.warn('Failed to setup agent output listener:', err);
@@ -533,6 +721,18 @@ export function useMaaAgentLogger() {
         unlistenRef.current();
         unlistenRef.current = null;
       }
+      if (unlistenLogRef.current) {
+        unlistenLogRef.current();
+        unlistenLogRef.current = null;
+      }
+      if (unlistenActionLogRef.current) {
+        unlistenActionLogRef.current();
+        unlistenActionLogRef.current = null;
+      }
+      if (unlistenProgressRef.current) {
+        unlistenProgressRef.current();
+        unlistenProgressRef.current = null;
+      }
     };
   }, [addLog]);
 }